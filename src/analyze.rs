@@ -0,0 +1,385 @@
+// Analyze-then-clean: estimate every clean-temp target before deleting anything
+//
+// 后台线程依次预估每个目标可释放的空间（dry run，不删除文件），界面上勾选目标、
+// 查看预计释放总量，确认后才开始清理。
+use crate::apps;
+use crate::history::format_bytes;
+use crate::theme::{self, theme};
+use crate::utils::{estimate_target, format_age, CleanOptions, CLEAN_TARGETS};
+use anyhow::Result;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::{
+    backend::Backend,
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    Frame, Terminal,
+};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+enum ScanEvent {
+    Started(usize),
+    Done(usize, usize, u64),
+}
+
+#[derive(Clone, Copy)]
+enum Estimate {
+    Pending,
+    Scanning,
+    Done { items: usize, bytes: u64 },
+    // 应用未安装，不扫描
+    NotInstalled,
+}
+
+// 全盘扫描最慢，放在最后，其余目标的结果可以先显示出来
+fn scan_order(estimates: &[Estimate]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..CLEAN_TARGETS.len())
+        .filter(|&index| !matches!(estimates[index], Estimate::NotInstalled))
+        .collect();
+    order.sort_by_key(|&index| CLEAN_TARGETS[index].id == "system-drive-temp");
+    order
+}
+
+fn start_scan(options: &CleanOptions, order: Vec<usize>, cancel: Arc<AtomicBool>) -> Receiver<ScanEvent> {
+    let (tx, rx) = mpsc::channel();
+    let options = options.clone();
+    thread::spawn(move || {
+        for index in order {
+            if cancel.load(Ordering::Relaxed) || tx.send(ScanEvent::Started(index)).is_err() {
+                return;
+            }
+            let (items, bytes) = estimate_target(CLEAN_TARGETS[index].id, &options, &cancel);
+            if tx.send(ScanEvent::Done(index, items, bytes)).is_err() {
+                return;
+            }
+        }
+    });
+    rx
+}
+
+// 选择清理目标：返回勾选后的选项，Esc 返回 None
+pub fn select_targets<B: Backend>(terminal: &mut Terminal<B>, options: &CleanOptions) -> Result<Option<CleanOptions>> {
+    let initial = options.selected_targets();
+    let mut checked: Vec<bool> = CLEAN_TARGETS
+        .iter()
+        .map(|target| initial.iter().any(|t| t.id == target.id))
+        .collect();
+    let mut estimates: Vec<Estimate> = CLEAN_TARGETS
+        .iter()
+        .map(|target| match apps::find(target.id) {
+            Some(app) if !app.installed() => Estimate::NotInstalled,
+            _ => Estimate::Pending,
+        })
+        .collect();
+    let mut selected = 0;
+    let mut message: Option<String> = None;
+    let mut confirming = false;
+
+    let cancel = Arc::new(AtomicBool::new(false));
+    let events = start_scan(options, scan_order(&estimates), Arc::clone(&cancel));
+
+    let result = loop {
+        while let Ok(event) = events.try_recv() {
+            match event {
+                ScanEvent::Started(index) => estimates[index] = Estimate::Scanning,
+                ScanEvent::Done(index, items, bytes) => estimates[index] = Estimate::Done { items, bytes },
+            }
+        }
+
+        terminal.draw(|f| {
+            render_selection(f, options, &checked, &estimates, selected, confirming, message.as_deref());
+            theme::finish_frame(f);
+        })?;
+
+        if !event::poll(Duration::from_millis(200))? {
+            continue;
+        }
+        let key = match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => key,
+            _ => continue,
+        };
+        message = None;
+        if confirming {
+            confirming = false;
+            if matches!(key.code, KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter) {
+                let targets = CLEAN_TARGETS
+                    .iter()
+                    .zip(&checked)
+                    .filter(|(_, c)| **c)
+                    .map(|(t, _)| t.id.to_string())
+                    .collect();
+                break Some(CleanOptions {
+                    targets,
+                    ..options.clone()
+                });
+            }
+            continue;
+        }
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => break None,
+            KeyCode::Up | KeyCode::Char('k') => selected = selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => selected = (selected + 1).min(CLEAN_TARGETS.len() - 1),
+            KeyCode::Char(' ') => checked[selected] = !checked[selected],
+            KeyCode::Char('a') => {
+                let all = checked.iter().all(|c| *c);
+                checked.iter_mut().for_each(|c| *c = !all);
+            }
+            KeyCode::Enter => {
+                if checked.iter().any(|c| *c) {
+                    confirming = true;
+                } else {
+                    message = Some("Select at least one target with Space".to_string());
+                }
+            }
+            _ => {}
+        }
+    };
+
+    // 离开界面时停止仍在进行的扫描
+    cancel.store(true, Ordering::Relaxed);
+    terminal.clear()?;
+    Ok(result)
+}
+
+// 已勾选目标的预计释放量，以及仍在扫描的已勾选目标数
+fn projected(checked: &[bool], estimates: &[Estimate]) -> (u64, usize, usize) {
+    let mut bytes = 0;
+    let mut items = 0;
+    let mut pending = 0;
+    for (estimate, _) in estimates.iter().zip(checked).filter(|(_, c)| **c) {
+        match estimate {
+            Estimate::Done { items: i, bytes: b } => {
+                bytes += b;
+                items += i;
+            }
+            Estimate::NotInstalled => {}
+            _ => pending += 1,
+        }
+    }
+    (bytes, items, pending)
+}
+
+fn render_selection(
+    f: &mut Frame,
+    options: &CleanOptions,
+    checked: &[bool],
+    estimates: &[Estimate],
+    selected: usize,
+    confirming: bool,
+    message: Option<&str>,
+) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(4),
+            Constraint::Length(3),
+        ])
+        .split(f.area());
+
+    let mut title = "🧹 Clean Temp Files: analyze".to_string();
+    if let Some(profile) = &options.profile {
+        title.push_str(&format!(" (profile: {})", profile));
+    }
+    let title = Paragraph::new(title)
+        .style(Style::default().fg(theme().accent).add_modifier(Modifier::BOLD))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(title, chunks[0]);
+
+    // 目标按分组显示，分组标题不可选择
+    let mut items: Vec<ListItem> = Vec::new();
+    let mut selected_row = 0;
+    for (index, target) in CLEAN_TARGETS.iter().enumerate() {
+        if index == 0 || CLEAN_TARGETS[index - 1].group != target.group {
+            items.push(ListItem::new(Line::from(Span::styled(
+                format!(" {}", target.group),
+                Style::default().fg(theme().title).add_modifier(Modifier::BOLD),
+            ))));
+        }
+        if index == selected {
+            selected_row = items.len();
+        }
+        let mark = if checked[index] { "[x]" } else { "[ ]" };
+        let estimate = match estimates[index] {
+            Estimate::Pending => "…".to_string(),
+            Estimate::Scanning => "⏳ scanning".to_string(),
+            Estimate::Done { items, bytes } => format!("{:>10}  {:>6} items", format_bytes(bytes), items),
+            Estimate::NotInstalled => "not installed".to_string(),
+        };
+        let note = if target.privacy { "🔒" } else { "" };
+        let style = if index == selected {
+            Style::default()
+                .fg(theme().selected_fg)
+                .bg(theme().selected_bg)
+                .add_modifier(Modifier::BOLD)
+        } else if checked[index] {
+            Style::default()
+        } else {
+            Style::default().fg(theme().muted)
+        };
+        items.push(ListItem::new(Line::from(Span::styled(
+            format!("   {} {:<30}{:<3} {}", mark, target.name, note, estimate),
+            style,
+        ))));
+    }
+    let mut list_title = " Targets ".to_string();
+    if let Some(age) = options.older_than {
+        list_title = format!(" Targets (items older than {}) ", format_age(age));
+    }
+    let list = List::new(items).block(
+        Block::default()
+            .title(list_title)
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme().accent)),
+    );
+    // 目标较多时随选择滚动
+    let mut state = ListState::default().with_selected(Some(selected_row));
+    f.render_stateful_widget(list, chunks[1], &mut state);
+
+    let (bytes, items, pending) = projected(checked, estimates);
+    let count = checked.iter().filter(|c| **c).count();
+    let mut summary = format!(
+        "Selected {} of {} targets · projected {} ({} items)",
+        count,
+        CLEAN_TARGETS.len(),
+        format_bytes(bytes),
+        items
+    );
+    if pending > 0 {
+        summary.push_str(&format!(" · {} still scanning", pending));
+    }
+    let safety = apps::find(CLEAN_TARGETS[selected].id)
+        .map(|app| format!("ℹ️  {}", app.safety))
+        .unwrap_or_default();
+    let summary = Paragraph::new(vec![
+        Line::from(Span::styled(
+            summary,
+            Style::default().fg(theme().title).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(Span::styled(safety, Style::default().fg(theme().muted))),
+    ])
+    .alignment(Alignment::Center)
+    .block(Block::default().borders(Borders::ALL));
+    f.render_widget(summary, chunks[2]);
+
+    let footer = if confirming {
+        Paragraph::new(format!(
+            "Delete about {} from {} targets? Y/Enter: Clean | any other key: Back",
+            format_bytes(bytes),
+            count
+        ))
+        .style(Style::default().fg(theme().warn).add_modifier(Modifier::BOLD))
+    } else if let Some(message) = message {
+        Paragraph::new(message).style(Style::default().fg(theme().warn))
+    } else {
+        Paragraph::new("↑/↓: Select | Space: Toggle | A: All | Enter: Clean | Q/Esc: Return")
+            .style(Style::default().fg(theme().muted))
+    }
+    .alignment(Alignment::Center)
+    .block(Block::default().borders(Borders::ALL));
+    f.render_widget(footer, chunks[3]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    const MB: u64 = 1024 * 1024;
+
+    fn index(id: &str) -> usize {
+        CLEAN_TARGETS.iter().position(|t| t.id == id).unwrap()
+    }
+
+    fn screen(terminal: &Terminal<TestBackend>) -> String {
+        let buffer = terminal.backend().buffer();
+        let width = buffer.area.width as usize;
+        buffer
+            .content
+            .chunks(width)
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn scan_order_skips_missing_apps_and_scans_the_system_drive_last() {
+        let mut estimates = vec![Estimate::Pending; CLEAN_TARGETS.len()];
+        estimates[0] = Estimate::NotInstalled;
+        let order = scan_order(&estimates);
+
+        assert_eq!(order.len(), CLEAN_TARGETS.len() - 1);
+        assert!(!order.contains(&0));
+        assert_eq!(order.last(), Some(&index("system-drive-temp")));
+        // 其余目标保持列表顺序
+        let rest = &order[..order.len() - 1];
+        assert!(rest.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn projected_counts_only_checked_targets() {
+        let estimates = [
+            Estimate::Done { items: 3, bytes: 2 * MB },
+            Estimate::Done { items: 5, bytes: 7 * MB },
+            Estimate::Scanning,
+            Estimate::Pending,
+            Estimate::NotInstalled,
+        ];
+        assert_eq!(projected(&[true, false, false, false, false], &estimates), (2 * MB, 3, 0));
+        assert_eq!(projected(&[true, true, true, true, true], &estimates), (9 * MB, 8, 2));
+        assert_eq!(projected(&[false; 5], &estimates), (0, 0, 0));
+    }
+
+    #[test]
+    fn cancelled_scan_sends_nothing() {
+        let cancel = Arc::new(AtomicBool::new(true));
+        let events = start_scan(&CleanOptions::default(), vec![0, 1], cancel);
+        assert!(events.recv_timeout(Duration::from_secs(5)).is_err());
+    }
+
+    #[test]
+    fn renders_estimates_and_projected_total() {
+        let options = CleanOptions {
+            profile: Some("weekly".into()),
+            ..CleanOptions::default()
+        };
+        let mut checked = vec![false; CLEAN_TARGETS.len()];
+        let mut estimates = vec![Estimate::Pending; CLEAN_TARGETS.len()];
+        let system = index("system-drive-temp");
+        checked[0] = true;
+        checked[system] = true;
+        estimates[0] = Estimate::Done { items: 12, bytes: 3 * MB };
+        estimates[system] = Estimate::Scanning;
+        estimates[1] = Estimate::NotInstalled;
+
+        let mut terminal = Terminal::new(TestBackend::new(120, 50)).unwrap();
+        terminal
+            .draw(|f| render_selection(f, &options, &checked, &estimates, 0, false, None))
+            .unwrap();
+        let text = screen(&terminal);
+        for expected in [
+            "Clean Temp Files: analyze (profile: weekly)",
+            "[x]",
+            "3.00 MB      12 items",
+            "not installed",
+            "scanning",
+            "Selected 2 of",
+            "projected 3.00 MB (12 items) · 1 still scanning",
+            "Enter: Clean",
+        ] {
+            assert!(text.contains(expected), "missing {:?} in:\n{}", expected, text);
+        }
+
+        terminal
+            .draw(|f| render_selection(f, &options, &checked, &estimates, 0, true, None))
+            .unwrap();
+        assert!(screen(&terminal).contains("Delete about 3.00 MB from 2 targets?"));
+    }
+}
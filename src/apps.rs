@@ -0,0 +1,322 @@
+// Application cleaners: crash dumps, error reports, servicing logs, IDE and chat app caches
+//
+// 每个条目对应一个 clean-temp 目标（名称和分组见 utils::CLEAN_TARGETS），定义：
+// - roots：应用的数据目录，任一存在即视为已安装
+// - paths：相对于 roots 的可清理目录或文件，"*" 匹配任意一级子目录（如 JetBrains 的各产品版本），
+//   空字符串表示 roots 本身
+// - processes：应用运行时不清理
+// - safety：清理前显示的注意事项
+use crate::browser::running_processes;
+use crate::config::{env_dir, xdg_cache_home, xdg_config_home};
+use std::fs;
+use std::path::PathBuf;
+
+pub struct AppCleaner {
+    pub id: &'static str,
+    roots: fn() -> Vec<PathBuf>,
+    paths: &'static [&'static str],
+    processes: &'static [&'static str],
+    pub safety: &'static str,
+}
+
+fn join_all(base: Option<PathBuf>, paths: &[&str]) -> Vec<PathBuf> {
+    base.map(|base| paths.iter().map(|path| base.join(path)).collect())
+        .unwrap_or_default()
+}
+
+fn crash_dump_roots() -> Vec<PathBuf> {
+    if cfg!(windows) {
+        join_all(env_dir("LOCALAPPDATA"), &["CrashDumps"])
+    } else {
+        vec![PathBuf::from("/var/crash")]
+    }
+}
+
+fn error_report_roots() -> Vec<PathBuf> {
+    if !cfg!(windows) {
+        return Vec::new();
+    }
+    let mut roots = join_all(env_dir("ProgramData"), &[r"Microsoft\Windows\WER"]);
+    roots.extend(join_all(env_dir("LOCALAPPDATA"), &[r"Microsoft\Windows\WER"]));
+    roots
+}
+
+fn cbs_log_roots() -> Vec<PathBuf> {
+    if !cfg!(windows) {
+        return Vec::new();
+    }
+    join_all(env_dir("windir"), &[r"Logs\CBS"])
+}
+
+fn jetbrains_roots() -> Vec<PathBuf> {
+    if cfg!(windows) {
+        join_all(env_dir("LOCALAPPDATA"), &["JetBrains"])
+    } else {
+        join_all(xdg_cache_home(), &["JetBrains"])
+    }
+}
+
+fn vscode_roots() -> Vec<PathBuf> {
+    if cfg!(windows) {
+        join_all(env_dir("APPDATA"), &["Code"])
+    } else {
+        join_all(xdg_config_home(), &["Code"])
+    }
+}
+
+fn teams_roots() -> Vec<PathBuf> {
+    if cfg!(windows) {
+        // 经典版 Teams 和新版 Teams（MSIX 包）
+        let mut roots = join_all(env_dir("APPDATA"), &[r"Microsoft\Teams"]);
+        roots.extend(join_all(
+            env_dir("LOCALAPPDATA"),
+            &[r"Packages\MSTeams_8wekyb3d8bbwe\LocalCache\Microsoft\MSTeams"],
+        ));
+        roots
+    } else {
+        join_all(xdg_config_home(), &["Microsoft/Microsoft Teams"])
+    }
+}
+
+fn slack_roots() -> Vec<PathBuf> {
+    if cfg!(windows) {
+        // 安装版和 Microsoft Store 版
+        let mut roots = join_all(env_dir("APPDATA"), &["Slack"]);
+        roots.extend(join_all(
+            env_dir("LOCALAPPDATA"),
+            &[r"Packages\91750D7E.Slack_8she8kybcnzg4\LocalCache\Roaming\Slack"],
+        ));
+        roots
+    } else {
+        join_all(xdg_config_home(), &["Slack"])
+    }
+}
+
+pub const APP_CLEANERS: [AppCleaner; 7] = [
+    AppCleaner {
+        id: "crash-dumps",
+        roots: crash_dump_roots,
+        paths: &[""],
+        processes: &[],
+        safety: "Crash dumps are only needed when reporting a crash to the app's developers",
+    },
+    AppCleaner {
+        id: "error-reports",
+        roots: error_report_roots,
+        paths: &["ReportQueue", "ReportArchive", "Temp"],
+        processes: &[],
+        safety: "Queued reports are not sent to Microsoft; the system-wide queue needs administrator rights",
+    },
+    AppCleaner {
+        id: "cbs-logs",
+        roots: cbs_log_roots,
+        paths: &[""],
+        processes: &[],
+        safety: "Windows servicing logs; the CBS.log in use by Windows Update is locked and skipped",
+    },
+    AppCleaner {
+        id: "jetbrains-caches",
+        roots: jetbrains_roots,
+        paths: &["*/caches", "*/index", "*/log", "*/tmp"],
+        processes: &[
+            "idea64", "idea", "pycharm64", "pycharm", "clion64", "clion", "webstorm64", "webstorm", "rider64",
+            "rider", "goland64", "goland", "rustrover64", "rustrover", "datagrip64", "datagrip", "phpstorm64",
+            "phpstorm",
+        ],
+        safety: "Settings and plugins are kept; projects are re-indexed on the next start",
+    },
+    AppCleaner {
+        id: "vscode-caches",
+        roots: vscode_roots,
+        paths: &[
+            "Cache",
+            "CachedData",
+            "CachedExtensionVSIXs",
+            "Code Cache",
+            "GPUCache",
+            "Service Worker/CacheStorage",
+            "logs",
+        ],
+        processes: &["code"],
+        safety: "Settings, extensions and workspace state are kept",
+    },
+    AppCleaner {
+        id: "teams-cache",
+        roots: teams_roots,
+        paths: &[
+            "Cache",
+            "Code Cache",
+            "GPUCache",
+            "Service Worker/CacheStorage",
+            "tmp",
+            "logs",
+            "EBWebView/*/Cache",
+            "EBWebView/*/Code Cache",
+        ],
+        processes: &["teams", "ms-teams"],
+        safety: "You stay signed in; chats and images are downloaded again when opened",
+    },
+    AppCleaner {
+        id: "slack-cache",
+        roots: slack_roots,
+        paths: &["Cache", "Code Cache", "GPUCache", "Service Worker/CacheStorage", "logs"],
+        processes: &["slack"],
+        safety: "You stay signed in; messages and files are downloaded again when opened",
+    },
+];
+
+pub fn find(id: &str) -> Option<&'static AppCleaner> {
+    APP_CLEANERS.iter().find(|app| app.id == id)
+}
+
+// 展开相对路径中的 "*"（任意一级子目录）
+fn expand(base: PathBuf, pattern: &str) -> Vec<PathBuf> {
+    let mut paths = vec![base];
+    for component in pattern.split('/').filter(|c| !c.is_empty()) {
+        paths = paths
+            .into_iter()
+            .flat_map(|path| {
+                if component == "*" {
+                    fs::read_dir(&path)
+                        .into_iter()
+                        .flatten()
+                        .flatten()
+                        .map(|entry| entry.path())
+                        .filter(|path| path.is_dir())
+                        .collect()
+                } else {
+                    vec![path.join(component)]
+                }
+            })
+            .collect();
+    }
+    paths
+}
+
+impl AppCleaner {
+    pub fn installed(&self) -> bool {
+        (self.roots)().iter().any(|root| root.is_dir())
+    }
+
+    pub fn is_running(&self) -> bool {
+        !self.processes.is_empty()
+            && running_processes()
+                .iter()
+                .any(|p| self.processes.contains(&p.as_str()))
+    }
+
+    // 存在的可清理路径
+    pub fn paths(&self) -> Vec<PathBuf> {
+        let mut paths: Vec<PathBuf> = (self.roots)()
+            .into_iter()
+            .filter(|root| root.is_dir())
+            .flat_map(|root| self.paths.iter().flat_map(move |pattern| expand(root.clone(), pattern)))
+            .filter(|path| path.exists())
+            .collect();
+        paths.sort();
+        paths.dedup();
+        paths
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("lemo-apps-{}-{}", name, std::process::id()))
+    }
+
+    // roots 是函数指针，测试目录由进程号确定
+    fn test_roots() -> Vec<PathBuf> {
+        vec![test_dir("missing"), test_dir("ide")]
+    }
+
+    fn missing_roots() -> Vec<PathBuf> {
+        vec![test_dir("missing")]
+    }
+
+    fn cleaner(roots: fn() -> Vec<PathBuf>, paths: &'static [&'static str]) -> AppCleaner {
+        AppCleaner {
+            id: "test-app",
+            roots,
+            paths,
+            processes: &[],
+            safety: "",
+        }
+    }
+
+    #[test]
+    fn expands_stars_one_level_at_a_time() {
+        let base = test_dir("expand");
+        let _ = fs::remove_dir_all(&base);
+        fs::create_dir_all(base.join("IDEA2024.1").join("caches").join("deep")).unwrap();
+        fs::create_dir_all(base.join("PyCharm2024.2").join("caches")).unwrap();
+        fs::create_dir_all(base.join("Rider2024.3")).unwrap();
+        // "*" 只匹配目录
+        fs::write(base.join("notes.txt"), b"x").unwrap();
+
+        let mut paths = expand(base.clone(), "*/caches");
+        paths.sort();
+        assert_eq!(
+            paths,
+            [
+                base.join("IDEA2024.1").join("caches"),
+                base.join("PyCharm2024.2").join("caches"),
+                base.join("Rider2024.3").join("caches"),
+            ]
+        );
+        assert_eq!(expand(base.clone(), ""), vec![base.clone()]);
+        assert_eq!(expand(base.clone(), "a/b"), [base.join("a").join("b")]);
+        assert_eq!(
+            expand(base.clone(), "*/caches/*"),
+            [base.join("IDEA2024.1").join("caches").join("deep")]
+        );
+        assert!(expand(base.join("missing"), "*/caches").is_empty());
+
+        fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[test]
+    fn detects_installed_apps_and_existing_paths() {
+        let root = test_dir("ide");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("IDEA2024.1").join("caches")).unwrap();
+        fs::create_dir_all(root.join("IDEA2024.1").join("log")).unwrap();
+        fs::create_dir_all(root.join("PyCharm2024.2").join("log")).unwrap();
+        fs::write(root.join("PyCharm2024.2").join("tmp"), b"x").unwrap();
+
+        let app = cleaner(test_roots, &["*/caches", "*/log", "*/tmp", "*/index"]);
+        assert!(app.installed());
+        assert_eq!(
+            app.paths(),
+            [
+                root.join("IDEA2024.1").join("caches"),
+                root.join("IDEA2024.1").join("log"),
+                root.join("PyCharm2024.2").join("log"),
+                root.join("PyCharm2024.2").join("tmp"),
+            ]
+        );
+        // 空字符串表示 roots 本身
+        assert_eq!(cleaner(test_roots, &[""]).paths(), vec![root.clone()]);
+
+        let missing = cleaner(missing_roots, &[""]);
+        assert!(!missing.installed());
+        assert!(missing.paths().is_empty());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn every_cleaner_is_a_clean_target() {
+        for app in &APP_CLEANERS {
+            assert!(
+                crate::utils::CLEAN_TARGETS.iter().any(|t| t.id == app.id),
+                "{} has no clean target",
+                app.id
+            );
+            assert!(find(app.id).is_some());
+        }
+        assert!(find("unknown").is_none());
+    }
+}
@@ -0,0 +1,373 @@
+// Battery and power status
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChargeState {
+    Charging,
+    Discharging,
+    Full,
+    NotCharging,
+    Unknown,
+}
+
+impl ChargeState {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChargeState::Charging => "Charging",
+            ChargeState::Discharging => "Discharging",
+            ChargeState::Full => "Full",
+            ChargeState::NotCharging => "Not charging",
+            ChargeState::Unknown => "Unknown",
+        }
+    }
+}
+
+// 容量单位：sysfs 报告能量（µWh）或电荷（µAh），Windows 报告 mWh
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CapacityUnit {
+    WattHours,
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    MilliampHours,
+}
+
+impl CapacityUnit {
+    pub fn label(&self) -> &'static str {
+        match self {
+            CapacityUnit::WattHours => "Wh",
+            CapacityUnit::MilliampHours => "mAh",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatteryInfo {
+    pub name: String,
+    pub percent: Option<f32>,
+    pub state: ChargeState,
+    // 放电时为剩余使用时间，充电时为充满所需时间
+    pub time_remaining: Option<Duration>,
+    pub design_capacity: Option<f64>,
+    pub full_capacity: Option<f64>,
+    pub capacity_unit: CapacityUnit,
+    pub cycle_count: Option<u32>,
+}
+
+impl BatteryInfo {
+    // 电池损耗百分比（1 - 满充容量 / 设计容量）
+    pub fn wear_percent(&self) -> Option<f64> {
+        match (self.design_capacity, self.full_capacity) {
+            (Some(design), Some(full)) if design > 0.0 => {
+                Some(((1.0 - full / design) * 100.0).max(0.0))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PowerStatus {
+    pub ac_online: Option<bool>,
+    pub batteries: Vec<BatteryInfo>,
+}
+
+// Read the current power status of this machine
+#[cfg(target_os = "linux")]
+pub fn read_power_status() -> PowerStatus {
+    read_power_supply_dir(std::path::Path::new("/sys/class/power_supply"))
+}
+
+// 解析 /sys/class/power_supply 结构的目录（可以指向测试用的固定目录）
+#[cfg(target_os = "linux")]
+pub fn read_power_supply_dir(root: &std::path::Path) -> PowerStatus {
+    use std::fs;
+
+    let mut status = PowerStatus::default();
+    let mut entries: Vec<_> = match fs::read_dir(root) {
+        Ok(entries) => entries.flatten().map(|e| e.path()).collect(),
+        Err(_) => return status,
+    };
+    entries.sort();
+
+    for supply in entries {
+        let read = |name: &str| {
+            fs::read_to_string(supply.join(name))
+                .ok()
+                .map(|v| v.trim().to_string())
+        };
+        let read_number = |name: &str| read(name).and_then(|v| v.parse::<f64>().ok());
+
+        match read("type").as_deref() {
+            Some("Mains") | Some("USB") => {
+                if let Some(online) = read("online") {
+                    // 任意一个外部电源在线即视为接通电源
+                    status.ac_online = Some(status.ac_online.unwrap_or(false) || online == "1");
+                }
+            }
+            Some("Battery") => {
+                // 部分系统会把外设（鼠标、手柄）电池也列出来，只统计系统电池
+                if read("scope").as_deref() == Some("Device") {
+                    continue;
+                }
+                if read("present").as_deref() == Some("0") {
+                    continue;
+                }
+
+                let state = match read("status").as_deref() {
+                    Some("Charging") => ChargeState::Charging,
+                    Some("Discharging") => ChargeState::Discharging,
+                    Some("Full") => ChargeState::Full,
+                    Some("Not charging") => ChargeState::NotCharging,
+                    _ => ChargeState::Unknown,
+                };
+
+                // 优先使用能量（µWh / µW），否则使用电荷（µAh / µA）
+                let (now, full, design, rate, unit) = if read_number("energy_full").is_some() {
+                    (
+                        read_number("energy_now"),
+                        read_number("energy_full"),
+                        read_number("energy_full_design"),
+                        read_number("power_now"),
+                        CapacityUnit::WattHours,
+                    )
+                } else {
+                    (
+                        read_number("charge_now"),
+                        read_number("charge_full"),
+                        read_number("charge_full_design"),
+                        read_number("current_now"),
+                        CapacityUnit::MilliampHours,
+                    )
+                };
+
+                let percent = read_number("capacity").map(|v| v as f32).or_else(|| {
+                    match (now, full) {
+                        (Some(now), Some(full)) if full > 0.0 => Some((now / full * 100.0) as f32),
+                        _ => None,
+                    }
+                });
+
+                let time_remaining = match (state, now, full, rate) {
+                    (ChargeState::Discharging, Some(now), _, Some(rate)) if rate > 0.0 => {
+                        Some(Duration::from_secs_f64(now / rate * 3600.0))
+                    }
+                    (ChargeState::Charging, Some(now), Some(full), Some(rate)) if rate > 0.0 => {
+                        Some(Duration::from_secs_f64((full - now).max(0.0) / rate * 3600.0))
+                    }
+                    _ => None,
+                };
+
+                // 将 µWh / µAh 转换为 Wh / mAh
+                let scale = match unit {
+                    CapacityUnit::WattHours => 1_000_000.0,
+                    CapacityUnit::MilliampHours => 1_000.0,
+                };
+
+                status.batteries.push(BatteryInfo {
+                    name: supply
+                        .file_name()
+                        .map(|n| n.to_string_lossy().to_string())
+                        .unwrap_or_default(),
+                    percent,
+                    state,
+                    time_remaining,
+                    design_capacity: design.map(|v| v / scale),
+                    full_capacity: full.map(|v| v / scale),
+                    capacity_unit: unit,
+                    // 不支持的驱动会报告 0
+                    cycle_count: read_number("cycle_count")
+                        .map(|v| v as u32)
+                        .filter(|&c| c > 0),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    status
+}
+
+// Read the current power status of this machine
+#[cfg(windows)]
+pub fn read_power_status() -> PowerStatus {
+    use winapi::um::winbase::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
+
+    let mut raw: SYSTEM_POWER_STATUS = unsafe { std::mem::zeroed() };
+    if unsafe { GetSystemPowerStatus(&mut raw) } == 0 {
+        return PowerStatus::default();
+    }
+
+    let ac_online = match raw.ACLineStatus {
+        0 => Some(false),
+        1 => Some(true),
+        _ => None,
+    };
+
+    // BatteryFlag: 128 = 无电池, 255 = 未知
+    if raw.BatteryFlag == 128 || raw.BatteryFlag == 255 {
+        return PowerStatus {
+            ac_online,
+            batteries: Vec::new(),
+        };
+    }
+
+    let percent = if raw.BatteryLifePercent <= 100 {
+        Some(raw.BatteryLifePercent as f32)
+    } else {
+        None
+    };
+
+    let state = if raw.BatteryFlag & 8 != 0 {
+        ChargeState::Charging
+    } else if ac_online == Some(true) {
+        if percent == Some(100.0) {
+            ChargeState::Full
+        } else {
+            ChargeState::NotCharging
+        }
+    } else if ac_online == Some(false) {
+        ChargeState::Discharging
+    } else {
+        ChargeState::Unknown
+    };
+
+    // BatteryLifeTime 为 0xFFFFFFFF 表示未知（接通电源时通常如此）
+    let time_remaining = if raw.BatteryLifeTime != u32::MAX && state == ChargeState::Discharging {
+        Some(Duration::from_secs(raw.BatteryLifeTime as u64))
+    } else {
+        None
+    };
+
+    // GetSystemPowerStatus 不提供设计容量和循环次数
+    PowerStatus {
+        ac_online,
+        batteries: vec![BatteryInfo {
+            name: "Battery".to_string(),
+            percent,
+            state,
+            time_remaining,
+            design_capacity: None,
+            full_capacity: None,
+            capacity_unit: CapacityUnit::WattHours,
+            cycle_count: None,
+        }],
+    }
+}
+
+#[cfg(not(any(windows, target_os = "linux")))]
+pub fn read_power_status() -> PowerStatus {
+    PowerStatus::default()
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    // 测试用的 power_supply 目录：<tmp>/lemo-power-<name>-<pid>
+    fn fixture(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("lemo-power-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        root
+    }
+
+    fn supply(root: &Path, name: &str, files: &[(&str, &str)]) {
+        let dir = root.join(name);
+        fs::create_dir_all(&dir).unwrap();
+        for (file, value) in files {
+            fs::write(dir.join(file), format!("{}\n", value)).unwrap();
+        }
+    }
+
+    #[test]
+    fn reads_energy_based_battery() {
+        let root = fixture("energy");
+        supply(
+            &root,
+            "BAT0",
+            &[
+                ("type", "Battery"),
+                ("status", "Discharging"),
+                ("energy_now", "25000000"),
+                ("energy_full", "50000000"),
+                ("energy_full_design", "62500000"),
+                ("power_now", "12500000"),
+                ("cycle_count", "0"),
+            ],
+        );
+        let status = read_power_supply_dir(&root);
+        assert_eq!(status.ac_online, None);
+        assert_eq!(status.batteries.len(), 1);
+
+        let battery = &status.batteries[0];
+        assert_eq!(battery.name, "BAT0");
+        assert_eq!(battery.state, ChargeState::Discharging);
+        assert_eq!(battery.capacity_unit, CapacityUnit::WattHours);
+        // 没有 capacity 文件时由 energy_now / energy_full 计算
+        assert_eq!(battery.percent, Some(50.0));
+        assert_eq!(battery.full_capacity, Some(50.0));
+        assert_eq!(battery.design_capacity, Some(62.5));
+        assert!((battery.wear_percent().unwrap() - 20.0).abs() < 1e-9);
+        assert_eq!(battery.time_remaining, Some(Duration::from_secs(2 * 3600)));
+        assert_eq!(battery.cycle_count, None);
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn reads_charge_based_battery() {
+        let root = fixture("charge");
+        supply(
+            &root,
+            "BAT1",
+            &[
+                ("type", "Battery"),
+                ("status", "Charging"),
+                ("capacity", "80"),
+                ("charge_now", "3200000"),
+                ("charge_full", "4000000"),
+                ("charge_full_design", "4000000"),
+                ("current_now", "1600000"),
+                ("cycle_count", "312"),
+            ],
+        );
+        let battery = &read_power_supply_dir(&root).batteries[0];
+        assert_eq!(battery.state, ChargeState::Charging);
+        assert_eq!(battery.capacity_unit, CapacityUnit::MilliampHours);
+        assert_eq!(battery.percent, Some(80.0));
+        assert_eq!(battery.full_capacity, Some(4000.0));
+        assert_eq!(battery.wear_percent(), Some(0.0));
+        // 充满还需 (4000 - 3200) / 1600 小时
+        assert_eq!(battery.time_remaining, Some(Duration::from_secs(30 * 60)));
+        assert_eq!(battery.cycle_count, Some(312));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn skips_device_batteries_and_reads_mains() {
+        let root = fixture("device");
+        supply(&root, "AC", &[("type", "Mains"), ("online", "1")]);
+        supply(&root, "usb-c", &[("type", "USB"), ("online", "0")]);
+        supply(
+            &root,
+            "hidpp_battery_0",
+            &[("type", "Battery"), ("scope", "Device"), ("capacity", "40")],
+        );
+        let status = read_power_supply_dir(&root);
+        assert_eq!(status.ac_online, Some(true));
+        assert!(status.batteries.is_empty());
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn empty_directory_has_no_batteries() {
+        let root = fixture("empty");
+        let status = read_power_supply_dir(&root);
+        assert_eq!(status.ac_online, None);
+        assert!(status.batteries.is_empty());
+        fs::remove_dir_all(&root).unwrap();
+
+        assert!(read_power_supply_dir(&root).batteries.is_empty());
+    }
+}
@@ -0,0 +1,435 @@
+// Browser data: Chromium-family (Chrome, Edge, Brave, Vivaldi, Opera, Chromium) and Firefox profiles
+//
+// 每个浏览器有数据目录（配置、Cookies、历史记录）和缓存目录；Windows 上 Chromium 两者相同，
+// Linux 上缓存位于 ~/.cache 下。清理按类别进行：缓存、代码缓存、GPU 缓存、Service Worker 缓存，
+// 以及需要明确选择的 Cookies 和历史记录。浏览器运行时不清理它的数据（文件被占用，或被浏览器写回）。
+//
+// Firefox 的历史记录和书签同在 places.sqlite 中，因此只清理表单历史和会话备份，不删除 places.sqlite。
+use crate::config::{env_dir, home_dir, xdg_cache_home, xdg_config_home};
+use crate::history::format_bytes;
+use crate::utils::measure_paths;
+use anyhow::Result;
+use std::fs;
+use std::path::{Path, PathBuf};
+use sysinfo::{ProcessesToUpdate, System};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Category {
+    Cache,
+    CodeCache,
+    GpuCache,
+    ServiceWorker,
+    Cookies,
+    History,
+}
+
+impl Category {
+    pub const ALL: [Category; 6] = [
+        Category::Cache,
+        Category::CodeCache,
+        Category::GpuCache,
+        Category::ServiceWorker,
+        Category::Cookies,
+        Category::History,
+    ];
+
+    // 对应的 clean-temp 目标 id
+    pub fn target_id(self) -> &'static str {
+        match self {
+            Category::Cache => "browser-cache",
+            Category::CodeCache => "browser-code-cache",
+            Category::GpuCache => "browser-gpu-cache",
+            Category::ServiceWorker => "browser-service-workers",
+            Category::Cookies => "browser-cookies",
+            Category::History => "browser-history",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Category::Cache => "cache",
+            Category::CodeCache => "code cache",
+            Category::GpuCache => "GPU cache",
+            Category::ServiceWorker => "service worker cache",
+            Category::Cookies => "cookies",
+            Category::History => "history",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Family {
+    Chromium,
+    Firefox,
+}
+
+#[derive(Debug, Clone)]
+pub struct Browser {
+    pub name: &'static str,
+    family: Family,
+    // 进程名（小写，不含 .exe）
+    processes: &'static [&'static str],
+    data_dir: PathBuf,
+    cache_dir: PathBuf,
+}
+
+// 一个浏览器配置文件：数据目录和对应的缓存目录
+#[derive(Debug, Clone)]
+pub struct BrowserProfile {
+    pub name: String,
+    data: PathBuf,
+    cache: PathBuf,
+}
+
+// 已知浏览器的位置（不检查是否存在）
+fn known_browsers() -> Vec<Browser> {
+    if cfg!(windows) {
+        windows_browsers(env_dir("LOCALAPPDATA"), env_dir("APPDATA"))
+    } else if let (Some(home), Some(config), Some(cache)) = (home_dir(), xdg_config_home(), xdg_cache_home()) {
+        linux_browsers(&home, &config, &cache)
+    } else {
+        Vec::new()
+    }
+}
+
+fn chromium(name: &'static str, processes: &'static [&'static str], data_dir: PathBuf, cache_dir: PathBuf) -> Browser {
+    Browser {
+        name,
+        family: Family::Chromium,
+        processes,
+        data_dir,
+        cache_dir,
+    }
+}
+
+// Windows：Chromium 的数据和缓存都在 %LOCALAPPDATA%（Opera 的数据在 %APPDATA%），Firefox 分开存放
+fn windows_browsers(local: Option<PathBuf>, roaming: Option<PathBuf>) -> Vec<Browser> {
+    let mut browsers = Vec::new();
+    if let Some(local) = &local {
+        let mut add = |name, processes, path: &str| {
+            let user_data = local.join(path).join("User Data");
+            browsers.push(chromium(name, processes, user_data.clone(), user_data));
+        };
+        add("Chrome", &["chrome"], r"Google\Chrome");
+        add("Edge", &["msedge"], r"Microsoft\Edge");
+        add("Brave", &["brave"], r"BraveSoftware\Brave-Browser");
+        add("Vivaldi", &["vivaldi"], "Vivaldi");
+        add("Chromium", &["chromium", "chrome"], "Chromium");
+    }
+    if let Some(roaming) = &roaming {
+        let opera = roaming.join(r"Opera Software\Opera Stable");
+        let opera_cache = local
+            .as_ref()
+            .map(|local| local.join(r"Opera Software\Opera Stable"))
+            .unwrap_or_else(|| opera.clone());
+        browsers.push(chromium("Opera", &["opera"], opera, opera_cache));
+    }
+    if let (Some(roaming), Some(local)) = (&roaming, &local) {
+        browsers.push(Browser {
+            name: "Firefox",
+            family: Family::Firefox,
+            processes: &["firefox"],
+            data_dir: roaming.join(r"Mozilla\Firefox\Profiles"),
+            cache_dir: local.join(r"Mozilla\Firefox\Profiles"),
+        });
+    }
+    browsers
+}
+
+// Linux：数据在 XDG 配置目录，缓存在 XDG 缓存目录，Firefox 的数据在 ~/.mozilla
+fn linux_browsers(home: &Path, config: &Path, cache: &Path) -> Vec<Browser> {
+    let browser = |name, processes, path: &str| chromium(name, processes, config.join(path), cache.join(path));
+    vec![
+        browser("Chrome", &["chrome"], "google-chrome"),
+        browser("Edge", &["msedge"], "microsoft-edge"),
+        browser("Brave", &["brave"], "BraveSoftware/Brave-Browser"),
+        browser("Vivaldi", &["vivaldi-bin", "vivaldi"], "vivaldi"),
+        // Linux 的进程名最多 15 个字符
+        browser("Chromium", &["chromium", "chromium-browse"], "chromium"),
+        browser("Opera", &["opera"], "opera"),
+        Browser {
+            name: "Firefox",
+            family: Family::Firefox,
+            processes: &["firefox", "firefox-bin"],
+            data_dir: home.join(".mozilla").join("firefox"),
+            cache_dir: cache.join("mozilla").join("firefox"),
+        },
+    ]
+}
+
+// 本机已安装（数据目录存在）的浏览器
+pub fn installed() -> Vec<Browser> {
+    known_browsers().into_iter().filter(|b| b.data_dir.is_dir()).collect()
+}
+
+// 当前运行的进程名（小写，去掉 .exe）
+pub fn running_processes() -> Vec<String> {
+    let mut sys = System::new();
+    sys.refresh_processes(ProcessesToUpdate::All, true);
+    sys.processes()
+        .values()
+        .map(|p| {
+            let name = p.name().to_string_lossy().to_lowercase();
+            name.strip_suffix(".exe").map(str::to_string).unwrap_or(name)
+        })
+        .collect()
+}
+
+impl Browser {
+    pub fn is_running(&self, processes: &[String]) -> bool {
+        processes.iter().any(|p| self.processes.contains(&p.as_str()))
+    }
+
+    // Chromium：含 Preferences 的子目录（Default、Profile 1 …），Opera 的数据目录本身就是配置文件；
+    // Firefox：含 prefs.js 的子目录
+    pub fn profiles(&self) -> Vec<BrowserProfile> {
+        let marker = match self.family {
+            Family::Chromium => "Preferences",
+            Family::Firefox => "prefs.js",
+        };
+        if self.data_dir.join(marker).is_file() {
+            return vec![BrowserProfile {
+                name: "Default".to_string(),
+                data: self.data_dir.clone(),
+                cache: self.cache_dir.clone(),
+            }];
+        }
+        let mut profiles: Vec<BrowserProfile> = fs::read_dir(&self.data_dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter(|entry| entry.path().join(marker).is_file())
+            .map(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                BrowserProfile {
+                    cache: self.cache_dir.join(&name),
+                    data: entry.path(),
+                    name,
+                }
+            })
+            .collect();
+        profiles.sort_by(|a, b| a.name.cmp(&b.name));
+        profiles
+    }
+
+    // 某个类别在所有配置文件中存在的路径：目录清理其中的内容，文件直接删除
+    pub fn paths(&self, category: Category) -> Vec<PathBuf> {
+        let mut paths = Vec::new();
+        if self.family == Family::Chromium && category == Category::GpuCache {
+            // 着色器缓存在 User Data 层级，所有配置文件共用
+            for name in ["GrShaderCache", "ShaderCache", "GraphiteDawnCache"] {
+                paths.push(self.data_dir.join(name));
+            }
+        }
+        for profile in self.profiles() {
+            paths.extend(profile_paths(self.family, &profile, category));
+        }
+        paths.retain(|path| path.exists());
+        paths.dedup();
+        paths
+    }
+}
+
+fn profile_paths(family: Family, profile: &BrowserProfile, category: Category) -> Vec<PathBuf> {
+    let data = |names: &[&str]| names.iter().map(|name| profile.data.join(name)).collect::<Vec<_>>();
+    let cache = |names: &[&str]| names.iter().map(|name| profile.cache.join(name)).collect::<Vec<_>>();
+    match (family, category) {
+        (Family::Chromium, Category::Cache) => cache(&["Cache"]),
+        (Family::Chromium, Category::CodeCache) => cache(&["Code Cache"]),
+        (Family::Chromium, Category::GpuCache) => data(&["GPUCache"]),
+        (Family::Chromium, Category::ServiceWorker) => {
+            data(&["Service Worker/CacheStorage", "Service Worker/ScriptCache"])
+        }
+        (Family::Chromium, Category::Cookies) => data(&[
+            "Network/Cookies",
+            "Network/Cookies-journal",
+            "Cookies",
+            "Cookies-journal",
+        ]),
+        (Family::Chromium, Category::History) => data(&[
+            "History",
+            "History-journal",
+            "Visited Links",
+            "Top Sites",
+            "Top Sites-journal",
+        ]),
+        (Family::Firefox, Category::Cache) => cache(&["cache2"]),
+        (Family::Firefox, Category::CodeCache) => cache(&["startupCache"]),
+        (Family::Firefox, Category::GpuCache) => cache(&["shader-cache"]),
+        // 每个站点一个目录：storage/default/<站点>/cache
+        (Family::Firefox, Category::ServiceWorker) => fs::read_dir(profile.data.join("storage").join("default"))
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| entry.path().join("cache"))
+            .collect(),
+        (Family::Firefox, Category::Cookies) => {
+            data(&["cookies.sqlite", "cookies.sqlite-wal", "cookies.sqlite-shm"])
+        }
+        (Family::Firefox, Category::History) => data(&["formhistory.sqlite", "sessionstore-backups"]),
+    }
+}
+
+// lemo browsers：已安装的浏览器、配置文件和每个类别可清理的大小
+pub fn print_browsers() -> Result<()> {
+    let browsers = installed();
+    if browsers.is_empty() {
+        println!("No supported browsers found.");
+        return Ok(());
+    }
+
+    let running = running_processes();
+    for browser in &browsers {
+        let profiles = browser.profiles();
+        let names: Vec<&str> = profiles.iter().map(|p| p.name.as_str()).collect();
+        println!("{} (profiles: {})", browser.name, names.join(", "));
+        println!("   {}", browser.data_dir.display());
+        if browser.is_running(&running) {
+            println!("   ⚠️  Running: close it before cleaning");
+        }
+        let mut total = 0;
+        for category in Category::ALL {
+            let (items, bytes) = measure_paths(&browser.paths(category));
+            total += bytes;
+            println!(
+                "   {:<22} {:>10}  {:>6} items  ({})",
+                category.label(),
+                format_bytes(bytes),
+                items,
+                category.target_id()
+            );
+        }
+        println!("   {:<22} {:>10}", "total", format_bytes(total));
+        println!();
+    }
+    println!("Cookies and history are only cleaned when selected explicitly (privacy profile or the analyze screen).");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("lemo-browser-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn touch(path: PathBuf) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, b"x").unwrap();
+    }
+
+    fn installed_in(browsers: Vec<Browser>) -> Vec<Browser> {
+        browsers.into_iter().filter(|b| b.data_dir.is_dir()).collect()
+    }
+
+    fn profile_names(browser: &Browser) -> Vec<String> {
+        browser.profiles().into_iter().map(|p| p.name).collect()
+    }
+
+    #[test]
+    fn finds_linux_profiles_and_caches() {
+        let home = temp_dir("linux");
+        let (config, cache) = (home.join(".config"), home.join(".cache"));
+        let chrome = config.join("google-chrome");
+        touch(chrome.join("Default").join("Preferences"));
+        touch(chrome.join("Profile 1").join("Preferences"));
+        // 没有 Preferences 的目录不是配置文件
+        fs::create_dir_all(chrome.join("Crashpad")).unwrap();
+        touch(chrome.join("GrShaderCache").join("data_0"));
+        touch(chrome.join("Default").join("GPUCache").join("data_0"));
+        touch(chrome.join("Default").join("Network").join("Cookies"));
+        touch(cache.join("google-chrome").join("Default").join("Cache").join("data_0"));
+        touch(cache.join("google-chrome").join("Profile 1").join("Code Cache").join("js"));
+        let firefox = home.join(".mozilla").join("firefox").join("abc.default");
+        touch(firefox.join("prefs.js"));
+        touch(firefox.join("cookies.sqlite"));
+        touch(firefox.join("storage").join("default").join("https+++example.org").join("cache").join("x"));
+        touch(cache.join("mozilla").join("firefox").join("abc.default").join("cache2").join("entry"));
+
+        let browsers = installed_in(linux_browsers(&home, &config, &cache));
+        let names: Vec<&str> = browsers.iter().map(|b| b.name).collect();
+        assert_eq!(names, ["Chrome", "Firefox"]);
+
+        let (chrome_browser, firefox_browser) = (&browsers[0], &browsers[1]);
+        assert_eq!(profile_names(chrome_browser), ["Default", "Profile 1"]);
+        assert_eq!(
+            chrome_browser.paths(Category::Cache),
+            [cache.join("google-chrome").join("Default").join("Cache")]
+        );
+        assert_eq!(
+            chrome_browser.paths(Category::CodeCache),
+            [cache.join("google-chrome").join("Profile 1").join("Code Cache")]
+        );
+        assert_eq!(
+            chrome_browser.paths(Category::GpuCache),
+            [chrome.join("GrShaderCache"), chrome.join("Default").join("GPUCache")]
+        );
+        assert_eq!(
+            chrome_browser.paths(Category::Cookies),
+            [chrome.join("Default").join("Network").join("Cookies")]
+        );
+        assert!(chrome_browser.paths(Category::History).is_empty());
+
+        assert_eq!(profile_names(firefox_browser), ["abc.default"]);
+        assert_eq!(
+            firefox_browser.paths(Category::Cache),
+            [cache.join("mozilla").join("firefox").join("abc.default").join("cache2")]
+        );
+        assert_eq!(
+            firefox_browser.paths(Category::ServiceWorker),
+            [firefox.join("storage").join("default").join("https+++example.org").join("cache")]
+        );
+        assert_eq!(firefox_browser.paths(Category::Cookies), [firefox.join("cookies.sqlite")]);
+
+        fs::remove_dir_all(&home).unwrap();
+    }
+
+    #[test]
+    fn finds_windows_profiles_and_caches() {
+        let root = temp_dir("windows");
+        let (local, roaming) = (root.join("Local"), root.join("Roaming"));
+        let edge = local.join(r"Microsoft\Edge").join("User Data");
+        touch(edge.join("Default").join("Preferences"));
+        touch(edge.join("Default").join("Cache").join("data_0"));
+        // Opera 的数据目录本身就是配置文件，缓存在 %LOCALAPPDATA%
+        let opera = roaming.join(r"Opera Software\Opera Stable");
+        touch(opera.join("Preferences"));
+        touch(opera.join("History"));
+        let opera_cache = local.join(r"Opera Software\Opera Stable");
+        touch(opera_cache.join("Cache").join("data_0"));
+        let firefox = roaming.join(r"Mozilla\Firefox\Profiles");
+        touch(firefox.join("xyz.default-release").join("prefs.js"));
+        let firefox_cache = local.join(r"Mozilla\Firefox\Profiles").join("xyz.default-release");
+        touch(firefox_cache.join("startupCache").join("scriptCache"));
+
+        let browsers = installed_in(windows_browsers(Some(local.clone()), Some(roaming.clone())));
+        let names: Vec<&str> = browsers.iter().map(|b| b.name).collect();
+        assert_eq!(names, ["Edge", "Opera", "Firefox"]);
+
+        assert_eq!(profile_names(&browsers[0]), ["Default"]);
+        assert_eq!(browsers[0].paths(Category::Cache), [edge.join("Default").join("Cache")]);
+        assert_eq!(profile_names(&browsers[1]), ["Default"]);
+        assert_eq!(browsers[1].paths(Category::Cache), [opera_cache.join("Cache")]);
+        assert_eq!(browsers[1].paths(Category::History), [opera.join("History")]);
+        assert_eq!(profile_names(&browsers[2]), ["xyz.default-release"]);
+        assert_eq!(browsers[2].paths(Category::CodeCache), [firefox_cache.join("startupCache")]);
+
+        // 没有 %LOCALAPPDATA% 时 Opera 的缓存退回数据目录，也不查找 Firefox
+        let browsers = windows_browsers(None, Some(roaming));
+        assert_eq!(browsers.len(), 1);
+        assert_eq!(browsers[0].cache_dir, opera);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn matches_running_processes() {
+        let browsers = linux_browsers(Path::new("/home/u"), Path::new("/home/u/.config"), Path::new("/home/u/.cache"));
+        let chromium = browsers.iter().find(|b| b.name == "Chromium").unwrap();
+        assert!(chromium.is_running(&["bash".into(), "chromium-browse".into()]));
+        assert!(!chromium.is_running(&["chrome".into()]));
+    }
+}
@@ -0,0 +1,303 @@
+// System components: Windows Update downloads, Delivery Optimization cache and the WinSxS component store
+//
+// 下载缓存由服务占用：清理前停止相关服务（只停止正在运行的），清理后再启动停止过的服务。
+// 组件存储通过 `dism /Online /Cleanup-Image /StartComponentCleanup` 清理，输出逐行回调，
+// 结束后解析结果。所有外部命令都通过 CommandRunner 执行，测试时可以替换。
+use std::env;
+use std::io::{self, BufRead, BufReader};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+// 执行外部命令：每行输出调用一次 on_line，返回退出码
+pub trait CommandRunner {
+    fn run(&mut self, program: &str, args: &[&str], on_line: &mut dyn FnMut(&str)) -> io::Result<i32>;
+}
+
+// 实际执行命令
+pub struct SystemRunner;
+
+impl CommandRunner for SystemRunner {
+    fn run(&mut self, program: &str, args: &[&str], on_line: &mut dyn FnMut(&str)) -> io::Result<i32> {
+        let mut command = Command::new(program);
+        // stdin 不继承：net 等命令可能等待确认输入
+        command.args(args).stdin(Stdio::null()).stdout(Stdio::piped()).stderr(Stdio::null());
+        #[cfg(windows)]
+        {
+            use std::os::windows::process::CommandExt;
+            // CREATE_NO_WINDOW
+            command.creation_flags(0x08000000);
+        }
+        let mut child = command.spawn()?;
+        if let Some(stdout) = child.stdout.take() {
+            for line in BufReader::new(stdout).split(b'\n').map_while(Result::ok) {
+                // 进度条用 '\r' 原地刷新，只取最后一段
+                let text = String::from_utf8_lossy(&line);
+                if let Some(segment) = text.split('\r').map(str::trim).rfind(|s| !s.is_empty()) {
+                    on_line(segment);
+                }
+            }
+        }
+        Ok(child.wait()?.code().unwrap_or(-1))
+    }
+}
+
+// 需要停止服务才能清理的下载缓存
+pub struct ServiceCache {
+    pub id: &'static str,
+    pub dir: fn() -> Option<PathBuf>,
+    pub services: &'static [&'static str],
+}
+
+fn windows_dir(path: &str) -> Option<PathBuf> {
+    if !cfg!(windows) {
+        return None;
+    }
+    env::var("windir").ok().map(|windir| PathBuf::from(windir).join(path))
+}
+
+pub const SERVICE_CACHES: [ServiceCache; 2] = [
+    ServiceCache {
+        id: "windows-update-cache",
+        dir: || windows_dir(r"SoftwareDistribution\Download"),
+        services: &["wuauserv", "bits"],
+    },
+    ServiceCache {
+        id: "delivery-optimization",
+        dir: || windows_dir(r"ServiceProfiles\NetworkService\AppData\Local\Microsoft\Windows\DeliveryOptimization\Cache"),
+        services: &["dosvc"],
+    },
+];
+
+pub fn find_cache(id: &str) -> Option<&'static ServiceCache> {
+    SERVICE_CACHES.iter().find(|cache| cache.id == id)
+}
+
+// sc query 的输出中 STATE 行为 RUNNING
+pub fn service_running(runner: &mut dyn CommandRunner, service: &str) -> io::Result<bool> {
+    let mut running = false;
+    runner.run("sc", &["query", service], &mut |line| {
+        if line.trim_start().starts_with("STATE") && line.contains("RUNNING") {
+            running = true;
+        }
+    })?;
+    Ok(running)
+}
+
+// 停止正在运行的服务，返回停止了哪些；某个服务停止失败时重新启动已停止的服务并返回错误
+pub fn stop_services(
+    runner: &mut dyn CommandRunner,
+    services: &[&'static str],
+    log: &mut dyn FnMut(&str),
+) -> Result<Vec<&'static str>, String> {
+    let mut stopped = Vec::new();
+    for &service in services {
+        match service_running(runner, service) {
+            Ok(true) => {}
+            Ok(false) => continue,
+            Err(e) => {
+                start_services(runner, &stopped, log);
+                return Err(format!("Cannot query service {}: {}", service, e));
+            }
+        }
+        log(&format!("   ⏸  Stopping service {}...", service));
+        match runner.run("net", &["stop", service], &mut |_| {}) {
+            Ok(0) => stopped.push(service),
+            result => {
+                start_services(runner, &stopped, log);
+                let reason = match result {
+                    Ok(code) => format!("exit code {}", code),
+                    Err(e) => e.to_string(),
+                };
+                return Err(format!("Cannot stop service {} ({})", service, reason));
+            }
+        }
+    }
+    Ok(stopped)
+}
+
+// 按停止的相反顺序重新启动服务
+pub fn start_services(runner: &mut dyn CommandRunner, stopped: &[&str], log: &mut dyn FnMut(&str)) {
+    for service in stopped.iter().rev() {
+        log(&format!("   ▶  Starting service {}...", service));
+        match runner.run("net", &["start", service], &mut |_| {}) {
+            Ok(0) => {}
+            Ok(code) => log(&format!("   ⚠️  Failed to start {} (exit code {})", service, code)),
+            Err(e) => log(&format!("   ⚠️  Failed to start {}: {}", service, e)),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DismResult {
+    Completed { restart_required: bool },
+    Failed { code: String, message: Option<String> },
+}
+
+// DISM 以 "The operation completed successfully." 结束；失败时输出 "Error: <代码>"，下一行为说明
+pub fn parse_dism_output(lines: &[String], exit_code: i32) -> DismResult {
+    // 3010：成功，需要重新启动
+    let restart_required = exit_code == 3010 || lines.iter().any(|line| line.to_lowercase().contains("restart"));
+    if let Some(index) = lines.iter().position(|line| line.trim_start().starts_with("Error:")) {
+        let code = lines[index].trim_start()["Error:".len()..].trim().to_string();
+        let message = lines[index + 1..]
+            .iter()
+            .map(|line| line.trim())
+            .find(|line| !line.is_empty())
+            .map(str::to_string);
+        return DismResult::Failed { code, message };
+    }
+    if exit_code == 0 || exit_code == 3010 || lines.iter().any(|line| line.contains("completed successfully")) {
+        return DismResult::Completed { restart_required };
+    }
+    DismResult::Failed {
+        code: exit_code.to_string(),
+        message: None,
+    }
+}
+
+// 清理组件存储（可能需要几分钟），输出逐行传给 on_line
+pub fn start_component_cleanup(runner: &mut dyn CommandRunner, on_line: &mut dyn FnMut(&str)) -> io::Result<DismResult> {
+    let mut lines = Vec::new();
+    let exit_code = runner.run(
+        "dism",
+        &["/Online", "/Cleanup-Image", "/StartComponentCleanup"],
+        &mut |line| {
+            on_line(line);
+            lines.push(line.to_string());
+        },
+    )?;
+    Ok(parse_dism_output(&lines, exit_code))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 按命令返回预设的输出和退出码，并记录执行过的命令
+    #[derive(Default)]
+    struct MockRunner {
+        responses: Vec<(String, Vec<&'static str>, i32)>,
+        calls: Vec<String>,
+    }
+
+    impl MockRunner {
+        fn respond(mut self, command: &str, lines: &[&'static str], code: i32) -> Self {
+            self.responses.push((command.to_string(), lines.to_vec(), code));
+            self
+        }
+    }
+
+    impl CommandRunner for MockRunner {
+        fn run(&mut self, program: &str, args: &[&str], on_line: &mut dyn FnMut(&str)) -> io::Result<i32> {
+            let command = format!("{} {}", program, args.join(" "));
+            self.calls.push(command.clone());
+            match self.responses.iter().find(|(c, _, _)| *c == command) {
+                Some((_, lines, code)) => {
+                    lines.iter().for_each(|line| on_line(line));
+                    Ok(*code)
+                }
+                None => Err(io::Error::new(io::ErrorKind::NotFound, command)),
+            }
+        }
+    }
+
+    const RUNNING: &str = "        STATE              : 4  RUNNING";
+    const STOPPED: &str = "        STATE              : 1  STOPPED";
+
+    #[test]
+    fn stops_only_running_services_and_restarts_them() {
+        let mut runner = MockRunner::default()
+            .respond("sc query wuauserv", &["SERVICE_NAME: wuauserv", RUNNING], 0)
+            .respond("sc query bits", &["SERVICE_NAME: bits", STOPPED], 0)
+            .respond("net stop wuauserv", &[], 0)
+            .respond("net start wuauserv", &[], 0);
+        let mut log = Vec::new();
+        let stopped = stop_services(&mut runner, &["wuauserv", "bits"], &mut |line| log.push(line.to_string()));
+        assert_eq!(stopped, Ok(vec!["wuauserv"]));
+
+        start_services(&mut runner, &["wuauserv"], &mut |line| log.push(line.to_string()));
+        assert_eq!(
+            runner.calls,
+            vec!["sc query wuauserv", "net stop wuauserv", "sc query bits", "net start wuauserv"]
+        );
+        assert_eq!(
+            log,
+            vec!["   ⏸  Stopping service wuauserv...", "   ▶  Starting service wuauserv..."]
+        );
+    }
+
+    #[test]
+    fn failed_stop_restarts_already_stopped_services() {
+        let mut runner = MockRunner::default()
+            .respond("sc query wuauserv", &[RUNNING], 0)
+            .respond("sc query bits", &[RUNNING], 0)
+            .respond("net stop wuauserv", &[], 0)
+            .respond("net stop bits", &[], 2)
+            .respond("net start wuauserv", &[], 0);
+        let result = stop_services(&mut runner, &["wuauserv", "bits"], &mut |_| {});
+        assert_eq!(result, Err("Cannot stop service bits (exit code 2)".to_string()));
+        assert_eq!(runner.calls.last().map(String::as_str), Some("net start wuauserv"));
+    }
+
+    #[test]
+    fn streams_and_parses_successful_cleanup() {
+        let mut runner = MockRunner::default().respond(
+            "dism /Online /Cleanup-Image /StartComponentCleanup",
+            &[
+                "Deployment Image Servicing and Management tool",
+                "[==========================100.0%==========================]",
+                "The operation completed successfully.",
+            ],
+            0,
+        );
+        let mut streamed = Vec::new();
+        let result = start_component_cleanup(&mut runner, &mut |line| streamed.push(line.to_string())).unwrap();
+        assert_eq!(result, DismResult::Completed { restart_required: false });
+        assert_eq!(streamed.len(), 3);
+    }
+
+    #[test]
+    fn parses_dism_errors() {
+        let lines: Vec<String> = ["", "Error: 740", "", "Elevated permissions are required to run DISM."]
+            .iter()
+            .map(|line| line.to_string())
+            .collect();
+        assert_eq!(
+            parse_dism_output(&lines, 740),
+            DismResult::Failed {
+                code: "740".to_string(),
+                message: Some("Elevated permissions are required to run DISM.".to_string()),
+            }
+        );
+        assert_eq!(
+            parse_dism_output(&[], 3010),
+            DismResult::Completed { restart_required: true }
+        );
+        assert_eq!(
+            parse_dism_output(&[], 5),
+            DismResult::Failed {
+                code: "5".to_string(),
+                message: None
+            }
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn system_runner_keeps_the_last_progress_update() {
+        let mut lines = Vec::new();
+        let code = SystemRunner
+            .run("sh", &["-c", "printf '10%%\\r50%%\\r100%%\\ndone\\n'; exit 3"], &mut |line| {
+                lines.push(line.to_string())
+            })
+            .unwrap();
+        assert_eq!(code, 3);
+        assert_eq!(lines, vec!["100%", "done"]);
+    }
+
+    #[test]
+    fn missing_dism_is_an_error() {
+        let mut runner = MockRunner::default();
+        assert!(start_component_cleanup(&mut runner, &mut |_| {}).is_err());
+    }
+}
@@ -0,0 +1,129 @@
+// Configuration file support
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+// 用户配置（config.toml），所有字段都有默认值，缺失的配置文件等价于空配置
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    // 温度传感器的友好名称与分组，键为 sysinfo 报告的原始标签
+    //
+    // [sensors."coretemp Package id 0"]
+    // name = "CPU Package"
+    // group = "CPU"
+    pub sensors: BTreeMap<String, SensorConfig>,
+    // TUI 主题名称（内置主题或 [themes.*] 中的自定义主题）
+    pub theme: Option<String>,
+    pub themes: BTreeMap<String, ThemeConfig>,
+    // 清理时排除的路径和通配符，在所有目录层级生效
+    //
+    // [clean]
+    // exclude = ['D:\Projects', '**/node_modules', '*.keep']
+    // protect_projects = true
+    pub clean: CleanConfig,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct CleanConfig {
+    pub exclude: Vec<String>,
+    // 跳过版本控制目录和项目根目录（包含 .git、Cargo.toml、package.json 等的目录）
+    pub protect_projects: bool,
+}
+
+impl Default for CleanConfig {
+    fn default() -> Self {
+        Self {
+            exclude: Vec::new(),
+            protect_projects: true,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default)]
+pub struct SensorConfig {
+    pub name: Option<String>,
+    pub group: Option<String>,
+}
+
+// 自定义主题，未设置的颜色沿用 base 主题
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default)]
+pub struct ThemeConfig {
+    pub base: Option<String>,
+    pub accent: Option<String>,
+    pub title: Option<String>,
+    pub text: Option<String>,
+    pub muted: Option<String>,
+    pub selected_fg: Option<String>,
+    pub selected_bg: Option<String>,
+    pub primary: Option<String>,
+    pub secondary: Option<String>,
+    pub ok: Option<String>,
+    pub warn: Option<String>,
+    pub critical: Option<String>,
+    pub background: Option<String>,
+    pub ascii: Option<bool>,
+}
+
+impl Config {
+    // Load config.toml from the lemo config directory (missing file => defaults)
+    pub fn load() -> Result<Self> {
+        let path = match config_path() {
+            Some(p) if p.exists() => p,
+            _ => return Ok(Self::default()),
+        };
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Cannot read {}", path.display()))?;
+        toml::from_str(&content).with_context(|| format!("Invalid config file {}", path.display()))
+    }
+}
+
+// 非空的环境变量，作为目录
+pub fn env_dir(name: &str) -> Option<PathBuf> {
+    env::var(name).ok().filter(|dir| !dir.is_empty()).map(PathBuf::from)
+}
+
+// 用户主目录：Windows 为 %USERPROFILE%，其他平台为 $HOME
+pub fn home_dir() -> Option<PathBuf> {
+    env_dir("USERPROFILE").or_else(|| env_dir("HOME"))
+}
+
+// XDG 基础目录（非 Windows 平台）：环境变量未设置时使用主目录下的默认位置
+pub fn xdg_config_home() -> Option<PathBuf> {
+    env_dir("XDG_CONFIG_HOME").or_else(|| home_dir().map(|home| home.join(".config")))
+}
+
+pub fn xdg_cache_home() -> Option<PathBuf> {
+    env_dir("XDG_CACHE_HOME").or_else(|| home_dir().map(|home| home.join(".cache")))
+}
+
+pub fn xdg_data_home() -> Option<PathBuf> {
+    env_dir("XDG_DATA_HOME").or_else(|| home_dir().map(|home| home.join(".local").join("share")))
+}
+
+// lemo 配置目录：Windows 为 %APPDATA%\lemo，其他平台为 $XDG_CONFIG_HOME/lemo 或 ~/.config/lemo
+pub fn config_dir() -> Option<PathBuf> {
+    if cfg!(windows) {
+        return env_dir("APPDATA").map(|p| p.join("lemo"));
+    }
+    xdg_config_home().map(|dir| dir.join("lemo"))
+}
+
+pub fn config_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("config.toml"))
+}
+
+// lemo 数据目录（快照、日志等）：Windows 为 %LOCALAPPDATA%\lemo，其他平台为 $XDG_DATA_HOME/lemo 或 ~/.local/share/lemo
+pub fn data_dir() -> Option<PathBuf> {
+    if cfg!(windows) {
+        return env_dir("LOCALAPPDATA").map(|p| p.join("lemo"));
+    }
+    xdg_data_home().map(|dir| dir.join("lemo"))
+}
@@ -0,0 +1,212 @@
+// `lemo daemon`: run registered schedules when they are due and enforce disk-space policies
+//
+// 每个检查周期重新读取 schedules.toml 和 policies.toml（命令行或 TUI 的修改无需重启 daemon）。
+// 使用电池供电或 CPU 繁忙时跳过本次运行，下一个检查周期再试。
+use crate::battery::{self, ChargeState};
+use crate::config;
+use crate::history::format_bytes;
+use crate::policy;
+use crate::schedule::{self, Schedule};
+use crate::utils::run_unattended;
+use anyhow::{Context, Result};
+use chrono::Local;
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+use sysinfo::System;
+
+pub struct DaemonOptions {
+    pub check_interval: Duration,
+    // CPU 使用率高于该值时视为繁忙
+    pub max_cpu: f32,
+    pub allow_battery: bool,
+}
+
+fn log(message: &str) {
+    println!("[{}] {}", Local::now().format("%Y-%m-%d %H:%M:%S"), message);
+}
+
+fn on_battery() -> bool {
+    let status = battery::read_power_status();
+    status.ac_online == Some(false)
+        || status.batteries.iter().any(|b| b.state == ChargeState::Discharging)
+}
+
+// 两次采样之间的平均 CPU 使用率
+fn cpu_usage() -> f32 {
+    let mut system = System::new();
+    system.refresh_cpu_usage();
+    thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL.max(Duration::from_secs(1)));
+    system.refresh_cpu_usage();
+    system.global_cpu_usage()
+}
+
+fn skip_reason(options: &DaemonOptions) -> Option<String> {
+    if !options.allow_battery && on_battery() {
+        return Some("on battery".to_string());
+    }
+    let usage = cpu_usage();
+    if usage > options.max_cpu {
+        return Some(format!("system busy (CPU {:.0}%)", usage));
+    }
+    None
+}
+
+// 报告保存在 <数据目录>/reports/<name>-YYYYmmdd-HHMMSS.log
+fn write_report(name: &str, header: &str, lines: &[String], result: &str) -> Result<PathBuf> {
+    let dir = config::data_dir()
+        .map(|d| d.join("reports"))
+        .ok_or_else(|| anyhow::anyhow!("Cannot determine data directory"))?;
+    fs::create_dir_all(&dir).with_context(|| format!("Cannot create {}", dir.display()))?;
+
+    let path = dir.join(format!("{}-{}.log", name, Local::now().format("%Y%m%d-%H%M%S")));
+    let mut content = format!("{}\n\n", header);
+    for line in lines {
+        content.push_str(line);
+        content.push('\n');
+    }
+    content.push('\n');
+    content.push_str(result);
+    content.push('\n');
+    fs::write(&path, content).with_context(|| format!("Cannot write {}", path.display()))?;
+    Ok(path)
+}
+
+fn run_due_schedules(options: &DaemonOptions) {
+    let schedules = match schedule::load() {
+        Ok(schedules) => schedules,
+        Err(e) => {
+            log(&format!("❌ {:#}", e));
+            return;
+        }
+    };
+    let mut state = schedule::load_state();
+    let now = Local::now();
+    let due: Vec<&Schedule> = schedules.iter().filter(|s| s.is_due(now, state.get(&s.id))).collect();
+    if due.is_empty() {
+        return;
+    }
+
+    // 电池和负载只在有任务到期时检查一次
+    let skip = skip_reason(options);
+    for schedule in due {
+        let entry = state.entry(schedule.id).or_default();
+        if let Some(reason) = &skip {
+            log(&format!("⏸ Skipping #{} {}: {}", schedule.id, schedule.task, reason));
+            entry.last_result = Some(format!("⏸ Skipped: {}", reason));
+            continue;
+        }
+
+        log(&format!("▶ Running #{} {} ({})", schedule.id, schedule.task, schedule.options_display()));
+        let (lines, result) = match schedule.clean_options() {
+            Ok(clean_options) => run_unattended(&schedule.task, &clean_options),
+            Err(e) => (Vec::new(), Err(e)),
+        };
+        let summary = match &result {
+            Ok(()) => "✅ Completed".to_string(),
+            Err(e) => format!("❌ {:#}", e),
+        };
+        let header = format!(
+            "Schedule #{}: {} ({}, {})",
+            schedule.id,
+            schedule.task,
+            schedule.when_display(),
+            schedule.options_display()
+        );
+        let name = format!("{}-{}", schedule.task, schedule.id);
+        match write_report(&name, &header, &lines, &summary) {
+            Ok(path) => {
+                log(&format!("{} (report: {})", summary, path.display()));
+                entry.last_report = Some(path);
+            }
+            Err(e) => log(&format!("{} (report not written: {:#})", summary, e)),
+        }
+        entry.last_run = Some(Local::now().to_rfc3339());
+        entry.last_result = Some(summary);
+    }
+    if let Err(e) = schedule::save_state(&state) {
+        log(&format!("❌ {:#}", e));
+    }
+}
+
+fn enforce_policies(options: &DaemonOptions) {
+    let policies = match policy::load() {
+        Ok(policies) => policies,
+        Err(e) => {
+            log(&format!("❌ {:#}", e));
+            return;
+        }
+    };
+    let mut state = policy::load_state();
+    let now = Local::now();
+    let triggered: Vec<_> = policies
+        .iter()
+        .filter(|p| p.cooled_down(now, state.get(&p.id)))
+        .filter_map(|p| {
+            let disk = policy::find_disk(&p.drive())?;
+            let needed = p.needed_bytes(&disk)?;
+            Some((p, disk, needed))
+        })
+        .collect();
+    if triggered.is_empty() {
+        return;
+    }
+
+    let skip = skip_reason(options);
+    for (policy, disk, needed) in triggered {
+        let entry = state.entry(policy.id).or_default();
+        if let Some(reason) = &skip {
+            log(&format!("⏸ Skipping policy #{} ({}): {}", policy.id, policy.rule_display(), reason));
+            entry.last_result = Some(format!("⏸ Skipped: {}", reason));
+            continue;
+        }
+
+        log(&format!(
+            "▶ Policy #{}: {} free on {}, freeing {} ({})",
+            policy.id,
+            format_bytes(disk.available),
+            policy.drive(),
+            format_bytes(needed),
+            policy.options_display()
+        ));
+        let (lines, result) = policy::enforce(policy, &disk, needed);
+        let summary = match &result {
+            Ok(outcome) => outcome.clone(),
+            Err(e) => format!("❌ {:#}", e),
+        };
+        let header = format!("Policy #{}: {} ({})", policy.id, policy.rule_display(), policy.options_display());
+        match write_report(&format!("policy-{}", policy.id), &header, &lines, &summary) {
+            Ok(path) => {
+                log(&format!("{} (report: {})", summary, path.display()));
+                entry.last_report = Some(path);
+            }
+            Err(e) => log(&format!("{} (report not written: {:#})", summary, e)),
+        }
+        entry.last_run = Some(Local::now().to_rfc3339());
+        entry.last_result = Some(summary);
+    }
+    if let Err(e) = policy::save_state(&state) {
+        log(&format!("❌ {:#}", e));
+    }
+}
+
+pub fn run_daemon(options: DaemonOptions) -> Result<()> {
+    log(&format!(
+        "🕒 lemo daemon {} started (checking every {}s, schedules: {}, policies: {})",
+        env!("CARGO_PKG_VERSION"),
+        options.check_interval.as_secs(),
+        schedule::schedules_path()
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|| "-".to_string()),
+        policy::policies_path()
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|| "-".to_string())
+    ));
+
+    loop {
+        run_due_schedules(&options);
+        enforce_policies(&options);
+        thread::sleep(options.check_interval);
+    }
+}
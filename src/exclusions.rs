@@ -0,0 +1,344 @@
+// Cleaner exclusions: configured paths and globs plus built-in VCS and project protection
+//
+// 规则在所有目录层级生效：被排除的文件保留，被排除的目录整个跳过。
+// - 不含 '/' 或 '\' 的规则匹配文件名，如 "*.keep"、"node_modules"
+// - 含分隔符的规则匹配完整路径，如 "D:\Projects"（该目录及其下所有内容）、"**/build/*.log"
+// - `*` 和 `?` 不跨越目录，`**` 匹配任意多级目录
+use crate::config::Config;
+use anyhow::Result;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+// 版本控制目录，任何层级都不删除
+const VCS_DIRS: [&str; 3] = [".git", ".hg", ".svn"];
+
+// 项目根目录的标志：目录中含有这些文件（或版本控制目录）时整个跳过
+const PROJECT_MARKERS: [&str; 14] = [
+    ".git",
+    ".hg",
+    ".svn",
+    "Cargo.toml",
+    "package.json",
+    "pyproject.toml",
+    "setup.py",
+    "go.mod",
+    "pom.xml",
+    "build.gradle",
+    "build.gradle.kts",
+    "CMakeLists.txt",
+    "composer.json",
+    "Gemfile",
+];
+const PROJECT_EXTENSIONS: [&str; 3] = ["sln", "csproj", "vcxproj"];
+
+#[derive(Debug)]
+enum Rule {
+    Vcs,
+    ProjectRoot,
+    // 完整路径前缀（不含通配符）
+    Path(String),
+    // 完整路径通配符
+    PathGlob(Vec<char>),
+    // 文件名通配符
+    NameGlob(Vec<char>),
+}
+
+#[derive(Debug)]
+struct Entry {
+    label: String,
+    rule: Rule,
+}
+
+#[derive(Debug)]
+pub struct Exclusions {
+    entries: Vec<Entry>,
+    // 每条规则保留下来的文件数
+    saved: Mutex<Vec<usize>>,
+}
+
+// 统一使用 '/' 分隔；Windows 路径不区分大小写
+fn normalize(text: &str) -> String {
+    let text = text.replace('\\', "/");
+    let text = text.trim_end_matches('/');
+    if cfg!(windows) {
+        text.to_lowercase()
+    } else {
+        text.to_string()
+    }
+}
+
+// 通配符匹配：* 和 ? 不跨越 '/'，** 匹配任意多级目录（开头或 '/' 之后的 "**/" 也可以匹配零级）
+//
+// 双指针回溯：只记住最近的 * 和最近的 ** 的位置，失配时先让 * 多匹配一个字符（不跨越 '/'），
+// 不行再让 ** 多匹配（"**/" 每次多匹配一级目录）；遇到新的 ** 时丢弃之前的 *。
+// 每次回溯都向前推进文本位置，最坏 O(模式长度 × 文本长度)。
+fn glob_match(pattern: &[char], text: &[char]) -> bool {
+    let (mut p, mut t) = (0, 0);
+    // (* 之后的模式位置, * 当前匹配到的文本位置)
+    let mut star: Option<(usize, usize)> = None;
+    // (** 之后的模式位置, ** 当前匹配到的文本位置, 是否为 "**/")
+    let mut globstar: Option<(usize, usize, bool)> = None;
+    while p < pattern.len() || t < text.len() {
+        match pattern.get(p) {
+            Some('*') if pattern.get(p + 1) == Some(&'*') => {
+                // 段中间的 ** 只匹配任意字符，其后的 '/' 必须出现
+                let slash = pattern.get(p + 2) == Some(&'/') && (p == 0 || pattern[p - 1] == '/');
+                p += if slash { 3 } else { 2 };
+                globstar = Some((p, t, slash));
+                star = None;
+                continue;
+            }
+            Some('*') => {
+                p += 1;
+                star = Some((p, t));
+                continue;
+            }
+            Some('?') if text.get(t).is_some_and(|c| *c != '/') => {
+                p += 1;
+                t += 1;
+                continue;
+            }
+            Some(c) if *c != '?' && text.get(t) == Some(c) => {
+                p += 1;
+                t += 1;
+                continue;
+            }
+            _ => {}
+        }
+
+        if let Some((star_p, star_t)) = star {
+            if text.get(star_t).is_some_and(|c| *c != '/') {
+                star = Some((star_p, star_t + 1));
+                p = star_p;
+                t = star_t + 1;
+                continue;
+            }
+        }
+        if let Some((globstar_p, globstar_t, slash)) = globstar {
+            // "**/" 匹配空串或以 '/' 结尾的若干级目录
+            let next = if slash {
+                text[globstar_t..].iter().position(|c| *c == '/').map(|i| globstar_t + i + 1)
+            } else {
+                (globstar_t < text.len()).then_some(globstar_t + 1)
+            };
+            if let Some(next) = next {
+                globstar = Some((globstar_p, next, slash));
+                star = None;
+                p = globstar_p;
+                t = next;
+                continue;
+            }
+        }
+        return false;
+    }
+    true
+}
+
+impl Default for Exclusions {
+    // 只有内置保护
+    fn default() -> Self {
+        Self::new(&[], true)
+    }
+}
+
+impl Exclusions {
+    pub fn new(patterns: &[String], protect_projects: bool) -> Self {
+        let mut entries = Vec::new();
+        if protect_projects {
+            entries.push(Entry {
+                label: "version control directories (built-in)".to_string(),
+                rule: Rule::Vcs,
+            });
+            entries.push(Entry {
+                label: "project roots (built-in)".to_string(),
+                rule: Rule::ProjectRoot,
+            });
+        }
+        for pattern in patterns.iter().filter(|p| !p.trim().is_empty()) {
+            let normalized = normalize(pattern.trim());
+            let rule = if !normalized.contains('/') {
+                Rule::NameGlob(normalized.chars().collect())
+            } else if normalized.contains(['*', '?']) {
+                Rule::PathGlob(normalized.chars().collect())
+            } else {
+                Rule::Path(normalized)
+            };
+            entries.push(Entry {
+                label: pattern.trim().to_string(),
+                rule,
+            });
+        }
+        let saved = Mutex::new(vec![0; entries.len()]);
+        Self { entries, saved }
+    }
+
+    // 内置保护 + config.toml 中 [clean] 的规则
+    pub fn load() -> Result<Self> {
+        let config = Config::load()?;
+        Ok(Self::new(&config.clean.exclude, config.clean.protect_projects))
+    }
+
+    // 路径本身命中的规则（项目根目录需要目录内容，见 project_rule）
+    pub fn rule_for(&self, path: &Path, is_dir: bool) -> Option<usize> {
+        let full = normalize(&path.to_string_lossy());
+        let name = normalize(&path.file_name().unwrap_or_default().to_string_lossy());
+        self.entries.iter().position(|entry| match &entry.rule {
+            Rule::Vcs => is_dir && VCS_DIRS.contains(&name.as_str()),
+            Rule::ProjectRoot => false,
+            Rule::Path(prefix) => full == *prefix || full.starts_with(&format!("{}/", prefix)),
+            Rule::PathGlob(pattern) => glob_match(pattern, &full.chars().collect::<Vec<_>>()),
+            Rule::NameGlob(pattern) => glob_match(pattern, &name.chars().collect::<Vec<_>>()),
+        })
+    }
+
+    // 目录内容中有项目标志文件时，返回项目保护规则
+    pub fn project_rule(&self, children: &[PathBuf]) -> Option<usize> {
+        let rule = self.entries.iter().position(|entry| matches!(entry.rule, Rule::ProjectRoot))?;
+        children
+            .iter()
+            .any(|child| {
+                let name = child.file_name().unwrap_or_default().to_string_lossy();
+                PROJECT_MARKERS.contains(&name.as_ref())
+                    || child
+                        .extension()
+                        .is_some_and(|ext| PROJECT_EXTENSIONS.contains(&ext.to_string_lossy().as_ref()))
+            })
+            .then_some(rule)
+    }
+
+    pub fn record(&self, rule: usize, files: usize) {
+        if let Ok(mut saved) = self.saved.lock() {
+            saved[rule] += files;
+        }
+    }
+
+    // (规则, 保留的文件数)，只包含实际生效过的规则
+    pub fn summary(&self) -> Vec<(String, usize)> {
+        let saved = self.saved.lock().map(|s| s.clone()).unwrap_or_default();
+        self.entries
+            .iter()
+            .zip(saved)
+            .filter(|(_, count)| *count > 0)
+            .map(|(entry, count)| (entry.label.clone(), count))
+            .collect()
+    }
+}
+
+// 目录下的文件数（记录被排除目录保留了多少文件）
+pub fn count_files(path: &Path) -> usize {
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !metadata.is_dir() {
+        return 1;
+    }
+    fs::read_dir(path)
+        .map(|entries| entries.flatten().map(|entry| count_files(&entry.path())).sum())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn glob(pattern: &str, text: &str) -> bool {
+        glob_match(&pattern.chars().collect::<Vec<_>>(), &text.chars().collect::<Vec<_>>())
+    }
+
+    fn user_rules(patterns: &[&str]) -> Exclusions {
+        Exclusions::new(&patterns.iter().map(|p| p.to_string()).collect::<Vec<_>>(), false)
+    }
+
+    #[test]
+    fn single_star_and_question_mark_stay_in_one_directory() {
+        assert!(glob("/tmp/*.log", "/tmp/a.log"));
+        assert!(glob("/tmp/*.log", "/tmp/.log"));
+        assert!(!glob("/tmp/*.log", "/tmp/sub/a.log"));
+        assert!(glob("/tmp/?.log", "/tmp/a.log"));
+        assert!(!glob("/tmp/?.log", "/tmp//.log"));
+        assert!(!glob("/tmp/a?b", "/tmp/a/b"));
+        assert!(glob("*", "name"));
+        assert!(!glob("*", "dir/name"));
+    }
+
+    #[test]
+    fn double_star_matches_any_number_of_directories() {
+        // "**/" 也可以匹配零级目录
+        assert!(glob("**/build/*.log", "build/x.log"));
+        assert!(glob("**/build/*.log", "/home/me/app/build/x.log"));
+        assert!(!glob("**/build/*.log", "/home/me/app/build/sub/x.log"));
+        assert!(glob("/tmp/**/cache", "/tmp/cache"));
+        assert!(glob("/tmp/**/cache", "/tmp/a/b/cache"));
+        assert!(!glob("/tmp/**/cache", "/var/a/cache"));
+        assert!(glob("/tmp/**", "/tmp/a/b/c"));
+        // 段中间的 ** 匹配任意字符，但不能匹配零级目录
+        assert!(glob("/tmp/a**/x", "/tmp/ab/c/x"));
+        assert!(!glob("/tmp/a**/x", "/tmp/ax"));
+    }
+
+    #[test]
+    fn many_stars_over_a_long_path_stay_fast() {
+        // 递归实现在这里需要指数级时间
+        let text = format!("/{}", ["a"; 200].join("/"));
+        let pattern = format!("{}/b", ["**"; 10].join("/"));
+        let started = std::time::Instant::now();
+        assert!(!glob(&pattern, &text));
+        assert!(!glob(&format!("/{}b", "*a".repeat(30)), &format!("/{}", "a".repeat(400))));
+        assert!(glob(&format!("/{}", "*a".repeat(30)), &format!("/{}", "a".repeat(400))));
+        assert!(started.elapsed() < std::time::Duration::from_secs(1));
+    }
+
+    #[test]
+    fn name_globs_match_the_file_name_only() {
+        let exclusions = user_rules(&["*.keep", "node_modules"]);
+        assert_eq!(exclusions.rule_for(Path::new("/tmp/a/data.keep"), false), Some(0));
+        assert_eq!(exclusions.rule_for(Path::new("/tmp/app/node_modules"), true), Some(1));
+        assert_eq!(exclusions.rule_for(Path::new("/tmp/data.keep.bak"), false), None);
+        assert_eq!(exclusions.rule_for(Path::new("/tmp/node_modules2"), true), None);
+    }
+
+    #[test]
+    fn path_rules_cover_the_directory_and_everything_below() {
+        let exclusions = user_rules(&[r"D:\Projects\", "/srv/data"]);
+        assert_eq!(exclusions.rule_for(Path::new(r"D:\Projects"), true), Some(0));
+        assert_eq!(exclusions.rule_for(Path::new(r"D:\Projects\app\x.tmp"), false), Some(0));
+        assert_eq!(exclusions.rule_for(Path::new(r"D:\Projects2\x.tmp"), false), None);
+        assert_eq!(exclusions.rule_for(Path::new("/srv/data/cache/x"), false), Some(1));
+        assert_eq!(exclusions.rule_for(Path::new("/srv/database"), true), None);
+    }
+
+    #[test]
+    fn windows_rules_ignore_case() {
+        let exclusions = user_rules(&[r"D:\Projects", "*.KEEP", r"C:\Temp\**\*.log"]);
+        let folded = cfg!(windows);
+        assert_eq!(exclusions.rule_for(Path::new(r"d:\projects\app"), true).is_some(), folded);
+        assert_eq!(exclusions.rule_for(Path::new(r"C:\Temp\x\data.keep"), false).is_some(), folded);
+        assert_eq!(exclusions.rule_for(Path::new(r"c:\temp\x\y\A.LOG"), false).is_some(), folded);
+        // 大小写相同时所有平台都匹配
+        assert_eq!(exclusions.rule_for(Path::new(r"C:\Temp\x\y\a.log"), false), Some(2));
+    }
+
+    #[test]
+    fn builtin_rules_protect_vcs_directories_and_project_roots() {
+        let exclusions = Exclusions::new(&["*.keep".to_string()], true);
+        assert_eq!(exclusions.rule_for(Path::new("/tmp/app/.git"), true), Some(0));
+        // 名为 .git 的文件（git worktree）不是版本控制目录
+        assert_eq!(exclusions.rule_for(Path::new("/tmp/app/.git"), false), None);
+        assert_eq!(exclusions.rule_for(Path::new("/tmp/x.keep"), false), Some(2));
+
+        let project = |names: &[&str]| {
+            let children: Vec<PathBuf> = names.iter().map(|name| Path::new("/tmp/dir").join(name)).collect();
+            exclusions.project_rule(&children)
+        };
+        assert_eq!(project(&["src", "Cargo.toml"]), Some(1));
+        assert_eq!(project(&["App.sln", "bin"]), Some(1));
+        assert_eq!(project(&[".hg"]), Some(1));
+        assert_eq!(project(&["notes.txt", "Cargo.toml.bak"]), None);
+        assert_eq!(project(&[]), None);
+
+        let unprotected = Exclusions::new(&[], false);
+        assert_eq!(unprotected.rule_for(Path::new("/tmp/app/.git"), true), None);
+        assert_eq!(unprotected.project_rule(&[PathBuf::from("/tmp/dir/Cargo.toml")]), None);
+    }
+}
@@ -0,0 +1,576 @@
+// Persistent operation history: one JSON line per clean / icon fix / install run
+//
+// 记录保存在 <数据目录>/history/history-YYYY-MM.jsonl，每月一个文件，只保留最近 MAX_HISTORY_FILES 个
+use crate::config;
+use crate::theme::{self, theme};
+use crate::utils::is_admin;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, MouseEventKind};
+use ratatui::{
+    backend::Backend,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Bar, BarChart, BarGroup, Block, Borders, Cell, Paragraph, Row, Table},
+    Frame, Terminal,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+const MAX_HISTORY_FILES: usize = 12;
+
+// 单个清理目标的结果
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TargetResult {
+    pub name: String,
+    pub deleted: usize,
+    pub skipped: usize,
+    pub freed_bytes: u64,
+}
+
+impl TargetResult {
+    pub fn new(name: &str, deleted: usize, skipped: usize, freed_bytes: u64) -> Self {
+        Self {
+            name: name.to_string(),
+            deleted,
+            skipped,
+            freed_bytes,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    // RFC 3339 本地时间
+    pub timestamp: String,
+    pub operation: String,
+    pub user: Option<String>,
+    pub elevated: bool,
+    pub options: BTreeMap<String, String>,
+    pub targets: Vec<TargetResult>,
+    pub duration_ms: u64,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+impl HistoryEntry {
+    pub fn freed_bytes(&self) -> u64 {
+        self.targets.iter().map(|t| t.freed_bytes).sum()
+    }
+
+    pub fn deleted(&self) -> usize {
+        self.targets.iter().map(|t| t.deleted).sum()
+    }
+
+    fn time(&self) -> Option<DateTime<Local>> {
+        DateTime::parse_from_rfc3339(&self.timestamp)
+            .ok()
+            .map(|t| t.with_timezone(&Local))
+    }
+
+    fn time_display(&self) -> String {
+        match self.time() {
+            Some(time) => time.format("%Y-%m-%d %H:%M").to_string(),
+            None => self.timestamp.clone(),
+        }
+    }
+
+    fn options_display(&self) -> String {
+        if self.options.is_empty() {
+            return "-".to_string();
+        }
+        self.options
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+pub fn history_dir() -> Option<PathBuf> {
+    config::data_dir().map(|dir| dir.join("history"))
+}
+
+fn current_user() -> Option<String> {
+    env::var("USERNAME")
+        .or_else(|_| env::var("USER"))
+        .ok()
+        .filter(|u| !u.is_empty())
+}
+
+// 执行一次操作并写入历史记录；写入失败不影响操作本身的结果
+// func 每完成一个目标就加入 targets，失败或取消时已完成的目标同样被记录
+pub fn track<F>(operation: &str, options: &[(&str, String)], func: F) -> Result<()>
+where
+    F: FnOnce(&mut Vec<TargetResult>) -> Result<()>,
+{
+    let (entry, result) = run_tracked(operation, options, func);
+    let _ = append(&entry);
+    result
+}
+
+fn run_tracked<F>(operation: &str, options: &[(&str, String)], func: F) -> (HistoryEntry, Result<()>)
+where
+    F: FnOnce(&mut Vec<TargetResult>) -> Result<()>,
+{
+    let timestamp = Local::now().to_rfc3339();
+    let start = Instant::now();
+    let mut targets = Vec::new();
+    let result = func(&mut targets);
+
+    let error = result.as_ref().err().map(|e| format!("{:#}", e));
+    let entry = HistoryEntry {
+        timestamp,
+        operation: operation.to_string(),
+        user: current_user(),
+        elevated: is_admin(),
+        options: options.iter().map(|(k, v)| (k.to_string(), v.clone())).collect(),
+        targets,
+        duration_ms: start.elapsed().as_millis() as u64,
+        success: error.is_none(),
+        error,
+    };
+    (entry, result)
+}
+
+pub fn append(entry: &HistoryEntry) -> Result<()> {
+    let dir = history_dir().ok_or_else(|| anyhow::anyhow!("Cannot determine data directory"))?;
+    append_to(&dir, entry)
+}
+
+fn append_to(dir: &Path, entry: &HistoryEntry) -> Result<()> {
+    fs::create_dir_all(dir).with_context(|| format!("Cannot create {}", dir.display()))?;
+
+    let path = dir.join(format!("history-{}.jsonl", Local::now().format("%Y-%m")));
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Cannot open {}", path.display()))?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)?;
+
+    rotate(dir);
+    Ok(())
+}
+
+// 删除超出保留数量的最旧月份文件
+fn rotate(dir: &Path) {
+    let mut files = history_files(dir);
+    while files.len() > MAX_HISTORY_FILES {
+        let _ = fs::remove_file(files.remove(0));
+    }
+}
+
+// 按文件名（即月份）升序排列
+fn history_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| {
+                    p.file_name()
+                        .and_then(|n| n.to_str())
+                        .is_some_and(|n| n.starts_with("history-") && n.ends_with(".jsonl"))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+// 读取全部记录（按时间升序），损坏的行直接跳过
+pub fn load() -> Vec<HistoryEntry> {
+    match history_dir() {
+        Some(dir) => load_from(&dir),
+        None => Vec::new(),
+    }
+}
+
+fn load_from(dir: &Path) -> Vec<HistoryEntry> {
+    history_files(dir)
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .flat_map(|content| {
+            content
+                .lines()
+                .filter_map(|line| serde_json::from_str::<HistoryEntry>(line).ok())
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+// 按月份汇总释放的空间
+pub fn freed_by_month(entries: &[HistoryEntry]) -> BTreeMap<String, u64> {
+    let mut totals = BTreeMap::new();
+    for entry in entries {
+        if let Some(time) = entry.time() {
+            *totals.entry(time.format("%Y-%m").to_string()).or_insert(0) += entry.freed_bytes();
+        }
+    }
+    totals
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    let mb = bytes as f64 / 1024.0 / 1024.0;
+    if mb >= 1024.0 {
+        format!("{:.2} GB", mb / 1024.0)
+    } else {
+        format!("{:.2} MB", mb)
+    }
+}
+
+// lemo history [--json]
+pub fn print_history(json: bool, limit: usize) -> Result<()> {
+    let entries = load();
+    let recent = &entries[entries.len().saturating_sub(limit)..];
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(recent)?);
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        println!("No operations recorded yet.");
+        return Ok(());
+    }
+
+    println!(
+        "{:<17} {:<15} {:<12} {:<6} {:>9} {:>12}  Result",
+        "Time", "Operation", "User", "Admin", "Duration", "Freed"
+    );
+    for entry in recent.iter().rev() {
+        println!(
+            "{:<17} {:<15} {:<12} {:<6} {:>8.1}s {:>12}  {}",
+            entry.time_display(),
+            entry.operation,
+            entry.user.as_deref().unwrap_or("-"),
+            if entry.elevated { "yes" } else { "no" },
+            entry.duration_ms as f64 / 1000.0,
+            format_bytes(entry.freed_bytes()),
+            match &entry.error {
+                Some(error) => format!("❌ {}", error),
+                None => "✅".to_string(),
+            }
+        );
+    }
+
+    println!();
+    println!("📊 Freed space by month:");
+    for (month, bytes) in freed_by_month(&entries) {
+        println!("   {}  {:>12}", month, format_bytes(bytes));
+    }
+    println!(
+        "   Total    {:>12}",
+        format_bytes(entries.iter().map(HistoryEntry::freed_bytes).sum())
+    );
+    Ok(())
+}
+
+// History 界面：上方为运行记录，下方为选中记录的明细和按月汇总
+pub fn show_history<B: Backend>(terminal: &mut Terminal<B>) -> Result<()> {
+    let mut entries = load();
+    // 最新的在最上面
+    entries.reverse();
+    let mut selected: usize = 0;
+
+    loop {
+        terminal.draw(|f| {
+            render_history(f, &entries, selected);
+            theme::finish_frame(f);
+        })?;
+
+        if !event::poll(Duration::from_millis(200))? {
+            continue;
+        }
+        let last = entries.len().saturating_sub(1);
+        match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => break,
+                KeyCode::Up | KeyCode::Char('k') => selected = selected.saturating_sub(1),
+                KeyCode::Down | KeyCode::Char('j') => selected = (selected + 1).min(last),
+                KeyCode::PageUp => selected = selected.saturating_sub(10),
+                KeyCode::PageDown => selected = (selected + 10).min(last),
+                KeyCode::Home => selected = 0,
+                KeyCode::End => selected = last,
+                _ => {}
+            },
+            Event::Mouse(mouse) => match mouse.kind {
+                MouseEventKind::ScrollUp => selected = selected.saturating_sub(1),
+                MouseEventKind::ScrollDown => selected = (selected + 1).min(last),
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+
+    terminal.clear()?;
+    Ok(())
+}
+
+fn render_history(f: &mut Frame, entries: &[HistoryEntry], selected: usize) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(8),
+            Constraint::Length(12),
+            Constraint::Length(3),
+        ])
+        .split(f.area());
+
+    let total: u64 = entries.iter().map(HistoryEntry::freed_bytes).sum();
+    let title = Paragraph::new(format!(
+        "📋 Operation History ({} runs, {} freed in total)",
+        entries.len(),
+        format_bytes(total)
+    ))
+    .style(Style::default().fg(theme().accent).add_modifier(Modifier::BOLD))
+    .alignment(Alignment::Center)
+    .block(Block::default().borders(Borders::ALL));
+    f.render_widget(title, chunks[0]);
+
+    render_runs(f, chunks[1], entries, selected);
+
+    let bottom = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(chunks[2]);
+    render_details(f, bottom[0], entries.get(selected));
+    render_monthly(f, bottom[1], entries);
+
+    let footer = Paragraph::new("↑/↓: Select | PgUp/PgDn: Page | Q/Esc: Return")
+        .style(Style::default().fg(theme().muted))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(footer, chunks[3]);
+}
+
+fn render_runs(f: &mut Frame, area: Rect, entries: &[HistoryEntry], selected: usize) {
+    let block = Block::default()
+        .title(" Runs ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme().accent));
+
+    if entries.is_empty() {
+        let empty = Paragraph::new("No operations recorded yet.")
+            .style(Style::default().fg(theme().muted))
+            .alignment(Alignment::Center)
+            .block(block);
+        f.render_widget(empty, area);
+        return;
+    }
+
+    // 保持选中行可见
+    let visible = area.height.saturating_sub(3) as usize;
+    let offset = selected.saturating_sub(visible.saturating_sub(1));
+
+    let header = Row::new(vec!["Time", "Operation", "User", "Admin", "Duration", "Freed", "Result"])
+        .style(Style::default().fg(theme().title).add_modifier(Modifier::BOLD));
+    let rows: Vec<Row> = entries
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(visible)
+        .map(|(index, entry)| {
+            let result = match &entry.error {
+                Some(_) => Span::styled("❌ Failed", Style::default().fg(theme().critical)),
+                None => Span::styled("✅ OK", Style::default().fg(theme().ok)),
+            };
+            let style = if index == selected {
+                Style::default()
+                    .fg(theme().selected_fg)
+                    .bg(theme().selected_bg)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            Row::new(vec![
+                Cell::from(entry.time_display()),
+                Cell::from(entry.operation.clone()),
+                Cell::from(entry.user.clone().unwrap_or_else(|| "-".to_string())),
+                Cell::from(if entry.elevated { "yes" } else { "no" }),
+                Cell::from(format!("{:.1}s", entry.duration_ms as f64 / 1000.0)),
+                Cell::from(format_bytes(entry.freed_bytes())),
+                Cell::from(result),
+            ])
+            .style(style)
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(17),
+            Constraint::Length(16),
+            Constraint::Length(12),
+            Constraint::Length(6),
+            Constraint::Length(9),
+            Constraint::Length(12),
+            Constraint::Min(8),
+        ],
+    )
+    .header(header)
+    .block(block);
+    f.render_widget(table, area);
+}
+
+fn render_details(f: &mut Frame, area: Rect, entry: Option<&HistoryEntry>) {
+    let mut lines = Vec::new();
+    if let Some(entry) = entry {
+        lines.push(Line::from(format!("Options: {}", entry.options_display())));
+        if let Some(error) = &entry.error {
+            lines.push(Line::from(Span::styled(
+                format!("Error: {}", error),
+                Style::default().fg(theme().critical),
+            )));
+        }
+        for target in &entry.targets {
+            lines.push(Line::from(format!(
+                "{:<24} {:>6} deleted {:>5} skipped {:>11}",
+                target.name,
+                target.deleted,
+                target.skipped,
+                format_bytes(target.freed_bytes)
+            )));
+        }
+        if entry.targets.len() > 1 {
+            lines.push(Line::from(Span::styled(
+                format!("{} items, {} freed", entry.deleted(), format_bytes(entry.freed_bytes())),
+                Style::default().add_modifier(Modifier::BOLD),
+            )));
+        }
+    }
+
+    let details = Paragraph::new(lines).block(
+        Block::default()
+            .title(" Details ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme().accent)),
+    );
+    f.render_widget(details, area);
+}
+
+fn render_monthly(f: &mut Frame, area: Rect, entries: &[HistoryEntry]) {
+    let totals = freed_by_month(entries);
+    // 只显示能放下的最近几个月，每个柱子 8 列宽
+    let fit = (area.width.saturating_sub(2) / 9).max(1) as usize;
+    let bars: Vec<Bar> = totals
+        .iter()
+        .skip(totals.len().saturating_sub(fit))
+        .map(|(month, bytes)| {
+            Bar::default()
+                .label(Line::from(month[2..].to_string()))
+                .value(bytes / 1024 / 1024)
+                .text_value(format_bytes(*bytes).replace(' ', ""))
+                .style(Style::default().fg(theme().primary))
+        })
+        .collect();
+
+    let chart = BarChart::default()
+        .block(
+            Block::default()
+                .title(" Freed per month (MB) ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().accent)),
+        )
+        .data(BarGroup::default().bars(&bars))
+        .bar_width(8)
+        .bar_gap(1);
+    f.render_widget(chart, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task::Cancelled;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("lemo-history-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn entry(operation: &str, freed: u64) -> HistoryEntry {
+        HistoryEntry {
+            timestamp: "2026-01-15T09:30:00+00:00".to_string(),
+            operation: operation.to_string(),
+            user: None,
+            elevated: false,
+            options: BTreeMap::new(),
+            targets: vec![TargetResult::new("Windows Temp", 3, 1, freed)],
+            duration_ms: 10,
+            success: true,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn append_and_load_round_trip() {
+        let dir = temp_dir("round-trip");
+        append_to(&dir, &entry("clean-temp", 100)).unwrap();
+        append_to(&dir, &entry("empty-trash", 50)).unwrap();
+        // 损坏的行被跳过
+        let path = history_files(&dir).pop().unwrap();
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        writeln!(file, "{{\"timestamp\":").unwrap();
+
+        let entries = load_from(&dir);
+        let operations: Vec<&str> = entries.iter().map(|e| e.operation.as_str()).collect();
+        assert_eq!(operations, ["clean-temp", "empty-trash"]);
+        assert_eq!(entries[0].freed_bytes(), 100);
+        assert_eq!(entries[1].deleted(), 3);
+        assert_eq!(freed_by_month(&entries).get("2026-01"), Some(&150));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn keeps_only_the_newest_monthly_files() {
+        let dir = temp_dir("rotate");
+        fs::create_dir_all(&dir).unwrap();
+        // 14 个早于当前月份的文件，加上当前月份共 15 个
+        for month in 1..=12 {
+            fs::write(dir.join(format!("history-2000-{:02}.jsonl", month)), "").unwrap();
+        }
+        fs::write(dir.join("history-2001-01.jsonl"), "").unwrap();
+        fs::write(dir.join("history-2001-02.jsonl"), "").unwrap();
+        fs::write(dir.join("notes.txt"), "").unwrap();
+
+        append_to(&dir, &entry("clean-temp", 1)).unwrap();
+        let names: Vec<String> = history_files(&dir)
+            .iter()
+            .map(|path| path.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(names.len(), MAX_HISTORY_FILES);
+        assert_eq!(names[0], "history-2000-04.jsonl");
+        assert_eq!(names[MAX_HISTORY_FILES - 1], format!("history-{}.jsonl", Local::now().format("%Y-%m")));
+        // 其他文件不受影响
+        assert!(dir.join("notes.txt").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn failed_runs_keep_completed_targets() {
+        let (entry, result) = run_tracked("clean-temp", &[("older_than", "7d".to_string())], |targets| {
+            targets.push(TargetResult::new("Windows Temp", 2, 0, 4096));
+            Err(Cancelled.into())
+        });
+        assert!(result.is_err());
+        assert!(!entry.success);
+        assert_eq!(entry.error.as_deref(), Some("Cancelled by user"));
+        assert_eq!(entry.targets.len(), 1);
+        assert_eq!(entry.freed_bytes(), 4096);
+        assert_eq!(entry.options.get("older_than").map(String::as_str), Some("7d"));
+
+        let (entry, result) = run_tracked("install", &[], |_| Ok(()));
+        assert!(result.is_ok());
+        assert!(entry.success && entry.error.is_none() && entry.targets.is_empty());
+    }
+}
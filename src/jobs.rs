@@ -0,0 +1,707 @@
+// Background job queue: tasks run one after another while the TUI stays usable
+//
+// 调度器在后台线程中按顺序执行排队的任务（复用 task::TaskHandle），
+// 每个状态变化和输出行都发布到事件总线；任务表（jobs 界面）和主菜单的提示都订阅该总线。
+use crate::task::{Progress, Task, TaskEvent, TaskHandle, TaskStatus};
+use crate::theme::{self, theme};
+use anyhow::Result;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, MouseEventKind};
+use ratatui::{
+    backend::Backend,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Gauge, List, ListItem, Paragraph, Wrap},
+    Frame, Terminal,
+};
+use std::collections::VecDeque;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+static SCHEDULER: OnceLock<Scheduler> = OnceLock::new();
+
+pub type JobId = u64;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JobState {
+    Queued,
+    Running,
+    Finished(TaskStatus),
+}
+
+impl JobState {
+    fn label(&self) -> (&'static str, ratatui::style::Color) {
+        match self {
+            JobState::Queued => ("⏳ Queued", theme().muted),
+            JobState::Running => ("▶ Running", theme().title),
+            JobState::Finished(TaskStatus::Succeeded) => ("✅ Done", theme().ok),
+            JobState::Finished(TaskStatus::Failed(_)) => ("❌ Failed", theme().critical),
+            JobState::Finished(TaskStatus::Cancelled) => ("⚠️ Cancelled", theme().warn),
+            JobState::Finished(TaskStatus::Running) => ("▶ Running", theme().title),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub enum JobEvent {
+    Queued { id: JobId },
+    Started { id: JobId },
+    Line { id: JobId, line: String },
+    Progress { id: JobId, progress: Progress },
+    Finished { id: JobId, title: String, status: TaskStatus },
+}
+
+// 事件总线：每个订阅者拥有独立的接收端，接收端被丢弃后自动退订
+#[derive(Default)]
+pub struct EventBus {
+    subscribers: Mutex<Vec<Sender<JobEvent>>>,
+}
+
+impl EventBus {
+    pub fn subscribe(&self) -> Receiver<JobEvent> {
+        let (tx, rx) = mpsc::channel();
+        lock(&self.subscribers).push(tx);
+        rx
+    }
+
+    pub fn publish(&self, event: JobEvent) {
+        lock(&self.subscribers).retain(|tx| tx.send(event.clone()).is_ok());
+    }
+}
+
+// 任务表中的一行
+#[derive(Clone)]
+pub struct Job {
+    pub id: JobId,
+    pub title: String,
+    pub state: JobState,
+    pub lines: Vec<String>,
+    pub progress: Option<Progress>,
+    pub queued_at: Instant,
+    pub started: Option<Instant>,
+    pub elapsed: Option<Duration>,
+}
+
+impl Job {
+    fn apply(&mut self, event: &JobEvent) {
+        match event {
+            JobEvent::Queued { .. } => self.state = JobState::Queued,
+            JobEvent::Started { .. } => {
+                self.state = JobState::Running;
+                self.started = Some(Instant::now());
+            }
+            JobEvent::Line { line, .. } => self.lines.push(line.clone()),
+            JobEvent::Progress { progress, .. } => self.progress = Some(progress.clone()),
+            JobEvent::Finished { status, .. } => {
+                self.elapsed = self.started.map(|s| s.elapsed());
+                if let TaskStatus::Failed(error) = status {
+                    self.lines.push(format!("❌ Error: {}", error));
+                }
+                self.state = JobState::Finished(status.clone());
+            }
+        }
+    }
+
+    fn elapsed(&self) -> Option<Duration> {
+        self.elapsed.or_else(|| self.started.map(|s| s.elapsed()))
+    }
+}
+
+#[derive(Default)]
+struct Queue {
+    next_id: JobId,
+    jobs: Vec<Job>,
+    pending: VecDeque<(JobId, Task)>,
+    // 用户请求取消的运行中任务
+    cancel: Vec<JobId>,
+    worker_started: bool,
+}
+
+pub struct Scheduler {
+    queue: Arc<(Mutex<Queue>, Condvar)>,
+    bus: Arc<EventBus>,
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+// 全局调度器（首次提交任务时启动工作线程）
+pub fn scheduler() -> &'static Scheduler {
+    SCHEDULER.get_or_init(|| Scheduler {
+        queue: Arc::new((Mutex::new(Queue::default()), Condvar::new())),
+        bus: Arc::new(EventBus::default()),
+    })
+}
+
+impl Scheduler {
+    pub fn bus(&self) -> &EventBus {
+        &self.bus
+    }
+
+    pub fn enqueue(&self, task: Task) -> JobId {
+        let (mutex, wake) = &*self.queue;
+        let mut queue = lock(mutex);
+        queue.next_id += 1;
+        let id = queue.next_id;
+        queue.jobs.push(Job {
+            id,
+            title: task.title.clone(),
+            state: JobState::Queued,
+            lines: Vec::new(),
+            progress: None,
+            queued_at: Instant::now(),
+            started: None,
+            elapsed: None,
+        });
+        queue.pending.push_back((id, task));
+        if !queue.worker_started {
+            queue.worker_started = true;
+            let shared = Arc::clone(&self.queue);
+            let bus = Arc::clone(&self.bus);
+            thread::spawn(move || worker(shared, bus));
+        }
+        // 持锁发布，保证 Queued 一定先于工作线程发布的 Started
+        self.bus.publish(JobEvent::Queued { id });
+        drop(queue);
+
+        wake.notify_one();
+        id
+    }
+
+    // 排队中的任务直接移除，运行中的任务在下一个检查点停止
+    pub fn cancel(&self, id: JobId) {
+        let (mutex, _) = &*self.queue;
+        let mut queue = lock(mutex);
+        let Some((state, title)) = queue
+            .jobs
+            .iter()
+            .find(|j| j.id == id)
+            .map(|j| (j.state.clone(), j.title.clone()))
+        else {
+            return;
+        };
+        match state {
+            JobState::Queued => {
+                queue.pending.retain(|(pending, _)| *pending != id);
+                let event = JobEvent::Finished {
+                    id,
+                    title,
+                    status: TaskStatus::Cancelled,
+                };
+                apply(&mut queue, &event);
+                drop(queue);
+                self.bus.publish(event);
+            }
+            JobState::Running => queue.cancel.push(id),
+            JobState::Finished(_) => {}
+        }
+    }
+
+    // 清除已结束的任务
+    pub fn clear_finished(&self) {
+        let (mutex, _) = &*self.queue;
+        lock(mutex).jobs.retain(|j| !matches!(j.state, JobState::Finished(_)));
+    }
+
+    pub fn jobs(&self) -> Vec<Job> {
+        let (mutex, _) = &*self.queue;
+        lock(mutex).jobs.clone()
+    }
+
+    // 有任务未结束时返回简短摘要，例如 "Jobs: 1 running, 2 queued"
+    pub fn summary(&self) -> Option<String> {
+        let (mutex, _) = &*self.queue;
+        let queue = lock(mutex);
+        let running = queue.jobs.iter().filter(|j| j.state == JobState::Running).count();
+        let queued = queue.jobs.iter().filter(|j| j.state == JobState::Queued).count();
+        if running + queued == 0 {
+            return None;
+        }
+        Some(format!("Jobs: {} running, {} queued", running, queued))
+    }
+}
+
+fn apply(queue: &mut Queue, event: &JobEvent) {
+    let id = match event {
+        JobEvent::Queued { id }
+        | JobEvent::Started { id }
+        | JobEvent::Line { id, .. }
+        | JobEvent::Progress { id, .. }
+        | JobEvent::Finished { id, .. } => *id,
+    };
+    if let Some(job) = queue.jobs.iter_mut().find(|j| j.id == id) {
+        job.apply(event);
+    }
+}
+
+// 工作线程：一次执行一个任务，把任务事件转发到任务表和事件总线
+fn worker(shared: Arc<(Mutex<Queue>, Condvar)>, bus: Arc<EventBus>) {
+    let (mutex, wake) = &*shared;
+    let publish = |event: JobEvent| {
+        apply(&mut lock(mutex), &event);
+        bus.publish(event);
+    };
+
+    loop {
+        let (id, task) = {
+            let mut queue = lock(mutex);
+            loop {
+                if let Some(next) = queue.pending.pop_front() {
+                    break next;
+                }
+                queue = wake.wait(queue).unwrap_or_else(|e| e.into_inner());
+            }
+        };
+
+        let title = task.title.clone();
+        publish(JobEvent::Started { id });
+        let mut handle = TaskHandle::spawn(task);
+        loop {
+            for event in handle.update() {
+                publish(match event {
+                    TaskEvent::Line(line) => JobEvent::Line { id, line },
+                    TaskEvent::Progress(progress) => JobEvent::Progress { id, progress },
+                });
+            }
+            if handle.is_finished() {
+                break;
+            }
+            {
+                let mut queue = lock(mutex);
+                if let Some(position) = queue.cancel.iter().position(|c| *c == id) {
+                    queue.cancel.remove(position);
+                    handle.cancel();
+                }
+            }
+            thread::sleep(Duration::from_millis(50));
+        }
+        publish(JobEvent::Finished {
+            id,
+            title,
+            status: handle.status.clone(),
+        });
+    }
+}
+
+// 主菜单使用：把任务结束事件转换为一行提示
+pub fn finished_message(event: &JobEvent) -> Option<String> {
+    match event {
+        JobEvent::Finished { title, status, .. } => Some(match status {
+            TaskStatus::Succeeded => format!("✅ Job finished: {}", title),
+            TaskStatus::Failed(error) => format!("❌ Job failed: {} ({})", title, error),
+            TaskStatus::Cancelled => format!("⚠️ Job cancelled: {}", title),
+            TaskStatus::Running => return None,
+        }),
+        _ => None,
+    }
+}
+
+// 可加入队列的操作
+pub struct QueueItem {
+    pub title: &'static str,
+    pub description: &'static str,
+    pub task: fn() -> Task,
+}
+
+// 勾选要执行的操作，回车后按顺序加入队列；返回是否提交了任务
+pub fn show_checklist<B: Backend>(terminal: &mut Terminal<B>, items: &[QueueItem]) -> Result<bool> {
+    let mut checked = vec![false; items.len()];
+    let mut selected = 0;
+    let mut message: Option<String> = None;
+
+    loop {
+        terminal.draw(|f| {
+            render_checklist(f, items, &checked, selected, message.as_deref());
+            theme::finish_frame(f);
+        })?;
+
+        let key = match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => key,
+            _ => continue,
+        };
+        message = None;
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(false),
+            KeyCode::Up | KeyCode::Char('k') => selected = selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => selected = (selected + 1).min(items.len().saturating_sub(1)),
+            KeyCode::Char(' ') => checked[selected] = !checked[selected],
+            KeyCode::Char('a') => {
+                let all = checked.iter().all(|c| *c);
+                checked.iter_mut().for_each(|c| *c = !all);
+            }
+            KeyCode::Enter => {
+                if !checked.iter().any(|c| *c) {
+                    message = Some("Select at least one action with Space".to_string());
+                    continue;
+                }
+                for (item, _) in items.iter().zip(&checked).filter(|(_, c)| **c) {
+                    scheduler().enqueue((item.task)());
+                }
+                return Ok(true);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn render_checklist(f: &mut Frame, items: &[QueueItem], checked: &[bool], selected: usize, message: Option<&str>) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0), Constraint::Length(3)])
+        .split(f.area());
+
+    let title = Paragraph::new("🧹 Queue Actions")
+        .style(Style::default().fg(theme().accent).add_modifier(Modifier::BOLD))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(title, chunks[0]);
+
+    let list_items: Vec<ListItem> = items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| {
+            let mark = if checked[index] { "[x]" } else { "[ ]" };
+            let style = if index == selected {
+                Style::default()
+                    .fg(theme().selected_fg)
+                    .bg(theme().selected_bg)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            ListItem::new(vec![
+                Line::from(Span::styled(format!(" {} {}", mark, item.title), style)),
+                Line::from(Span::styled(
+                    format!("       {}", item.description),
+                    Style::default().fg(theme().muted),
+                )),
+            ])
+        })
+        .collect();
+    let list = List::new(list_items).block(
+        Block::default()
+            .title(" Actions ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme().accent)),
+    );
+    f.render_widget(list, chunks[1]);
+
+    let footer = match message {
+        Some(message) => Paragraph::new(message).style(Style::default().fg(theme().warn)),
+        None => Paragraph::new("↑/↓: Select | Space: Toggle | A: All | Enter: Queue | Q/Esc: Return")
+            .style(Style::default().fg(theme().muted)),
+    }
+    .alignment(Alignment::Center)
+    .block(Block::default().borders(Borders::ALL));
+    f.render_widget(footer, chunks[2]);
+}
+
+// Jobs 界面：左侧任务列表，右侧选中任务的输出；任务在后台继续执行
+pub fn show_jobs<B: Backend>(terminal: &mut Terminal<B>) -> Result<()> {
+    let mut selected = 0;
+
+    loop {
+        let jobs = scheduler().jobs();
+        selected = selected.min(jobs.len().saturating_sub(1));
+        terminal.draw(|f| {
+            render_jobs(f, &jobs, selected);
+            theme::finish_frame(f);
+        })?;
+
+        if !event::poll(Duration::from_millis(200))? {
+            continue;
+        }
+        let last = jobs.len().saturating_sub(1);
+        match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => break,
+                KeyCode::Up | KeyCode::Char('k') => selected = selected.saturating_sub(1),
+                KeyCode::Down | KeyCode::Char('j') => selected = (selected + 1).min(last),
+                KeyCode::Char('c') => {
+                    if let Some(job) = jobs.get(selected) {
+                        scheduler().cancel(job.id);
+                    }
+                }
+                KeyCode::Char('x') => scheduler().clear_finished(),
+                _ => {}
+            },
+            Event::Mouse(mouse) => match mouse.kind {
+                MouseEventKind::ScrollUp => selected = selected.saturating_sub(1),
+                MouseEventKind::ScrollDown => selected = (selected + 1).min(last),
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+
+    terminal.clear()?;
+    Ok(())
+}
+
+fn render_jobs(f: &mut Frame, jobs: &[Job], selected: usize) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0), Constraint::Length(3)])
+        .split(f.area());
+
+    let title = Paragraph::new(format!(
+        "📋 Jobs ({})",
+        scheduler().summary().unwrap_or_else(|| "idle".to_string())
+    ))
+    .style(Style::default().fg(theme().accent).add_modifier(Modifier::BOLD))
+    .alignment(Alignment::Center)
+    .block(Block::default().borders(Borders::ALL));
+    f.render_widget(title, chunks[0]);
+
+    let body = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(35), Constraint::Percentage(65)])
+        .split(chunks[1]);
+
+    let items: Vec<ListItem> = jobs
+        .iter()
+        .enumerate()
+        .map(|(index, job)| {
+            let (state, color) = job.state.label();
+            let elapsed = job
+                .elapsed()
+                .map(|e| format!(" {:.1}s", e.as_secs_f64()))
+                .unwrap_or_else(|| format!(" waiting {:.0}s", job.queued_at.elapsed().as_secs_f64()));
+            let style = if index == selected {
+                Style::default().bg(theme().selected_bg).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            ListItem::new(vec![
+                Line::from(format!("#{} {}", job.id, job.title)),
+                Line::from(vec![
+                    Span::styled(format!("   {}", state), Style::default().fg(color)),
+                    Span::styled(elapsed, Style::default().fg(theme().muted)),
+                ]),
+            ])
+            .style(style)
+        })
+        .collect();
+    let list = List::new(items).block(
+        Block::default()
+            .title(" Queue ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme().accent)),
+    );
+    f.render_widget(list, body[0]);
+
+    render_job_output(f, body[1], jobs.get(selected));
+
+    let footer = Paragraph::new("↑/↓: Select | C: Cancel job | X: Clear finished | Q/Esc: Return (jobs keep running)")
+        .style(Style::default().fg(theme().muted))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(footer, chunks[2]);
+}
+
+fn render_job_output(f: &mut Frame, area: Rect, job: Option<&Job>) {
+    let Some(job) = job else {
+        let empty = Paragraph::new("No jobs. Use \"Queue Actions\" in the main menu to add some.")
+            .style(Style::default().fg(theme().muted))
+            .alignment(Alignment::Center)
+            .block(Block::default().title(" Output ").borders(Borders::ALL));
+        f.render_widget(empty, area);
+        return;
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(3)])
+        .split(area);
+
+    // 自动滚动到最新内容
+    let visible_height = chunks[0].height.saturating_sub(2) as usize;
+    let lines: Vec<Line> = job
+        .lines
+        .iter()
+        .skip(job.lines.len().saturating_sub(visible_height))
+        .map(|s| Line::from(s.clone()))
+        .collect();
+    let output = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(format!(" #{} {} ", job.id, job.title))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().title)),
+        )
+        .wrap(Wrap { trim: false });
+    f.render_widget(output, chunks[0]);
+
+    let (ratio, label) = match (&job.state, &job.progress) {
+        (JobState::Finished(_), _) => (1.0, job.state.label().0.to_string()),
+        (_, Some(progress)) => (
+            progress.ratio(),
+            format!("{}/{} {}", (progress.done + 1).min(progress.total), progress.total, progress.label),
+        ),
+        (_, None) => (0.0, job.state.label().0.to_string()),
+    };
+    let gauge = Gauge::default()
+        .block(Block::default().borders(Borders::ALL))
+        .gauge_style(Style::default().fg(theme().primary))
+        .ratio(ratio)
+        .label(label);
+    f.render_widget(gauge, chunks[1]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task::Cancelled;
+
+    fn new_scheduler() -> Scheduler {
+        Scheduler {
+            queue: Arc::new((Mutex::new(Queue::default()), Condvar::new())),
+            bus: Arc::new(EventBus::default()),
+        }
+    }
+
+    fn describe(event: &JobEvent) -> String {
+        match event {
+            JobEvent::Queued { id } => format!("queued {}", id),
+            JobEvent::Started { id } => format!("started {}", id),
+            JobEvent::Line { id, line } => format!("line {} {}", id, line),
+            JobEvent::Progress { id, progress } => {
+                format!("progress {} {}/{} {}", id, progress.done, progress.total, progress.label)
+            }
+            JobEvent::Finished { id, status, .. } => format!("finished {} {:?}", id, status),
+        }
+    }
+
+    // 收集事件直到指定任务结束（最多 5 秒）
+    fn collect_until_finished(events: &Receiver<JobEvent>, id: JobId) -> Vec<String> {
+        let mut seen = Vec::new();
+        loop {
+            let event = events.recv_timeout(Duration::from_secs(5)).expect("job did not finish");
+            seen.push(describe(&event));
+            if matches!(event, JobEvent::Finished { id: finished, .. } if finished == id) {
+                return seen;
+            }
+        }
+    }
+
+    fn state(scheduler: &Scheduler, id: JobId) -> JobState {
+        scheduler.jobs().into_iter().find(|j| j.id == id).unwrap().state
+    }
+
+    #[test]
+    fn runs_jobs_in_order_and_publishes_events_in_order() {
+        let scheduler = new_scheduler();
+        let events = scheduler.bus().subscribe();
+        let (started_tx, started_rx) = mpsc::channel();
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+
+        let first = scheduler.enqueue(Task::new("First", move |ctx| {
+            ctx.println("hello");
+            ctx.report_progress(1, 2, "half");
+            started_tx.send(()).unwrap();
+            release_rx.recv().unwrap();
+            Ok(())
+        }));
+        let second = scheduler.enqueue(Task::new("Second", |_| anyhow::bail!("boom")));
+
+        started_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(state(&scheduler, first), JobState::Running);
+        assert_eq!(state(&scheduler, second), JobState::Queued);
+        assert_eq!(scheduler.summary().as_deref(), Some("Jobs: 1 running, 1 queued"));
+
+        release_tx.send(()).unwrap();
+        let seen = collect_until_finished(&events, second);
+        assert_eq!(
+            seen,
+            vec![
+                "queued 1",
+                "queued 2",
+                "started 1",
+                "line 1 hello",
+                "progress 1 1/2 half",
+                "finished 1 Succeeded",
+                "started 2",
+                "finished 2 Failed(\"boom\")",
+            ]
+        );
+
+        assert_eq!(state(&scheduler, first), JobState::Finished(TaskStatus::Succeeded));
+        let failed = scheduler.jobs().into_iter().find(|j| j.id == second).unwrap();
+        assert_eq!(failed.state, JobState::Finished(TaskStatus::Failed("boom".into())));
+        assert_eq!(failed.lines.last().map(String::as_str), Some("❌ Error: boom"));
+        assert_eq!(scheduler.summary(), None);
+
+        scheduler.clear_finished();
+        assert!(scheduler.jobs().is_empty());
+    }
+
+    #[test]
+    fn cancels_queued_and_running_jobs() {
+        let scheduler = new_scheduler();
+        let events = scheduler.bus().subscribe();
+        let (started_tx, started_rx) = mpsc::channel();
+
+        let running = scheduler.enqueue(Task::new("Running", move |ctx| {
+            started_tx.send(()).unwrap();
+            while !ctx.is_cancelled() {
+                thread::sleep(Duration::from_millis(1));
+            }
+            Err(Cancelled.into())
+        }));
+        let queued = scheduler.enqueue(Task::new("Queued", |_| Ok(())));
+        started_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+
+        // 排队中的任务立即结束，不会再被执行
+        scheduler.cancel(queued);
+        assert_eq!(state(&scheduler, queued), JobState::Finished(TaskStatus::Cancelled));
+        assert_eq!(state(&scheduler, running), JobState::Running);
+
+        scheduler.cancel(running);
+        let seen = collect_until_finished(&events, running);
+        assert_eq!(
+            seen,
+            vec!["queued 1", "queued 2", "started 1", "finished 2 Cancelled", "finished 1 Cancelled"]
+        );
+        assert_eq!(state(&scheduler, running), JobState::Finished(TaskStatus::Cancelled));
+
+        // 已结束的任务再取消不产生新事件
+        scheduler.cancel(running);
+        thread::sleep(Duration::from_millis(100));
+        assert!(events.try_recv().is_err());
+    }
+
+    #[test]
+    fn dropped_subscribers_are_removed() {
+        let bus = EventBus::default();
+        let kept = bus.subscribe();
+        drop(bus.subscribe());
+        bus.publish(JobEvent::Queued { id: 7 });
+        assert_eq!(lock(&bus.subscribers).len(), 1);
+        assert_eq!(describe(&kept.try_recv().unwrap()), "queued 7");
+    }
+
+    #[test]
+    fn finished_message_describes_the_outcome() {
+        let finished = |status| JobEvent::Finished {
+            id: 1,
+            title: "Clean temp".into(),
+            status,
+        };
+        assert_eq!(
+            finished_message(&finished(TaskStatus::Succeeded)).as_deref(),
+            Some("✅ Job finished: Clean temp")
+        );
+        assert_eq!(
+            finished_message(&finished(TaskStatus::Failed("denied".into()))).as_deref(),
+            Some("❌ Job failed: Clean temp (denied)")
+        );
+        assert_eq!(
+            finished_message(&finished(TaskStatus::Cancelled)).as_deref(),
+            Some("⚠️ Job cancelled: Clean temp")
+        );
+        assert_eq!(finished_message(&JobEvent::Started { id: 1 }), None);
+    }
+}
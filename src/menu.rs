@@ -0,0 +1,647 @@
+// Main menu: action registry, fuzzy command palette, menu rendering and the schedules screen
+use crate::analyze;
+use crate::history;
+use crate::jobs;
+use crate::task;
+use crate::monitor::{show_realtime_monitor, MonitorOptions};
+use crate::profile;
+use crate::schedule;
+use crate::theme::{self, theme};
+use crate::utils::*;
+use anyhow::Result;
+use chrono::Local;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::{
+    backend::Backend,
+    layout::{Alignment, Constraint, Direction, Layout, Position, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Clear, List, ListItem, Paragraph, Row, Table, Wrap},
+    Frame, Terminal,
+};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+// 执行完一个操作后菜单是否继续
+pub enum Flow {
+    Continue,
+    Exit,
+}
+
+pub type Handler<B> = fn(&mut Terminal<B>) -> Result<Flow>;
+
+// 主菜单中的一个操作
+pub struct Action<B: Backend> {
+    pub id: &'static str,
+    pub title: &'static str,
+    pub description: &'static str,
+    pub requires_admin: bool,
+    pub handler: Handler<B>,
+}
+
+// 注册所有菜单操作（顺序即菜单顺序，前 9 项可用数字键直接执行）
+pub fn actions<B>() -> Vec<Action<B>>
+where
+    B: Backend + std::io::Write,
+{
+    vec![
+        Action {
+            id: "fix-icon-cache",
+            title: "🔧 Fix Icon Cache",
+            description: "Delete the Windows icon and thumbnail caches and restart Explorer. \
+                          Fixes blank or wrong icons on the desktop and in Explorer.",
+            requires_admin: true,
+            handler: run_fix_icon_cache::<B>,
+        },
+        Action {
+            id: "clean-temp",
+            title: "🧹 Clean Temp Files",
+            description: "Remove temporary files using a cleaning profile: standard (system temp, \
+                          prefetch, .bak files, recycle bin), safe, aggressive, privacy or your own. \
+                          Files in use are skipped.",
+            requires_admin: true,
+            handler: run_clean_temp::<B>,
+        },
+        Action {
+            id: "monitor",
+            title: "📊 Real-time Monitor",
+            description: "Live dashboard for CPU, memory, disks, network, sensors and battery. \
+                          Press M for memory history, T for sensors, S to save a snapshot.",
+            requires_admin: false,
+            handler: run_monitor::<B>,
+        },
+        Action {
+            id: "queue",
+            title: "🧹 Queue Actions",
+            description: "Tick several cleanup actions and run them one after another in the \
+                          background. Keep using the monitor while they run.",
+            requires_admin: true,
+            handler: run_queue::<B>,
+        },
+        Action {
+            id: "jobs",
+            title: "📋 Jobs",
+            description: "State and output of queued and running background jobs. Cancel a job \
+                          with C.",
+            requires_admin: false,
+            handler: run_jobs::<B>,
+        },
+        Action {
+            id: "schedules",
+            title: "⏳ Schedules",
+            description: "Scheduled cleanups run by `lemo daemon`: when they ran, their result and \
+                          when they run next. Add schedules with `lemo schedule add`.",
+            requires_admin: false,
+            handler: run_schedules::<B>,
+        },
+        Action {
+            id: "history",
+            title: "📋 Operation History",
+            description: "Browse past clean, icon fix and install runs with per-target results \
+                          and the space freed per month.",
+            requires_admin: false,
+            handler: run_history::<B>,
+        },
+        Action {
+            id: "install",
+            title: "📦 Install to System",
+            description: "Copy lemo to %LOCALAPPDATA%\\lemo and add it to the system PATH so it \
+                          can be started from any terminal.",
+            requires_admin: true,
+            handler: run_install::<B>,
+        },
+        Action {
+            id: "uninstall",
+            title: "🗑️ Uninstall from System",
+            description: "Remove lemo from %LOCALAPPDATA%\\lemo and from the system PATH.",
+            requires_admin: true,
+            handler: run_uninstall::<B>,
+        },
+        Action {
+            id: "exit",
+            title: "➡️ Exit",
+            description: "Quit lemo.",
+            requires_admin: false,
+            handler: |_| Ok(Flow::Exit),
+        },
+    ]
+}
+
+fn run_fix_icon_cache<B: Backend>(terminal: &mut Terminal<B>) -> Result<Flow> {
+    task::run_in_tui(terminal, fix_icon_cache_task(true))?;
+    Ok(Flow::Continue)
+}
+
+fn run_clean_temp<B: Backend>(terminal: &mut Terminal<B>) -> Result<Flow> {
+    let Some(profile) = profile::pick_profile(terminal)? else {
+        return Ok(Flow::Continue);
+    };
+    // 先预估各目标可释放的空间，确认后再清理
+    if let Some(options) = analyze::select_targets(terminal, &profile.clean_options(None)?)? {
+        task::run_in_tui(terminal, clean_temp_task(options))?;
+    }
+    Ok(Flow::Continue)
+}
+
+fn run_monitor<B: Backend>(terminal: &mut Terminal<B>) -> Result<Flow> {
+    show_realtime_monitor(terminal, MonitorOptions::default())?;
+    Ok(Flow::Continue)
+}
+
+fn run_queue<B: Backend>(terminal: &mut Terminal<B>) -> Result<Flow> {
+    let items = [
+        jobs::QueueItem {
+            title: "Clean temp files",
+            description: "System temp, prefetch, .bak files",
+            // 回收站是单独的一项，不在这里清空
+            task: || {
+                clean_temp_task(CleanOptions {
+                    targets: ["windows-temp", "prefetch", "windows-bak"].map(String::from).to_vec(),
+                    ..Default::default()
+                })
+            },
+        },
+        jobs::QueueItem {
+            title: "Clean developer caches",
+            description: "npm, pip, Yarn, Go, Cargo and Gradle download caches",
+            task: clean_dev_caches_task,
+        },
+        jobs::QueueItem {
+            title: "Fix icon cache",
+            description: "Delete the icon cache and restart Explorer",
+            task: || fix_icon_cache_task(true),
+        },
+        jobs::QueueItem {
+            title: "Empty recycle bin",
+            description: "Permanently delete everything in the recycle bin",
+            task: || empty_trash_task(CleanOptions::default()),
+        },
+    ];
+    if jobs::show_checklist(terminal, &items)? {
+        jobs::show_jobs(terminal)?;
+    }
+    Ok(Flow::Continue)
+}
+
+fn run_jobs<B: Backend>(terminal: &mut Terminal<B>) -> Result<Flow> {
+    jobs::show_jobs(terminal)?;
+    Ok(Flow::Continue)
+}
+
+fn run_schedules<B: Backend>(terminal: &mut Terminal<B>) -> Result<Flow> {
+    show_schedules(terminal)?;
+    Ok(Flow::Continue)
+}
+
+fn run_history<B: Backend>(terminal: &mut Terminal<B>) -> Result<Flow> {
+    history::show_history(terminal)?;
+    Ok(Flow::Continue)
+}
+
+fn run_install<B: Backend>(terminal: &mut Terminal<B>) -> Result<Flow> {
+    task::run_in_tui(terminal, install_task())?;
+    Ok(Flow::Continue)
+}
+
+fn run_uninstall<B: Backend>(terminal: &mut Terminal<B>) -> Result<Flow> {
+    task::run_in_tui(terminal, uninstall_task())?;
+    Ok(Flow::Continue)
+}
+
+// 模糊匹配：query 的字符按顺序出现在 text 中即匹配（忽略大小写）
+// 返回分数（越高越好）和匹配到的字符位置，用于高亮
+pub fn fuzzy_match(query: &str, text: &str) -> Option<(i32, Vec<usize>)> {
+    let query: Vec<char> = query.chars().filter(|c| !c.is_whitespace()).flat_map(|c| c.to_lowercase()).collect();
+    if query.is_empty() {
+        return Some((0, Vec::new()));
+    }
+
+    let chars: Vec<char> = text.chars().collect();
+    let mut positions = Vec::with_capacity(query.len());
+    let mut score = 0;
+    let mut next = 0;
+    for (index, ch) in chars.iter().enumerate() {
+        if next == query.len() {
+            break;
+        }
+        if ch.to_lowercase().eq(std::iter::once(query[next])) {
+            score += 1;
+            // 连续匹配和单词开头匹配加分
+            if positions.last() == Some(&index.wrapping_sub(1)) {
+                score += 5;
+            }
+            if index == 0 || !chars[index - 1].is_alphanumeric() {
+                score += 3;
+            }
+            positions.push(index);
+            next += 1;
+        }
+    }
+
+    if next < query.len() {
+        return None;
+    }
+    // 匹配越靠前越好
+    score -= positions[0] as i32 / 4;
+    Some((score, positions))
+}
+
+// 命令面板（`:` 或 Ctrl+P 打开）
+#[derive(Default)]
+pub struct Palette {
+    pub query: String,
+    pub selected: usize,
+}
+
+impl Palette {
+    // 按分数排序的匹配结果：(操作索引, 标题中匹配到的字符位置)
+    pub fn matches<B: Backend>(&self, actions: &[Action<B>]) -> Vec<(usize, Vec<usize>)> {
+        let mut matches: Vec<(i32, usize, Vec<usize>)> = actions
+            .iter()
+            .enumerate()
+            .filter_map(|(index, action)| {
+                let by_title = fuzzy_match(&self.query, action.title);
+                let by_id = fuzzy_match(&self.query, action.id).map(|(score, _)| (score, Vec::new()));
+                match (by_title, by_id) {
+                    (Some(t), Some(i)) if i.0 > t.0 => Some((i.0, index, i.1)),
+                    (Some(t), _) => Some((t.0, index, t.1)),
+                    (None, Some(i)) => Some((i.0, index, i.1)),
+                    (None, None) => None,
+                }
+            })
+            .collect();
+        // 分数相同时保持菜单顺序
+        matches.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+        matches.into_iter().map(|(_, index, positions)| (index, positions)).collect()
+    }
+}
+
+fn highlighted(text: &str, positions: &[usize], base: Style) -> Line<'static> {
+    let highlight = base.fg(theme().accent).add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+    Line::from(
+        text.chars()
+            .enumerate()
+            .map(|(i, c)| Span::styled(c.to_string(), if positions.contains(&i) { highlight } else { base }))
+            .collect::<Vec<_>>(),
+    )
+}
+
+pub fn render_menu<B: Backend>(
+    f: &mut Frame,
+    actions: &[Action<B>],
+    selected: usize,
+    palette: Option<&Palette>,
+    message: Option<&str>,
+) -> Rect {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0), Constraint::Length(3)])
+        .split(f.area());
+
+    // 自定义：修改应用标题、图标和颜色
+    let header = Paragraph::new("🍋 Lemo - Windows System Toolkit")
+        .style(Style::default().fg(theme().title).add_modifier(Modifier::BOLD))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(header, chunks[0]);
+
+    let body = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+        .split(chunks[1]);
+
+    let list_items: Vec<ListItem> = actions
+        .iter()
+        .enumerate()
+        .map(|(i, action)| {
+            let style = if i == selected {
+                Style::default().fg(theme().selected_fg).bg(theme().selected_bg).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme().text)
+            };
+            let shortcut = if i < 9 { format!("{}", i + 1) } else { " ".to_string() };
+            ListItem::new(format!(" {} {}", shortcut, action.title)).style(style)
+        })
+        .collect();
+
+    let list = List::new(list_items)
+        .block(Block::default().title("Main Menu").borders(Borders::ALL).border_style(Style::default().fg(theme().accent)));
+    f.render_widget(list, body[0]);
+
+    if let Some(action) = actions.get(selected) {
+        render_description(f, action, body[1]);
+    }
+
+    let footer_text = match message {
+        Some(message) => Line::from(Span::styled(message.to_string(), Style::default().fg(theme().warn))),
+        None => Line::from("↑/↓/Click: Navigate | Enter/1-9/Double-click: Run | : or Ctrl+P: Command palette | Q/Esc: Quit"),
+    };
+    let footer = Paragraph::new(footer_text)
+        .style(Style::default().fg(theme().muted))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(footer, chunks[2]);
+
+    if let Some(palette) = palette {
+        render_palette(f, actions, palette);
+    }
+
+    body[0]
+}
+
+// 鼠标位置对应的菜单项（list_area 为 render_menu 返回的列表区域）
+pub fn item_at(list_area: Rect, column: u16, row: u16, count: usize) -> Option<usize> {
+    let inner = Block::default().borders(Borders::ALL).inner(list_area);
+    if !inner.contains(Position::new(column, row)) {
+        return None;
+    }
+    let index = (row - inner.y) as usize;
+    (index < count).then_some(index)
+}
+
+fn render_description<B: Backend>(f: &mut Frame, action: &Action<B>, area: Rect) {
+    let label = Style::default().fg(theme().muted);
+    let mut lines = vec![
+        Line::from(Span::styled(action.title, Style::default().fg(theme().title).add_modifier(Modifier::BOLD))),
+        Line::from(""),
+        Line::from(action.description),
+        Line::from(""),
+        Line::from(vec![Span::styled("Command: ", label), Span::raw(action.id)]),
+    ];
+    if action.requires_admin {
+        let (text, color) = if is_admin() {
+            ("🛡️ Requires administrator (granted)", theme().ok)
+        } else {
+            ("🛡️ Requires administrator", theme().critical)
+        };
+        lines.push(Line::from(Span::styled(text, Style::default().fg(color))));
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .block(Block::default().title(" Description ").borders(Borders::ALL).border_style(Style::default().fg(theme().accent)))
+        .wrap(Wrap { trim: true });
+    f.render_widget(paragraph, area);
+}
+
+fn render_palette<B: Backend>(f: &mut Frame, actions: &[Action<B>], palette: &Palette) {
+    let area = f.area();
+    let width = area.width.saturating_sub(10).min(70);
+    let height = area.height.saturating_sub(6).min(14);
+    let popup = Rect::new(area.x + (area.width - width) / 2, area.y + 3, width, height);
+    f.render_widget(Clear, popup);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0)])
+        .split(popup);
+
+    let input = Paragraph::new(Line::from(vec![
+        Span::styled(": ", Style::default().fg(theme().accent)),
+        Span::raw(palette.query.clone()),
+        Span::styled("█", Style::default().fg(theme().muted)),
+    ]))
+    .block(Block::default().title(" Command Palette ").borders(Borders::ALL).border_style(Style::default().fg(theme().title)));
+    f.render_widget(input, chunks[0]);
+
+    let matches = palette.matches(actions);
+    let items: Vec<ListItem> = if matches.is_empty() {
+        vec![ListItem::new(Span::styled(" No matching commands", Style::default().fg(theme().muted)))]
+    } else {
+        matches
+            .iter()
+            .enumerate()
+            .map(|(i, (index, positions))| {
+                let action = &actions[*index];
+                let base = if i == palette.selected {
+                    Style::default().fg(theme().selected_fg).bg(theme().selected_bg).add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme().text)
+                };
+                let mut line = highlighted(action.title, positions, base);
+                line.spans.insert(0, Span::styled(" ", base));
+                line.spans.push(Span::styled(format!("  {}", action.id), base.fg(theme().muted)));
+                ListItem::new(line)
+            })
+            .collect()
+    };
+    let list = List::new(items).block(
+        Block::default()
+            .title(" ↑/↓: Select | Enter: Run | Esc: Close ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme().title)),
+    );
+    f.render_widget(list, chunks[1]);
+}
+
+// Schedules 界面：查看和删除计划（添加使用命令行）
+fn show_schedules<B: Backend>(terminal: &mut Terminal<B>) -> Result<()> {
+    let mut selected = 0;
+    let mut message: Option<String> = None;
+    // 等待确认删除的计划
+    let mut confirm: Option<u32> = None;
+
+    loop {
+        let (schedules, error) = match schedule::load() {
+            Ok(schedules) => (schedules, None),
+            Err(e) => (Vec::new(), Some(format!("❌ {:#}", e))),
+        };
+        let state = schedule::load_state();
+        selected = selected.min(schedules.len().saturating_sub(1));
+        let footer = error.or_else(|| message.clone());
+        terminal.draw(|f| {
+            render_schedules(f, &schedules, &state, selected, footer.as_deref());
+            theme::finish_frame(f);
+        })?;
+
+        if !event::poll(Duration::from_millis(500))? {
+            continue;
+        }
+        let key = match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => key,
+            _ => continue,
+        };
+        message = None;
+        if let Some(id) = confirm.take() {
+            if matches!(key.code, KeyCode::Char('y') | KeyCode::Char('Y')) {
+                message = Some(match schedule::remove(id) {
+                    Ok(()) => format!("🗑️ Removed schedule #{}", id),
+                    Err(e) => format!("❌ {:#}", e),
+                });
+            }
+            continue;
+        }
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => break,
+            KeyCode::Up | KeyCode::Char('k') => selected = selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => selected = (selected + 1).min(schedules.len().saturating_sub(1)),
+            KeyCode::Char('d') | KeyCode::Delete => {
+                if let Some(schedule) = schedules.get(selected) {
+                    confirm = Some(schedule.id);
+                    message = Some(format!("Remove schedule #{} ({})? Y to confirm", schedule.id, schedule.task));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    terminal.clear()?;
+    Ok(())
+}
+
+fn render_schedules(
+    f: &mut Frame,
+    schedules: &[schedule::Schedule],
+    state: &BTreeMap<u32, schedule::ScheduleState>,
+    selected: usize,
+    message: Option<&str>,
+) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0), Constraint::Length(3)])
+        .split(f.area());
+
+    let title = Paragraph::new(format!("⏳ Schedules ({})", schedules.len()))
+        .style(Style::default().fg(theme().accent).add_modifier(Modifier::BOLD))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(title, chunks[0]);
+
+    let block = Block::default()
+        .title(" Registered schedules (run by `lemo daemon`) ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme().accent));
+    if schedules.is_empty() {
+        let empty = Paragraph::new("No schedules. Add one with `lemo schedule add clean-temp --every weekly --at 03:00`.")
+            .style(Style::default().fg(theme().muted))
+            .alignment(Alignment::Center)
+            .block(block);
+        f.render_widget(empty, chunks[1]);
+    } else {
+        let now = Local::now();
+        let header = Row::new(vec!["ID", "Task", "When", "Options", "Last run", "Next run", "Last result"])
+            .style(Style::default().fg(theme().title).add_modifier(Modifier::BOLD));
+        let rows: Vec<Row> = schedules
+            .iter()
+            .enumerate()
+            .map(|(index, schedule)| {
+                let entry = state.get(&schedule.id);
+                let style = if index == selected {
+                    Style::default()
+                        .fg(theme().selected_fg)
+                        .bg(theme().selected_bg)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                };
+                Row::new(vec![
+                    Cell::from(schedule.id.to_string()),
+                    Cell::from(schedule.task.clone()),
+                    Cell::from(schedule.when_display()),
+                    Cell::from(schedule.options_display()),
+                    Cell::from(schedule::time_display(entry.and_then(|s| s.last_run.as_deref()))),
+                    Cell::from(
+                        schedule
+                            .next_slot(now)
+                            .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+                            .unwrap_or_else(|| "-".to_string()),
+                    ),
+                    Cell::from(entry.and_then(|s| s.last_result.clone()).unwrap_or_else(|| "-".to_string())),
+                ])
+                .style(style)
+            })
+            .collect();
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(4),
+                Constraint::Length(17),
+                Constraint::Length(24),
+                Constraint::Length(22),
+                Constraint::Length(17),
+                Constraint::Length(17),
+                Constraint::Min(10),
+            ],
+        )
+        .header(header)
+        .block(block);
+        f.render_widget(table, chunks[1]);
+    }
+
+    let footer = match message {
+        Some(message) => Paragraph::new(message).style(Style::default().fg(theme().warn)),
+        None => Paragraph::new("↑/↓: Select | D: Remove | Add: lemo schedule add ... | Q/Esc: Return")
+            .style(Style::default().fg(theme().muted)),
+    }
+    .alignment(Alignment::Center)
+    .block(Block::default().borders(Borders::ALL));
+    f.render_widget(footer, chunks[2]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+
+    fn action(id: &'static str, title: &'static str) -> Action<TestBackend> {
+        Action {
+            id,
+            title,
+            description: "",
+            requires_admin: false,
+            handler: |_| Ok(Flow::Continue),
+        }
+    }
+
+    #[test]
+    fn fuzzy_match_positions_and_case() {
+        assert_eq!(fuzzy_match("", "Anything"), Some((0, Vec::new())));
+        let (_, positions) = fuzzy_match("CT", "clean temp").unwrap();
+        assert_eq!(positions, vec![0, 6]);
+        assert_eq!(fuzzy_match("ctf", "clean temp"), None);
+        // 顺序必须一致
+        assert_eq!(fuzzy_match("tc", "clean"), None);
+        // 查询中的空格被忽略
+        assert_eq!(fuzzy_match("c t", "clean temp"), fuzzy_match("ct", "clean temp"));
+    }
+
+    #[test]
+    fn fuzzy_match_prefers_consecutive_and_word_starts() {
+        let score = |query, text| fuzzy_match(query, text).unwrap().0;
+        assert!(score("mon", "Monitor") > score("mon", "Common"));
+        assert!(score("ht", "History tools") > score("ht", "Shortcut"));
+        assert!(score("jobs", "Jobs") > score("jobs", "Queue jobs"));
+    }
+
+    #[test]
+    fn palette_ranks_matches() {
+        let actions = vec![
+            action("clean-temp", "🧹 Clean Temp Files"),
+            action("monitor", "📊 Real-time Monitor"),
+            action("history", "📋 Operation History"),
+            action("exit", "➡️ Exit"),
+        ];
+        let matches = |query: &str| {
+            Palette {
+                query: query.to_string(),
+                selected: 0,
+            }
+            .matches(&actions)
+            .into_iter()
+            .map(|(index, _)| actions[index].id)
+            .collect::<Vec<_>>()
+        };
+        // 空查询保持菜单顺序
+        assert_eq!(matches(""), ["clean-temp", "monitor", "history", "exit"]);
+        assert_eq!(matches("MONITOR"), ["monitor"]);
+        assert_eq!(matches("hist"), ["history"]);
+        assert_eq!(matches("zzz"), Vec::<&str>::new());
+        // 只有 id 匹配时不高亮标题
+        let by_id = Palette {
+            query: "clean-t".to_string(),
+            selected: 0,
+        }
+        .matches(&actions);
+        assert_eq!(by_id.first().map(|(index, _)| *index), Some(0));
+        assert!(by_id[0].1.is_empty());
+    }
+}
@@ -0,0 +1,389 @@
+// Real-time system monitor
+mod overview;
+mod render;
+mod source;
+
+use crate::recording::Recorder;
+use crate::snapshot::{self, MemorySnapshot, Snapshot};
+use crate::theme;
+use anyhow::Result;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Position, Rect};
+use ratatui::Terminal;
+use render::{render_memory_detail, render_monitor_ui, render_sensor_detail};
+use source::{FixtureSource, LiveSource, MetricsSource, RemoteSource, ReplaySource};
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+pub use overview::show_multi_host_overview;
+
+// 监控视图
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum MonitorView {
+    Overview,
+    Memory,
+    Sensors,
+}
+
+// 总览中的面板（点击后放大或进入详情视图）
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Panel {
+    Cpu,
+    Memory,
+    Battery,
+    Disks,
+    System,
+    Sensors,
+    Network,
+}
+
+// 上一帧中可以点击的区域，由渲染函数返回
+#[derive(Default)]
+struct MouseTargets {
+    panels: Vec<(Panel, Rect)>,
+    // 进程列表的内容区域（每行一个进程）与显示的 PID
+    process_rows: Option<(Rect, Vec<u32>)>,
+}
+
+// 可选的刷新间隔（毫秒），通过 +/- 切换
+const REFRESH_INTERVALS_MS: [u64; 6] = [250, 500, 1000, 2000, 5000, 10000];
+
+// 监控界面状态（当前视图、暂停、刷新间隔、状态提示）
+struct MonitorState {
+    view: MonitorView,
+    paused: bool,
+    interval_index: usize,
+    message: Option<(String, Instant)>,
+    // 正在录制到的文件
+    recording: Option<PathBuf>,
+    // 回放时显示的播放状态与标题
+    playback: Option<String>,
+    title: String,
+    // 放大显示的面板
+    maximized: Option<Panel>,
+    // 内存视图中选中的进程
+    selected_process: Option<u32>,
+    // 数据来源中断（如远程连接断开）的原因，之后保持显示最后一份快照
+    disconnected: Option<String>,
+}
+
+impl MonitorState {
+    fn new() -> Self {
+        Self {
+            view: MonitorView::Overview,
+            paused: false,
+            interval_index: 2, // 默认 1 秒刷新一次
+            message: None,
+            recording: None,
+            playback: None,
+            title: "📊 Real-time System Monitor".to_string(),
+            maximized: None,
+            selected_process: None,
+            disconnected: None,
+        }
+    }
+
+    fn interval(&self) -> Duration {
+        Duration::from_millis(REFRESH_INTERVALS_MS[self.interval_index])
+    }
+
+    fn faster(&mut self) {
+        self.interval_index = self.interval_index.saturating_sub(1);
+    }
+
+    fn slower(&mut self) {
+        self.interval_index = (self.interval_index + 1).min(REFRESH_INTERVALS_MS.len() - 1);
+    }
+
+    fn toggle_view(&mut self, view: MonitorView) {
+        self.view = if self.view == view { MonitorView::Overview } else { view };
+        self.maximized = None;
+    }
+
+    // 点击面板：内存和温度进入详情视图，其他面板放大，再次点击还原
+    fn click_panel(&mut self, panel: Panel) {
+        match (panel, self.maximized) {
+            (_, Some(_)) => self.maximized = None,
+            (Panel::Memory, None) => self.toggle_view(MonitorView::Memory),
+            (Panel::Sensors, None) => self.toggle_view(MonitorView::Sensors),
+            (panel, None) => self.maximized = Some(panel),
+        }
+    }
+
+    // 在内存视图的进程列表中上下移动选中项
+    fn move_process_selection(&mut self, pids: &[u32], delta: isize) {
+        let current = self
+            .selected_process
+            .and_then(|pid| pids.iter().position(|&p| p == pid));
+        let next = match current {
+            Some(index) => (index as isize + delta).clamp(0, pids.len() as isize - 1) as usize,
+            None => 0,
+        };
+        self.selected_process = pids.get(next).copied();
+    }
+
+    fn handle_mouse(&mut self, mouse: MouseEvent, targets: &MouseTargets) {
+        if mouse.kind != MouseEventKind::Down(MouseButton::Left) {
+            return;
+        }
+        let position = Position::new(mouse.column, mouse.row);
+
+        if let Some((area, pids)) = &targets.process_rows {
+            if area.contains(position) {
+                let row = (position.y - area.y) as usize;
+                if let Some(&pid) = pids.get(row) {
+                    self.selected_process = if self.selected_process == Some(pid) { None } else { Some(pid) };
+                }
+                return;
+            }
+        }
+
+        if let Some((panel, _)) = targets.panels.iter().find(|(_, area)| area.contains(position)) {
+            self.click_panel(*panel);
+        }
+    }
+
+    fn notify(&mut self, message: String) {
+        self.message = Some((message, Instant::now()));
+    }
+
+    // 状态提示显示 5 秒
+    fn current_message(&self) -> Option<&str> {
+        self.message
+            .as_ref()
+            .filter(|(_, at)| at.elapsed() < Duration::from_secs(5))
+            .map(|(msg, _)| msg.as_str())
+    }
+}
+
+// 内存历史采样点（字节）
+#[derive(Clone, Copy)]
+struct MemorySample {
+    used: u64,
+    total: u64,
+    swap_used: u64,
+    swap_total: u64,
+}
+
+// 内存历史记录，用于长时间运行时观察内存泄漏趋势
+struct MemoryHistory {
+    samples: VecDeque<MemorySample>,
+    capacity: usize,
+}
+
+impl MemoryHistory {
+    fn new(capacity: usize) -> Self {
+        Self {
+            samples: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    fn record(&mut self, memory: &MemorySnapshot) {
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(MemorySample {
+            used: memory.used,
+            total: memory.total,
+            swap_used: memory.swap_used,
+            swap_total: memory.swap_total,
+        });
+    }
+
+    // 回放跳转后根据录制数据重建历史
+    fn rebuild<'a>(&mut self, snapshots: impl DoubleEndedIterator<Item = &'a Snapshot>) {
+        let recent: Vec<&Snapshot> = snapshots.rev().take(self.capacity).collect();
+        self.samples.clear();
+        for snapshot in recent.into_iter().rev() {
+            self.record(&snapshot.memory);
+        }
+    }
+
+    // 转换为图表数据点（x: 采样序号, y: 使用率百分比）
+    fn memory_points(&self) -> Vec<(f64, f64)> {
+        self.samples
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (i as f64, percent_of(s.used, s.total)))
+            .collect()
+    }
+
+    fn swap_points(&self) -> Vec<(f64, f64)> {
+        self.samples
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (i as f64, percent_of(s.swap_used, s.swap_total)))
+            .collect()
+    }
+}
+
+fn percent_of(used: u64, total: u64) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        used as f64 / total as f64 * 100.0
+    }
+}
+
+// 监控启动选项（来自 `lemo monitor` 命令行）
+#[derive(Default)]
+pub struct MonitorOptions {
+    pub record: Option<PathBuf>,
+    pub replay: Option<PathBuf>,
+    pub fixture: Option<PathBuf>,
+    // 远程 agent 地址（host:port）
+    pub connect: Option<String>,
+    pub token: String,
+}
+
+impl MonitorOptions {
+    fn open_source(&self, interval: Duration) -> Result<Box<dyn MetricsSource>> {
+        Ok(match (&self.replay, &self.fixture, &self.connect) {
+            (Some(path), _, _) => Box::new(ReplaySource::load(path)?),
+            (None, Some(path), _) => Box::new(FixtureSource::load(path)?),
+            (None, None, Some(address)) => Box::new(RemoteSource::connect(address, &self.token)),
+            (None, None, None) => Box::new(LiveSource::start(interval)),
+        })
+    }
+}
+
+// 实时系统监控仪表盘
+pub fn show_realtime_monitor<B: ratatui::backend::Backend>(
+    terminal: &mut Terminal<B>,
+    options: MonitorOptions,
+) -> Result<()> {
+    let mut state = MonitorState::new();
+    let mut memory_history = MemoryHistory::new(600); // 保留最近 600 个采样点
+
+    let mut source = options.open_source(state.interval())?;
+
+    let mut recorder = match &options.record {
+        Some(path) => {
+            let recorder = Recorder::create(path)?;
+            state.recording = Some(path.clone());
+            Some(recorder)
+        }
+        None => None,
+    };
+
+    let mut targets = MouseTargets::default();
+    let mut current = source.first()?;
+    state.title = source.title();
+    memory_history.record(&current.memory);
+    if let Some(recorder) = recorder.as_mut() {
+        recorder.record(&current)?;
+    }
+
+    loop {
+        // 暂停时丢弃新数据：远程 agent 不受暂停控制，仍会继续推送
+        let polled = match source.poll() {
+            _ if state.disconnected.is_some() => None,
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                state.disconnected = Some(format!("{:#}", e));
+                None
+            }
+        };
+        if let Some(snapshot) = polled.filter(|_| !state.paused) {
+            current = snapshot;
+            match source.playback() {
+                // 回放时历史趋势跟随播放位置
+                Some(player) => memory_history.rebuild(player.history()),
+                None => memory_history.record(&current.memory),
+            }
+            if let Some(rec) = recorder.as_mut() {
+                if let Err(e) = rec.record(&current) {
+                    state.notify(format!("❌ Recording stopped: {}", e));
+                    state.recording = None;
+                    recorder = None;
+                }
+            }
+        }
+        state.playback = source.playback().map(|player| player.status());
+
+        terminal.draw(|f| {
+            targets = match state.view {
+                MonitorView::Overview => render_monitor_ui(f, &current, &state),
+                MonitorView::Memory => render_memory_detail(f, &current, &memory_history, &state),
+                MonitorView::Sensors => render_sensor_detail(f, &current.sensors, &state),
+            };
+            theme::finish_frame(f);
+        })?;
+
+        // 非阻塞事件检测
+        if event::poll(Duration::from_millis(100))? {
+            match event::read()? {
+                Event::Mouse(mouse) => state.handle_mouse(mouse, &targets),
+                Event::Key(key) if key.kind == KeyEventKind::Press => {
+                    // 回放时 P/+/- 控制播放，方向键跳转
+                    if let Some(player) = source.playback() {
+                        let seeked = match key.code {
+                            KeyCode::Char('p') | KeyCode::Char(' ') => {
+                                player.toggle_play();
+                                false
+                            }
+                            KeyCode::Char('+') | KeyCode::Char('=') => {
+                                player.faster();
+                                false
+                            }
+                            KeyCode::Char('-') => {
+                                player.slower();
+                                false
+                            }
+                            KeyCode::Left => player.seek(-10.0),
+                            KeyCode::Right => player.seek(10.0),
+                            KeyCode::Home => player.seek_start(),
+                            KeyCode::End => player.seek_end(),
+                            _ => false,
+                        };
+                        if seeked {
+                            current = player.current().clone();
+                            memory_history.rebuild(player.history());
+                        }
+                    } else {
+                        match key.code {
+                            KeyCode::Char('p') | KeyCode::Char(' ') => {
+                                state.paused = !state.paused;
+                                source.set_paused(state.paused);
+                            }
+                            KeyCode::Char('+') | KeyCode::Char('=') => {
+                                state.faster();
+                                source.set_interval(state.interval());
+                            }
+                            KeyCode::Char('-') => {
+                                state.slower();
+                                source.set_interval(state.interval());
+                            }
+                            _ => {}
+                        }
+                    }
+
+                    match key.code {
+                        // 先还原放大的面板
+                        KeyCode::Esc if state.maximized.is_some() => state.maximized = None,
+                        KeyCode::Char('q') | KeyCode::Esc | KeyCode::Enter => {
+                            break;
+                        }
+                        KeyCode::Up | KeyCode::Down if state.view == MonitorView::Memory => {
+                            let pids: Vec<u32> = current.top_memory_processes.iter().map(|p| p.pid).collect();
+                            state.move_process_selection(&pids, if key.code == KeyCode::Up { -1 } else { 1 });
+                        }
+                        KeyCode::Char('m') => state.toggle_view(MonitorView::Memory),
+                        KeyCode::Char('t') => state.toggle_view(MonitorView::Sensors),
+                        KeyCode::Char('s') => match snapshot::save_snapshot(&current) {
+                            Ok(path) => state.notify(format!("📸 Snapshot saved: {} (+ .json)", path.display())),
+                            Err(e) => state.notify(format!("❌ Failed to save snapshot: {}", e)),
+                        },
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    terminal.clear()?;
+    Ok(())
+}
@@ -0,0 +1,212 @@
+// Multi-host overview: one compact row per remote agent
+use super::{percent_of, show_realtime_monitor, MonitorOptions};
+use crate::remote::{RemoteClient, RemoteEvent};
+use crate::snapshot::Snapshot;
+use crate::theme::{self, theme};
+use anyhow::Result;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::Span,
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+    Frame, Terminal,
+};
+use std::time::{Duration, Instant};
+
+// 连接断开后自动重连的间隔
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+// 超过该时间没有收到数据则标记为无响应
+const STALE_AFTER: Duration = Duration::from_secs(15);
+
+struct HostEntry {
+    client: RemoteClient,
+    hostname: Option<String>,
+    latest: Option<Snapshot>,
+    error: Option<String>,
+    last_event: Instant,
+}
+
+impl HostEntry {
+    fn connect(address: &str, token: &str) -> Self {
+        Self {
+            client: RemoteClient::connect(address, token),
+            hostname: None,
+            latest: None,
+            error: None,
+            last_event: Instant::now(),
+        }
+    }
+
+    fn update(&mut self) {
+        for event in self.client.drain() {
+            self.last_event = Instant::now();
+            match event {
+                RemoteEvent::Connected { hostname } => {
+                    self.hostname = hostname;
+                    self.error = None;
+                }
+                RemoteEvent::Snapshot(snapshot) => self.latest = Some(*snapshot),
+                RemoteEvent::Failed(error) => self.error = Some(error),
+            }
+        }
+    }
+
+    fn status(&self) -> (String, Color) {
+        match (&self.error, &self.latest) {
+            (Some(error), _) => (format!("❌ {}", error), theme().critical),
+            (None, Some(_)) if self.last_event.elapsed() > STALE_AFTER => ("⚠️ Stale".to_string(), theme().warn),
+            (None, Some(_)) => ("✅ Online".to_string(), theme().ok),
+            (None, None) => ("⏳ Connecting".to_string(), theme().muted),
+        }
+    }
+}
+
+// 多主机总览：每个 agent 一行 CPU / 内存 / 磁盘，回车打开该主机的完整监控
+pub fn show_multi_host_overview<B: ratatui::backend::Backend>(
+    terminal: &mut Terminal<B>,
+    addresses: &[String],
+    token: &str,
+) -> Result<()> {
+    let mut hosts: Vec<HostEntry> = addresses.iter().map(|a| HostEntry::connect(a, token)).collect();
+    let mut selected = 0;
+
+    loop {
+        for (index, host) in hosts.iter_mut().enumerate() {
+            host.update();
+            if host.error.is_some() && host.last_event.elapsed() > RECONNECT_DELAY {
+                *host = HostEntry::connect(&addresses[index], token);
+            }
+        }
+
+        terminal.draw(|f| {
+            render_overview(f, &hosts, selected);
+            theme::finish_frame(f);
+        })?;
+
+        if event::poll(Duration::from_millis(200))? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    match key.code {
+                        KeyCode::Char('q') | KeyCode::Esc => break,
+                        KeyCode::Up => selected = selected.saturating_sub(1),
+                        KeyCode::Down => selected = (selected + 1).min(hosts.len().saturating_sub(1)),
+                        KeyCode::Char('r') => hosts[selected] = HostEntry::connect(&addresses[selected], token),
+                        KeyCode::Enter => {
+                            let options = MonitorOptions {
+                                connect: Some(addresses[selected].clone()),
+                                token: token.to_string(),
+                                ..Default::default()
+                            };
+                            // 单机视图连接失败时回到总览
+                            if let Err(e) = show_realtime_monitor(terminal, options) {
+                                hosts[selected].error = Some(format!("{:#}", e));
+                                hosts[selected].last_event = Instant::now();
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+
+    terminal.clear()?;
+    Ok(())
+}
+
+fn render_overview(f: &mut Frame, hosts: &[HostEntry], selected: usize) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0), Constraint::Length(3)])
+        .split(f.area());
+
+    let online = hosts.iter().filter(|h| h.error.is_none() && h.latest.is_some()).count();
+    let title = Paragraph::new(format!("🌐 Multi-host Overview ({}/{} online)", online, hosts.len()))
+        .style(Style::default().fg(theme().accent).add_modifier(Modifier::BOLD))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(title, chunks[0]);
+
+    let header = Row::new(vec!["Host", "Status", "CPU", "Memory", "Fullest disk", "Uptime", "Load"])
+        .style(Style::default().fg(theme().title).add_modifier(Modifier::BOLD));
+
+    let rows: Vec<Row> = hosts
+        .iter()
+        .enumerate()
+        .map(|(index, host)| {
+            let name = match &host.hostname {
+                Some(hostname) => format!("{} ({})", hostname, host.client.address()),
+                None => host.client.address().to_string(),
+            };
+            let (status, status_color) = host.status();
+
+            let mut cells = vec![Cell::from(name), Cell::from(Span::styled(status, Style::default().fg(status_color)))];
+            match &host.latest {
+                Some(snapshot) => {
+                    let cpu = snapshot.cpu.usage as f64;
+                    let memory = percent_of(snapshot.memory.used, snapshot.memory.total);
+                    let disk = snapshot
+                        .disks
+                        .iter()
+                        .map(|d| (d, percent_of(d.total.saturating_sub(d.available), d.total)))
+                        .max_by(|a, b| a.1.total_cmp(&b.1));
+
+                    cells.push(Cell::from(Span::styled(
+                        format!("{:>5.1}%", cpu),
+                        Style::default().fg(theme().level(cpu, 50.0, 80.0)),
+                    )));
+                    cells.push(Cell::from(Span::styled(
+                        format!("{:>5.1}% of {:.1} GB", memory, snapshot.memory.total as f64 / 1024.0 / 1024.0 / 1024.0),
+                        Style::default().fg(theme().level(memory, 70.0, 90.0)),
+                    )));
+                    cells.push(match disk {
+                        Some((disk, percent)) => Cell::from(Span::styled(
+                            format!("{} {:.0}%", disk.mount_point, percent),
+                            Style::default().fg(theme().level(percent, 70.0, 90.0)),
+                        )),
+                        None => Cell::from("-"),
+                    });
+                    cells.push(Cell::from(snapshot.system.uptime_display()));
+                    cells.push(Cell::from(snapshot.system.load_display().unwrap_or_else(|| "-".to_string())));
+                }
+                None => cells.extend((0..5).map(|_| Cell::from("-"))),
+            }
+
+            let style = if index == selected {
+                Style::default().bg(theme().selected_bg).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            Row::new(cells).style(style)
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Percentage(24),
+            Constraint::Percentage(20),
+            Constraint::Length(8),
+            Constraint::Length(20),
+            Constraint::Length(16),
+            Constraint::Length(12),
+            Constraint::Min(10),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .title(" Agents ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme().accent)),
+    );
+    f.render_widget(table, chunks[1]);
+
+    let footer = Paragraph::new("↑/↓: Select | Enter: Open dashboard | R: Reconnect | Q/Esc: Return")
+        .style(Style::default().fg(theme().muted))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(footer, chunks[2]);
+}
@@ -0,0 +1,953 @@
+// Monitor renderers: every panel is drawn from a Snapshot only
+use super::{percent_of, MemoryHistory, MonitorState, MouseTargets, Panel};
+use crate::battery;
+use crate::sensors::SensorReading;
+use crate::snapshot::{CpuSnapshot, DiskSnapshot, MemorySnapshot, NetworkSnapshot, ProcessSnapshot, Snapshot};
+use crate::system_report::SystemReport;
+use crate::theme::theme;
+use crate::utils::is_admin;
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    symbols,
+    text::{Line, Span},
+    widgets::{Axis, Block, Borders, Cell, Chart, Dataset, Gauge, GraphType, Paragraph, Row, Table},
+    Frame,
+};
+use std::time::Duration;
+
+// 渲染监控底部提示栏（各视图共用）
+fn render_monitor_footer(f: &mut Frame, area: Rect, hints: &str, state: &MonitorState) {
+    let line = match (state.current_message(), &state.playback) {
+        (Some(message), _) => Line::from(Span::styled(message.to_string(), Style::default().fg(theme().title))),
+        (None, Some(playback)) => Line::from(vec![
+            Span::raw(format!("{} | P: Play/Pause | ←/→: Seek | +/-: Speed | S: Snapshot | Q/Esc: Return | ", hints)),
+            Span::styled(playback.clone(), Style::default().fg(theme().accent).add_modifier(Modifier::BOLD)),
+        ]),
+        (None, None) => {
+            let mut spans = vec![Span::raw(format!(
+                "{} | P: Pause | +/-: Interval | S: Snapshot | Q/Esc: Return | ",
+                hints
+            ))];
+            if let Some(jobs) = crate::jobs::scheduler().summary() {
+                spans.push(Span::styled(format!("{} | ", jobs), Style::default().fg(theme().title)));
+            }
+            if state.recording.is_some() {
+                spans.push(Span::styled("⏺ REC ", Style::default().fg(theme().critical).add_modifier(Modifier::BOLD)));
+            }
+            spans.push(if let Some(error) = &state.disconnected {
+                Span::styled(
+                    format!("⚠️ Disconnected: {}", error),
+                    Style::default().fg(theme().critical).add_modifier(Modifier::BOLD),
+                )
+            } else if state.paused {
+                Span::styled("⏸ PAUSED", Style::default().fg(theme().warn).add_modifier(Modifier::BOLD))
+            } else {
+                Span::raw(format!("Refresh: {:.2}s", state.interval().as_secs_f64()))
+            });
+            Line::from(spans)
+        }
+    };
+
+    let footer = Paragraph::new(line)
+        .style(Style::default().fg(theme().muted))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(footer, area);
+}
+
+// 渲染监控 UI（Grid 布局）
+pub fn render_monitor_ui(f: &mut Frame, snapshot: &Snapshot, state: &MonitorState) -> MouseTargets {
+    // 主布局：顶部标题 + 中间内容 + 底部提示
+    let main_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),  // 标题
+            Constraint::Min(0),     // 内容
+            Constraint::Length(3),  // 底部提示
+        ])
+        .split(f.area());
+    
+    // 标题
+    let header = Paragraph::new(state.title.as_str())
+        .style(Style::default().fg(theme().accent).add_modifier(Modifier::BOLD))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(header, main_chunks[0]);
+
+    // 放大显示单个面板，点击或 Esc 还原
+    if let Some(panel) = state.maximized {
+        render_panel(f, panel, snapshot, main_chunks[1]);
+        render_monitor_footer(f, main_chunks[2], "Click/Esc: Restore | M: Memory | T: Sensors", state);
+        return MouseTargets {
+            panels: vec![(panel, main_chunks[1])],
+            process_rows: None,
+        };
+    }
+    
+    // 内容区域：左右分栏
+    let content_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(50),  // 左侧：CPU + 内存 + 电池 + 磁盘
+            Constraint::Percentage(50),  // 右侧：系统信息 + 温度 + 网络
+        ])
+        .split(main_chunks[1]);
+    
+    // 左侧：上下分割（CPU + 内存 + 电池 + 磁盘）
+    let left_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),  // CPU
+            Constraint::Length(3),  // 内存
+            Constraint::Length(4),  // 电池
+            Constraint::Min(0),  // 磁盘
+        ])
+        .split(content_chunks[0]);
+    
+    // 右侧：上中下分割（系统信息 + 温度 + 网络）
+    let right_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(9),  // 系统信息
+            Constraint::Length(6),  // 温度传感器
+            Constraint::Min(0),  // 网络
+        ])
+        .split(content_chunks[1]);
+    
+    let panels = vec![
+        (Panel::Cpu, left_chunks[0]),
+        (Panel::Memory, left_chunks[1]),
+        (Panel::Battery, left_chunks[2]),
+        (Panel::Disks, left_chunks[3]),
+        (Panel::System, right_chunks[0]),
+        (Panel::Sensors, right_chunks[1]),
+        (Panel::Network, right_chunks[2]),
+    ];
+    for (panel, area) in &panels {
+        render_panel(f, *panel, snapshot, *area);
+    }
+    
+    // 底部提示
+    render_monitor_footer(f, main_chunks[2], "Click: Zoom | M: Memory | T: Sensors", state);
+
+    MouseTargets {
+        panels,
+        process_rows: None,
+    }
+}
+
+fn render_panel(f: &mut Frame, panel: Panel, snapshot: &Snapshot, area: Rect) {
+    match panel {
+        Panel::Cpu => render_cpu_info(f, &snapshot.cpu, area),
+        Panel::Memory => render_memory_info(f, &snapshot.memory, area),
+        Panel::Battery => render_battery_info(f, &snapshot.power, area),
+        Panel::Disks => render_disk_info(f, &snapshot.disks, area),
+        Panel::System => render_system_info(f, &snapshot.system, area),
+        Panel::Sensors => render_sensor_summary(f, &snapshot.sensors, area),
+        Panel::Network => render_network_info(f, &snapshot.networks, area),
+    }
+}
+
+// 渲染 CPU 信息
+fn render_cpu_info(f: &mut Frame, cpu: &CpuSnapshot, area: Rect) {
+    let total_usage = cpu.usage;
+    
+    // 简化CPU名称显示
+    let cpu_brand = if cpu.brand.is_empty() {
+        "Unknown".to_string()
+    } else if cpu.brand.chars().count() > 35 {
+        format!("{}...", cpu.brand.chars().take(32).collect::<String>())
+    } else {
+        cpu.brand.clone()
+    };
+    
+    let gauge_color = theme().level(total_usage as f64, 50.0, 80.0);
+
+    let label = Span::styled(
+        format!("{:.1}%", total_usage),
+        Style::default().fg(theme().text).add_modifier(Modifier::BOLD)
+    );
+    
+    // 使用紧凑的 Block 样式
+    let gauge = Gauge::default()
+        .block(
+            Block::default()
+                .title(format!(" 🔧 CPU: {} ", cpu_brand))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().accent))
+        )
+        .gauge_style(Style::default().fg(gauge_color).add_modifier(Modifier::BOLD))
+        .percent(total_usage as u16)
+        .label(label);
+    
+    f.render_widget(gauge, area);
+}
+
+// 渲染内存信息
+fn render_memory_info(f: &mut Frame, memory: &MemorySnapshot, area: Rect) {
+    let total_mem = memory.total as f64 / 1024.0 / 1024.0 / 1024.0;
+    let used_mem = memory.used as f64 / 1024.0 / 1024.0 / 1024.0;
+    let usage_percent = (used_mem / total_mem) * 100.0;
+    
+    let gauge_color = theme().level(usage_percent, 60.0, 80.0);
+    
+    let label = Span::styled(
+        format!("{:.1}%", usage_percent),
+        Style::default().fg(theme().text).add_modifier(Modifier::BOLD)
+    );
+    
+    // 使用更简洁的标题
+    let gauge = Gauge::default()
+        .block(
+            Block::default()
+                .title(format!(" 💾 Memory: {:.1}/{:.1} GB ", used_mem, total_mem))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().accent))
+        )
+        .gauge_style(Style::default().fg(gauge_color).add_modifier(Modifier::BOLD))
+        .percent(usage_percent as u16)
+        .label(label);
+    
+    f.render_widget(gauge, area);
+}
+
+// 渲染内存详情视图（内存/交换区明细 + 历史趋势 + 占用最高的进程）
+pub fn render_memory_detail(
+    f: &mut Frame,
+    snapshot: &Snapshot,
+    history: &MemoryHistory,
+    state: &MonitorState,
+) -> MouseTargets {
+    const GB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+    let main_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),  // 标题
+            Constraint::Length(8),  // 明细 + 进程
+            Constraint::Min(0),     // 历史趋势
+            Constraint::Length(3),  // 底部提示
+        ])
+        .split(f.area());
+
+    let header = Paragraph::new("💾 Memory Details")
+        .style(Style::default().fg(theme().accent).add_modifier(Modifier::BOLD))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(header, main_chunks[0]);
+
+    let top_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(main_chunks[1]);
+
+    // 内存与交换区（Windows 上为页面文件）明细
+    let memory = &snapshot.memory;
+    let total = memory.total;
+    let used = memory.used;
+    let swap_total = memory.swap_total;
+    let swap_used = memory.swap_used;
+    let mem_percent = percent_of(used, total);
+    let swap_percent = percent_of(swap_used, swap_total);
+
+    let usage_color = |percent: f64| {
+        theme().level(percent, 60.0, 80.0)
+    };
+
+    let mut detail_lines = vec![
+        Line::from(vec![
+            Span::raw(format!("{:<12}", "Used:")),
+            Span::styled(
+                format!("{:.2} / {:.2} GB ({:.1}%)", used as f64 / GB, total as f64 / GB, mem_percent),
+                Style::default().fg(usage_color(mem_percent)).add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(format!("{:<12}{:.2} GB", "Available:", memory.available as f64 / GB)),
+        Line::from(format!("{:<12}{:.2} GB", "Free:", memory.free as f64 / GB)),
+        Line::from(""),
+    ];
+    if swap_total == 0 {
+        detail_lines.push(Line::from(Span::styled(
+            "Swap/Page file: not configured",
+            Style::default().fg(theme().muted),
+        )));
+    } else {
+        detail_lines.push(Line::from(vec![
+            Span::raw(format!("{:<12}", "Swap used:")),
+            Span::styled(
+                format!("{:.2} / {:.2} GB ({:.1}%)", swap_used as f64 / GB, swap_total as f64 / GB, swap_percent),
+                Style::default().fg(usage_color(swap_percent)).add_modifier(Modifier::BOLD),
+            ),
+        ]));
+    }
+
+    let details = Paragraph::new(detail_lines)
+        .block(
+            Block::default()
+                .title(" RAM & Swap/Page File ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().accent))
+        )
+        .style(Style::default().fg(theme().text));
+    f.render_widget(details, top_chunks[0]);
+
+    // 占用内存最多的进程（点击或 ↑/↓ 选中）
+    let visible_rows = top_chunks[1].height.saturating_sub(2) as usize;
+    let visible_processes: Vec<&ProcessSnapshot> = snapshot.top_memory_processes.iter().take(visible_rows).collect();
+    let process_lines: Vec<Line> = visible_processes
+        .iter()
+        .map(|p| {
+            let display_name = if p.name.chars().count() > 24 {
+                format!("{}...", p.name.chars().take(21).collect::<String>())
+            } else {
+                p.name.clone()
+            };
+            let line = Line::from(vec![
+                Span::raw(format!("{:<7} {:<25}", p.pid, display_name)),
+                Span::styled(
+                    format!("{:>9.1} MB", p.memory as f64 / 1024.0 / 1024.0),
+                    Style::default().fg(theme().title),
+                ),
+            ]);
+            if state.selected_process == Some(p.pid) {
+                line.style(Style::default().bg(theme().selected_bg).add_modifier(Modifier::BOLD))
+            } else {
+                line
+            }
+        })
+        .collect();
+
+    let process_title = match snapshot
+        .top_memory_processes
+        .iter()
+        .find(|p| Some(p.pid) == state.selected_process)
+    {
+        Some(p) => format!(" Top Memory Processes - {} (PID {}, CPU {:.1}%) ", p.name, p.pid, p.cpu_usage),
+        None => " Top Memory Processes ".to_string(),
+    };
+    let process_list = Paragraph::new(process_lines)
+        .block(
+            Block::default()
+                .title(process_title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().accent))
+        )
+        .style(Style::default().fg(theme().text));
+    f.render_widget(process_list, top_chunks[1]);
+
+    // 历史趋势图
+    let memory_points = history.memory_points();
+    let swap_points = history.swap_points();
+    let x_max = history.capacity.saturating_sub(1) as f64;
+
+    let mut datasets = vec![Dataset::default()
+        .name("RAM %")
+        .marker(symbols::Marker::Braille)
+        .graph_type(GraphType::Line)
+        .style(Style::default().fg(theme().primary))
+        .data(&memory_points)];
+    if swap_total > 0 {
+        datasets.push(
+            Dataset::default()
+                .name("Swap %")
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::default().fg(theme().secondary))
+                .data(&swap_points),
+        );
+    }
+
+    let chart = Chart::new(datasets)
+        .block(
+            Block::default()
+                .title(format!(" History (last {} samples) ", history.samples.len()))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().accent))
+        )
+        .x_axis(
+            Axis::default()
+                .style(Style::default().fg(theme().muted))
+                .bounds([0.0, x_max]),
+        )
+        .y_axis(
+            Axis::default()
+                .style(Style::default().fg(theme().muted))
+                .bounds([0.0, 100.0])
+                .labels(vec![Span::raw("0%"), Span::raw("50%"), Span::raw("100%")]),
+        );
+    f.render_widget(chart, main_chunks[2]);
+
+    render_monitor_footer(f, main_chunks[3], "M: Back to overview | ↑/↓/Click: Select process", state);
+
+    let process_area = Block::default().borders(Borders::ALL).inner(top_chunks[1]);
+    MouseTargets {
+        panels: Vec::new(),
+        process_rows: Some((process_area, visible_processes.iter().map(|p| p.pid).collect())),
+    }
+}
+
+// 格式化剩余时间（例如 2h 13m）
+fn format_duration_short(duration: Duration) -> String {
+    let minutes = duration.as_secs() / 60;
+    if minutes >= 60 {
+        format!("{}h {}m", minutes / 60, minutes % 60)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+// 渲染电池和电源信息
+fn render_battery_info(f: &mut Frame, power: &battery::PowerStatus, area: Rect) {
+    use battery::ChargeState;
+
+    let mut lines = Vec::new();
+    let title = if power.batteries.len() > 1 {
+        format!(" 🔋 Battery {} (1 of {}) ", power.batteries[0].name, power.batteries.len())
+    } else {
+        " 🔋 Battery ".to_string()
+    };
+
+    match power.batteries.first() {
+        None => {
+            let text = match power.ac_online {
+                Some(true) => "🔌 No battery (AC power)",
+                _ => "No battery",
+            };
+            lines.push(Line::from(Span::styled(text, Style::default().fg(theme().muted))));
+        }
+        Some(battery) => {
+            let percent = battery.percent.unwrap_or(0.0);
+            let color = if battery.state == ChargeState::Charging || battery.state == ChargeState::Full {
+                theme().ok
+            } else {
+                // 电量越低越严重
+                theme().level(100.0 - percent as f64, 70.0, 85.0)
+            };
+
+            let mut status_spans = vec![
+                Span::styled(
+                    battery
+                        .percent
+                        .map(|p| format!("{:.0}%", p))
+                        .unwrap_or_else(|| "?%".to_string()),
+                    Style::default().fg(color).add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(format!("  {}", battery.state.label())),
+            ];
+            if let Some(remaining) = battery.time_remaining {
+                let suffix = if battery.state == ChargeState::Charging {
+                    "until full"
+                } else {
+                    "left"
+                };
+                status_spans.push(Span::raw(format!("  {} {}", format_duration_short(remaining), suffix)));
+            }
+            lines.push(Line::from(status_spans));
+
+            let mut details = Vec::new();
+            if let Some(wear) = battery.wear_percent() {
+                details.push(format!(
+                    "Wear {:.1}% ({:.1}/{:.1} {})",
+                    wear,
+                    battery.full_capacity.unwrap_or(0.0),
+                    battery.design_capacity.unwrap_or(0.0),
+                    battery.capacity_unit.label()
+                ));
+            }
+            if let Some(cycles) = battery.cycle_count {
+                details.push(format!("{} cycles", cycles));
+            }
+            if details.is_empty() {
+                details.push("Capacity details unavailable".to_string());
+            }
+            lines.push(Line::from(Span::styled(details.join(" | "), Style::default().fg(theme().muted))));
+        }
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().accent))
+        )
+        .style(Style::default().fg(theme().text));
+
+    f.render_widget(paragraph, area);
+}
+
+// 渲染网络信息
+fn render_network_info(f: &mut Frame, networks: &[NetworkSnapshot], area: Rect) {
+    let mut network_lines = Vec::new();
+    
+    if networks.is_empty() {
+        network_lines.push(Line::from(Span::styled(
+            "No network interfaces detected",
+            Style::default().fg(theme().warn)
+        )));
+    } else {
+        // 收集有实际网络活动的接口（排除虚拟网卡和无活动接口）
+        let mut active_networks: Vec<_> = networks.iter()
+            .filter_map(|data| {
+                let interface_name = &data.name;
+                let name_lower = interface_name.to_lowercase();
+                
+                // 过滤虚拟网卡和回环接口
+                if name_lower.contains("loopback") 
+                    || name_lower.contains("vmware") 
+                    || name_lower.contains("virtualbox")
+                    || name_lower.contains("vboxnet")
+                    || name_lower.starts_with("lo")
+                {
+                    return None;
+                }
+                
+                // 获取实时网速（字节/秒）
+                let received_speed = data.received_per_sec;
+                let transmitted_speed = data.transmitted_per_sec;
+                
+                // 只显示有实际流量的接口（下载或上传速度 > 1 KB/s）
+                if received_speed < 1024 && transmitted_speed < 1024 {
+                    return None;
+                }
+                
+                Some((interface_name.clone(), received_speed, transmitted_speed))
+            })
+            .collect();
+        
+        // 按接口名称排序
+        active_networks.sort_by(|a, b| a.0.cmp(&b.0));
+        
+        // 只显示前2个活跃的网络接口（与任务管理器一致）
+        for (interface_name, received_speed, transmitted_speed) in active_networks.iter().take(2) {
+            // 截断接口名称以适应显示
+            let display_name = if interface_name.len() > 25 {
+                format!("{}...", &interface_name[..22])
+            } else {
+                interface_name.to_string()
+            };
+            
+            // 格式化速度显示
+            let download_str = format_speed(*received_speed);
+            let upload_str = format_speed(*transmitted_speed);
+            
+            network_lines.push(Line::from(vec![
+                Span::styled(
+                    format!("📡 {}", display_name),
+                    Style::default().fg(theme().accent).add_modifier(Modifier::BOLD)
+                ),
+            ]));
+            
+            network_lines.push(Line::from(vec![
+                Span::raw("   ↓ "),
+                Span::styled(
+                    download_str,
+                    Style::default().fg(theme().primary).add_modifier(Modifier::BOLD)
+                ),
+                Span::raw("  ↑ "),
+                Span::styled(
+                    upload_str,
+                    Style::default().fg(theme().secondary).add_modifier(Modifier::BOLD)
+                ),
+            ]));
+        }
+        
+        if network_lines.is_empty() {
+            network_lines.push(Line::from(Span::styled(
+                "No active network traffic",
+                Style::default().fg(theme().muted)
+            )));
+        }
+    }
+    
+    let paragraph = Paragraph::new(network_lines)
+        .block(
+            Block::default()
+                .title(" 🌐 Network (Real-time Speed) ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().accent))
+        )
+        .style(Style::default().fg(theme().text));
+    
+    f.render_widget(paragraph, area);
+}
+
+// 格式化网络速度显示
+fn format_speed(bytes_per_sec: u64) -> String {
+    let speed = bytes_per_sec as f64;
+    
+    if speed >= 1024.0 * 1024.0 * 1024.0 {
+        // GB/s
+        format!("{:.2} GB/s", speed / 1024.0 / 1024.0 / 1024.0)
+    } else if speed >= 1024.0 * 1024.0 {
+        // MB/s
+        format!("{:.2} MB/s", speed / 1024.0 / 1024.0)
+    } else if speed >= 1024.0 {
+        // KB/s
+        format!("{:.1} KB/s", speed / 1024.0)
+    } else {
+        // B/s
+        format!("{} B/s", speed as u64)
+    }
+}
+
+// 渲染磁盘信息
+fn render_disk_info(f: &mut Frame, disks: &[DiskSnapshot], area: Rect) {
+    // 快照中的磁盘已按盘符排序
+    let mut disk_lines = Vec::new();
+    
+    for disk in disks {
+        let total_space = disk.total as f64 / 1024.0 / 1024.0 / 1024.0;
+        let available_space = disk.available as f64 / 1024.0 / 1024.0 / 1024.0;
+        let used_space = total_space - available_space;
+        let usage_percent = (used_space / total_space) * 100.0;
+        
+        let mount_point = &disk.mount_point;
+        let bar_width = 20;
+        let filled = ((usage_percent / 100.0) * bar_width as f64) as usize;
+        let bar = "█".repeat(filled) + &"░".repeat(bar_width - filled);
+        
+        let color = theme().level(usage_percent, 70.0, 90.0);
+        
+        disk_lines.push(Line::from(vec![
+            Span::raw(format!("{:<8}", mount_point)),
+            Span::styled(bar, Style::default().fg(color)),
+            Span::raw(format!(" {:.0}%", usage_percent)),
+        ]));
+        
+        disk_lines.push(Line::from(format!(
+            "        {:.1}/{:.1} GB",
+            used_space, total_space
+        )));
+    }
+    
+    let paragraph = Paragraph::new(disk_lines)
+        .block(
+            Block::default()
+                .title(" 💿 Disks ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().accent))
+        )
+        .style(Style::default().fg(theme().text));
+    
+    f.render_widget(paragraph, area);
+}
+
+// 温度颜色：超过临界值或 80°C 为红色，超过 60°C 为黄色
+fn temperature_color(temp: f32, critical: Option<f32>) -> Color {
+    if critical.is_some_and(|c| temp >= c) {
+        theme().critical
+    } else {
+        theme().level(temp as f64, 60.0, 80.0)
+    }
+}
+
+fn format_temperature(temp: Option<f32>) -> String {
+    match temp {
+        Some(t) => format!("{:.1}°C", t),
+        None => "-".to_string(),
+    }
+}
+
+// 渲染温度传感器概览（按当前温度从高到低显示）
+fn render_sensor_summary(f: &mut Frame, sensors: &[SensorReading], area: Rect) {
+    let mut info_lines = Vec::new();
+
+    if sensors.is_empty() {
+        if is_admin() {
+            info_lines.push(Line::from(Span::styled(
+                "🌡️  No sensors detected",
+                Style::default().fg(theme().warn)
+            )));
+        } else {
+            info_lines.push(Line::from(Span::styled(
+                "⚠️  Admin rights required",
+                Style::default().fg(theme().critical)
+            )));
+            info_lines.push(Line::from(Span::styled(
+                "   for temperature monitoring",
+                Style::default().fg(theme().muted)
+            )));
+        }
+    } else {
+        let mut readings = sensors.to_vec();
+        readings.sort_by(|a, b| {
+            b.current
+                .unwrap_or(f32::MIN)
+                .total_cmp(&a.current.unwrap_or(f32::MIN))
+        });
+
+        let visible_rows = area.height.saturating_sub(2) as usize;
+        for reading in readings.iter().take(visible_rows) {
+            let display_name = if reading.name.chars().count() > 18 {
+                format!("{}...", reading.name.chars().take(15).collect::<String>())
+            } else {
+                reading.name.clone()
+            };
+            let color = reading
+                .current
+                .map(|t| temperature_color(t, reading.critical))
+                .unwrap_or(theme().muted);
+
+            info_lines.push(Line::from(vec![
+                Span::raw(format!("{:<20}", display_name)),
+                Span::styled(
+                    format_temperature(reading.current),
+                    Style::default().fg(color).add_modifier(Modifier::BOLD)
+                ),
+            ]));
+        }
+    }
+
+    let paragraph = Paragraph::new(info_lines)
+        .block(
+            Block::default()
+                .title(" 🌡️  Temperature ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().accent))
+        )
+        .style(Style::default().fg(theme().text));
+
+    f.render_widget(paragraph, area);
+}
+
+// 渲染传感器详情视图（所有传感器，含会话最低/最高、硬件最高和临界温度）
+pub fn render_sensor_detail(f: &mut Frame, readings: &[SensorReading], state: &MonitorState) -> MouseTargets {
+    let main_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),  // 标题
+            Constraint::Min(0),     // 传感器表格
+            Constraint::Length(3),  // 底部提示
+        ])
+        .split(f.area());
+
+    let header = Paragraph::new("🌡️  Sensors")
+        .style(Style::default().fg(theme().accent).add_modifier(Modifier::BOLD))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(header, main_chunks[0]);
+
+    let mut rows = Vec::new();
+    let mut current_group: Option<&str> = None;
+
+    for reading in readings {
+        // 每个分组前插入一行分组标题
+        if current_group != Some(reading.group.as_str()) {
+            current_group = Some(reading.group.as_str());
+            rows.push(Row::new(vec![Cell::from(Span::styled(
+                reading.group.clone(),
+                Style::default().fg(theme().accent).add_modifier(Modifier::BOLD),
+            ))]));
+        }
+
+        let color = reading
+            .current
+            .map(|t| temperature_color(t, reading.critical))
+            .unwrap_or(theme().muted);
+
+        // 显示原始标签，方便在 config.toml 中配置别名
+        let name = if reading.name == reading.label {
+            format!("  {}", reading.name)
+        } else {
+            format!("  {} [{}]", reading.name, reading.label)
+        };
+
+        rows.push(Row::new(vec![
+            Cell::from(name),
+            Cell::from(Span::styled(
+                format_temperature(reading.current),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            )),
+            Cell::from(format_temperature(reading.session_min)),
+            Cell::from(format_temperature(reading.session_max)),
+            Cell::from(format_temperature(reading.max)),
+            Cell::from(format_temperature(reading.critical)),
+        ]));
+    }
+
+    if readings.is_empty() {
+        let message = if is_admin() {
+            "No sensors detected"
+        } else {
+            "Admin rights required for temperature monitoring"
+        };
+        rows.push(Row::new(vec![Cell::from(Span::styled(
+            message,
+            Style::default().fg(theme().warn),
+        ))]));
+    }
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Min(24),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(10),
+        ],
+    )
+    .header(
+        Row::new(vec!["Sensor", "Current", "Min", "Max", "HW Max", "Critical"])
+            .style(Style::default().fg(theme().title).add_modifier(Modifier::BOLD)),
+    )
+    .block(
+        Block::default()
+            .title(format!(" {} sensors (min/max since monitor start) ", readings.len()))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme().accent)),
+    )
+    .style(Style::default().fg(theme().text));
+    f.render_widget(table, main_chunks[1]);
+
+    render_monitor_footer(f, main_chunks[2], "T: Back to overview | Names/groups: [sensors] in config.toml", state);
+
+    MouseTargets::default()
+}
+
+// 渲染系统基本信息
+fn render_system_info(f: &mut Frame, report: &SystemReport, area: Rect) {
+    let unknown = || "Unknown".to_string();
+    let label = |text: &str| Span::styled(format!("{:<8}", text), Style::default().fg(theme().muted));
+
+    let mut host_line = vec![
+        label("Host:"),
+        Span::raw(report.hostname.clone().unwrap_or_else(unknown)),
+    ];
+    if !report.users.is_empty() {
+        host_line.push(Span::styled(
+            format!("  ({})", report.users_display()),
+            Style::default().fg(theme().muted),
+        ));
+    }
+
+    let mut info_lines = vec![
+        Line::from(host_line),
+        Line::from(vec![label("OS:"), Span::raw(report.os_display())]),
+        Line::from(vec![
+            label("Kernel:"),
+            Span::raw(format!(
+                "{} ({})",
+                report.kernel_version.clone().unwrap_or_else(unknown),
+                report.arch
+            )),
+        ]),
+        Line::from(vec![
+            label("Boot:"),
+            Span::raw(format!("{}  up {}", report.boot_time_display(), report.uptime_display())),
+        ]),
+        Line::from(vec![
+            label("Cores:"),
+            Span::raw(format!(
+                "{} physical, {} logical | {} processes",
+                report.physical_cores.unwrap_or(0),
+                report.logical_cores,
+                report.process_count
+            )),
+        ]),
+    ];
+    if let Some(load) = report.load_display() {
+        info_lines.push(Line::from(vec![label("Load:"), Span::raw(load)]));
+    }
+    info_lines.push(Line::from(vec![
+        label("lemo:"),
+        Span::raw(format!("v{}", report.lemo_version)),
+    ]));
+
+    let paragraph = Paragraph::new(info_lines)
+        .block(
+            Block::default()
+                .title(" ℹ️  System Info ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().accent))
+        )
+        .style(Style::default().fg(theme().text));
+
+    f.render_widget(paragraph, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    fn screen(terminal: &Terminal<TestBackend>) -> String {
+        let buffer = terminal.backend().buffer();
+        let width = buffer.area.width as usize;
+        buffer
+            .content
+            .chunks(width)
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn renders_overview_from_snapshot() {
+        let snapshot = Snapshot::sample();
+        let state = MonitorState::new();
+        let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+        let mut targets = MouseTargets::default();
+        terminal
+            .draw(|f| targets = render_monitor_ui(f, &snapshot, &state))
+            .unwrap();
+
+        let text = screen(&terminal);
+        for expected in [
+            "Real-time System Monitor",
+            "CPU: Test CPU 8-Core",
+            "42.5%",
+            "Memory: 8.0/16.0 GB",
+            "/data",
+            "eth0",
+            "fixture-host",
+            "No battery",
+        ] {
+            assert!(text.contains(expected), "missing {:?} in:\n{}", expected, text);
+        }
+
+        // 七个面板铺满内容区域：左右各占一半，标题和底部提示各 3 行
+        assert_eq!(targets.panels.len(), 7);
+        let area = |panel| targets.panels.iter().find(|(p, _)| *p == panel).unwrap().1;
+        assert_eq!(area(Panel::Cpu), Rect::new(0, 3, 60, 3));
+        assert_eq!(area(Panel::System), Rect::new(60, 3, 60, 9));
+        assert_eq!(area(Panel::Disks).bottom(), 37);
+    }
+
+    #[test]
+    fn maximized_panel_fills_content_area() {
+        let snapshot = Snapshot::sample();
+        let mut state = MonitorState::new();
+        state.maximized = Some(Panel::Disks);
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        let mut targets = MouseTargets::default();
+        terminal
+            .draw(|f| targets = render_monitor_ui(f, &snapshot, &state))
+            .unwrap();
+
+        assert_eq!(targets.panels.len(), 1);
+        assert_eq!(targets.panels[0].1, Rect::new(0, 3, 80, 14));
+        let text = screen(&terminal);
+        assert!(text.contains("/data"));
+        assert!(!text.contains("eth0"));
+    }
+
+    #[test]
+    fn footer_shows_lost_connection() {
+        let snapshot = Snapshot::sample();
+        let mut state = MonitorState::new();
+        state.disconnected = Some("host:7878: Connection closed by agent".to_string());
+        let mut terminal = Terminal::new(TestBackend::new(160, 40)).unwrap();
+        terminal
+            .draw(|f| {
+                render_monitor_ui(f, &snapshot, &state);
+            })
+            .unwrap();
+        let text = screen(&terminal);
+        assert!(text.contains("Disconnected: host:7878: Connection closed by agent"), "{}", text);
+        assert!(!text.contains("Refresh:"));
+    }
+}
@@ -0,0 +1,270 @@
+// Monitor data sources
+//
+// 渲染层只消费 Snapshot，数据从哪里来（实时采集、快照文件、录制回放、远程 agent）由 MetricsSource 决定
+use crate::config;
+use crate::recording::{Recording, ReplayPlayer};
+use crate::remote::{RemoteClient, RemoteEvent};
+use crate::snapshot::{LiveCollector, Snapshot};
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// 等待远程 agent 第一份快照的最长时间（agent 采样间隔最长 10 秒）
+const REMOTE_FIRST_TIMEOUT: Duration = Duration::from_secs(15);
+
+pub trait MetricsSource {
+    // 监控界面标题
+    fn title(&self) -> String;
+
+    // 第一份快照（界面打开时立即显示）
+    fn first(&mut self) -> Result<Snapshot>;
+
+    // 非阻塞获取新快照，没有新数据时返回 None
+    fn poll(&mut self) -> Result<Option<Snapshot>>;
+
+    fn set_interval(&mut self, _interval: Duration) {}
+
+    fn set_paused(&mut self, _paused: bool) {}
+
+    // 回放来源提供播放控制
+    fn playback(&mut self) -> Option<&mut ReplayPlayer> {
+        None
+    }
+}
+
+// 采集线程的控制消息
+enum Control {
+    Interval(Duration),
+    Paused(bool),
+}
+
+// 实时采集：在后台线程中刷新 sysinfo，渲染循环不会被采集阻塞
+pub struct LiveSource {
+    first: Option<Snapshot>,
+    snapshots: Receiver<Snapshot>,
+    control: Sender<Control>,
+}
+
+impl LiveSource {
+    pub fn start(interval: Duration) -> Self {
+        // 配置文件有误时仍然可以使用监控，只是没有自定义传感器名称
+        let config = config::Config::load().unwrap_or_default();
+        let mut collector = LiveCollector::new(config.sensors);
+        let first = collector.snapshot();
+
+        let (snapshot_tx, snapshot_rx) = mpsc::channel();
+        let (control_tx, control_rx) = mpsc::channel();
+        thread::spawn(move || {
+            let mut interval = interval;
+            let mut paused = false;
+            let mut last_sample = Instant::now();
+            loop {
+                match control_rx.recv_timeout(interval.saturating_sub(last_sample.elapsed())) {
+                    Ok(Control::Interval(new_interval)) => interval = new_interval,
+                    Ok(Control::Paused(new_paused)) => paused = new_paused,
+                    Err(RecvTimeoutError::Timeout) => {
+                        // 暂停时保持当前读数不变
+                        if !paused && snapshot_tx.send(collector.sample()).is_err() {
+                            break;
+                        }
+                        last_sample = Instant::now();
+                    }
+                    // 监控界面已关闭
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+            }
+        });
+
+        Self {
+            first: Some(first),
+            snapshots: snapshot_rx,
+            control: control_tx,
+        }
+    }
+}
+
+impl MetricsSource for LiveSource {
+    fn title(&self) -> String {
+        "📊 Real-time System Monitor".to_string()
+    }
+
+    fn first(&mut self) -> Result<Snapshot> {
+        self.first
+            .take()
+            .ok_or_else(|| anyhow::anyhow!("Live source already started"))
+    }
+
+    fn poll(&mut self) -> Result<Option<Snapshot>> {
+        // 只保留最新的一份，渲染落后时丢弃中间的采样
+        let mut latest = None;
+        loop {
+            match self.snapshots.try_recv() {
+                Ok(snapshot) => latest = Some(snapshot),
+                Err(TryRecvError::Empty) => return Ok(latest),
+                Err(TryRecvError::Disconnected) => {
+                    return Err(anyhow::anyhow!("Metrics collector stopped unexpectedly"))
+                }
+            }
+        }
+    }
+
+    fn set_interval(&mut self, interval: Duration) {
+        let _ = self.control.send(Control::Interval(interval));
+    }
+
+    fn set_paused(&mut self, paused: bool) {
+        let _ = self.control.send(Control::Paused(paused));
+    }
+}
+
+// 固定快照：读取 `s` 键保存的 .json 快照文件，用于复现问题和界面测试
+pub struct FixtureSource {
+    path: PathBuf,
+    snapshot: Snapshot,
+}
+
+impl FixtureSource {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path).with_context(|| format!("Cannot open {}", path.display()))?;
+        let snapshot = serde_json::from_str(&content)
+            .with_context(|| format!("{} is not a lemo snapshot", path.display()))?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            snapshot,
+        })
+    }
+}
+
+impl MetricsSource for FixtureSource {
+    fn title(&self) -> String {
+        format!("📄 Snapshot: {}", self.path.display())
+    }
+
+    fn first(&mut self) -> Result<Snapshot> {
+        Ok(self.snapshot.clone())
+    }
+
+    fn poll(&mut self) -> Result<Option<Snapshot>> {
+        Ok(None)
+    }
+}
+
+// 回放录制文件（.lemorec）
+pub struct ReplaySource {
+    player: ReplayPlayer,
+}
+
+impl ReplaySource {
+    pub fn load(path: &Path) -> Result<Self> {
+        Ok(Self {
+            player: ReplayPlayer::new(Recording::load(path)?),
+        })
+    }
+}
+
+impl MetricsSource for ReplaySource {
+    fn title(&self) -> String {
+        let header = self.player.header();
+        format!(
+            "📼 Replay: {} ({})",
+            header.hostname.as_deref().unwrap_or("unknown host"),
+            header.started
+        )
+    }
+
+    fn first(&mut self) -> Result<Snapshot> {
+        Ok(self.player.current().clone())
+    }
+
+    fn poll(&mut self) -> Result<Option<Snapshot>> {
+        Ok(self.player.tick().then(|| self.player.current().clone()))
+    }
+
+    fn playback(&mut self) -> Option<&mut ReplayPlayer> {
+        Some(&mut self.player)
+    }
+}
+
+// 远程 agent（`lemo agent`）推送的快照
+pub struct RemoteSource {
+    client: RemoteClient,
+    hostname: Option<String>,
+}
+
+impl RemoteSource {
+    pub fn connect(address: &str, token: &str) -> Self {
+        Self {
+            client: RemoteClient::connect(address, token),
+            hostname: None,
+        }
+    }
+
+    fn handle(&mut self, event: RemoteEvent) -> Result<Option<Snapshot>> {
+        match event {
+            RemoteEvent::Connected { hostname } => {
+                self.hostname = hostname;
+                Ok(None)
+            }
+            RemoteEvent::Snapshot(snapshot) => Ok(Some(*snapshot)),
+            RemoteEvent::Failed(error) => Err(anyhow::anyhow!("{}: {}", self.client.address(), error)),
+        }
+    }
+}
+
+impl MetricsSource for RemoteSource {
+    fn title(&self) -> String {
+        match &self.hostname {
+            Some(hostname) => format!("🌐 Remote: {} ({})", hostname, self.client.address()),
+            None => format!("🌐 Remote: {}", self.client.address()),
+        }
+    }
+
+    fn first(&mut self) -> Result<Snapshot> {
+        // 握手后 agent 在一个采样间隔内推送第一份快照
+        while let Some(event) = self.client.wait(REMOTE_FIRST_TIMEOUT) {
+            if let Some(snapshot) = self.handle(event)? {
+                return Ok(snapshot);
+            }
+        }
+        Err(anyhow::anyhow!("No data from {}", self.client.address()))
+    }
+
+    fn poll(&mut self) -> Result<Option<Snapshot>> {
+        let mut latest = None;
+        for event in self.client.drain() {
+            if let Some(snapshot) = self.handle(event)? {
+                latest = Some(snapshot);
+            }
+        }
+        Ok(latest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixture_source_round_trip() {
+        let path = std::env::temp_dir().join(format!("lemo-fixture-{}.json", std::process::id()));
+        let snapshot = Snapshot::sample();
+        fs::write(&path, serde_json::to_string_pretty(&snapshot).unwrap()).unwrap();
+
+        let mut source = FixtureSource::load(&path).unwrap();
+        assert!(source.title().contains(&path.display().to_string()));
+        let loaded = source.first().unwrap();
+        assert_eq!(
+            serde_json::to_value(&loaded).unwrap(),
+            serde_json::to_value(&snapshot).unwrap()
+        );
+        // 固定快照不会产生新数据
+        assert!(source.poll().unwrap().is_none());
+
+        fs::write(&path, "not json").unwrap();
+        assert!(FixtureSource::load(&path).is_err());
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,400 @@
+// Disk-space policies: clean only when a drive is running low on free space
+//
+// 策略保存在 <配置目录>/policies.toml：
+//
+// [[policy]]
+// id = 1
+// drive = "C:\\"         # 省略时为系统盘
+// below_gb = 15.0        # 可用空间低于该值时触发
+// until_gb = 25.0        # 清理到可用空间达到该值为止
+// targets = ["clean-temp", "clean-dev-caches", "empty-trash"]
+// older_than = "7d"
+//
+// 按 targets 的顺序依次清理，每个目录从最旧的文件开始删除，释放够空间后停止。
+// `lemo daemon` 每个检查周期评估一次，`lemo policy check` 立即评估并执行。
+use crate::config;
+use crate::history::format_bytes;
+use crate::schedule::{self, ScheduleState};
+use crate::snapshot::{read_disks, DiskSnapshot};
+use crate::task;
+use crate::utils::{clean_until_freed_task, format_age, parse_age, run_until_freed, CleanOptions, SCHEDULABLE_TASKS};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+const GB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+// 同一策略两次执行之间至少间隔（清理后空间仍不足时避免每个检查周期重复扫描）
+const COOLDOWN_MINUTES: i64 = 60;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Policy {
+    pub id: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub drive: Option<String>,
+    pub below_gb: f64,
+    pub until_gb: f64,
+    pub targets: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub older_than: Option<String>,
+    #[serde(default)]
+    pub include_user: bool,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PolicyFile {
+    #[serde(default)]
+    policy: Vec<Policy>,
+}
+
+// 系统盘的挂载点
+pub fn system_drive() -> String {
+    if cfg!(windows) {
+        format!("{}\\", env::var("SystemDrive").unwrap_or_else(|_| "C:".to_string()))
+    } else {
+        "/".to_string()
+    }
+}
+
+// "C:"、"c:\" 和 "C:\" 是同一个盘；"/" 与 "/home/" 去掉末尾分隔符后比较
+pub fn same_mount(a: &str, b: &str) -> bool {
+    let normalize = |s: &str| {
+        let trimmed = s.trim_end_matches(['\\', '/']);
+        if cfg!(windows) {
+            trimmed.to_lowercase()
+        } else {
+            trimmed.to_string()
+        }
+    };
+    normalize(a) == normalize(b)
+}
+
+pub fn find_disk(drive: &str) -> Option<DiskSnapshot> {
+    read_disks().into_iter().find(|disk| same_mount(&disk.mount_point, drive))
+}
+
+impl Policy {
+    pub fn drive(&self) -> String {
+        self.drive.clone().unwrap_or_else(system_drive)
+    }
+
+    // 可用空间低于阈值时返回需要释放的字节数
+    pub fn needed_bytes(&self, disk: &DiskSnapshot) -> Option<u64> {
+        let below = (self.below_gb * GB) as u64;
+        let until = (self.until_gb * GB) as u64;
+        (disk.available < below).then(|| until.saturating_sub(disk.available))
+    }
+
+    // 只删除策略驱动器上的文件：其他驱动器释放的空间不计入 max_bytes
+    pub fn clean_options(&self, max_bytes: u64) -> Result<CleanOptions> {
+        Ok(CleanOptions {
+            include_user: self.include_user,
+            older_than: self.older_than.as_deref().map(parse_age).transpose()?,
+            max_bytes: Some(max_bytes),
+            drive: Some(self.drive()),
+            ..Default::default()
+        })
+    }
+
+    // 清理结果：可用空间仍低于阈值时不算成功
+    pub fn outcome(&self, before: u64, after: Option<&DiskSnapshot>) -> String {
+        let icon = match after {
+            Some(disk) if self.needed_bytes(disk).is_none() => "✅",
+            _ => "⚠️",
+        };
+        let mut summary = format!("{} {} → {}", icon, format_bytes(before), free_display(after));
+        if after.is_some_and(|disk| self.needed_bytes(disk).is_some()) {
+            summary.push_str(&format!(" (still below {} GB)", self.below_gb));
+        }
+        summary
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.targets.is_empty() {
+            return Err(anyhow::anyhow!("A policy needs at least one target"));
+        }
+        if let Some(task) = self.targets.iter().find(|t| !SCHEDULABLE_TASKS.contains(&t.as_str())) {
+            return Err(anyhow::anyhow!(
+                "Unknown target '{}' (available: {})",
+                task,
+                SCHEDULABLE_TASKS.join(", ")
+            ));
+        }
+        // NaN 与任何值比较都为 false，需要单独排除
+        if !self.below_gb.is_finite() || !self.until_gb.is_finite() {
+            return Err(anyhow::anyhow!(
+                "Invalid thresholds: --below ({}) and --until ({}) must be numbers of GB",
+                self.below_gb,
+                self.until_gb
+            ));
+        }
+        if self.below_gb <= 0.0 || self.until_gb < self.below_gb {
+            return Err(anyhow::anyhow!(
+                "Invalid thresholds: --until ({} GB) must be at least --below ({} GB)",
+                self.until_gb,
+                self.below_gb
+            ));
+        }
+        self.clean_options(0)?;
+        Ok(())
+    }
+
+    pub fn rule_display(&self) -> String {
+        format!(
+            "{} below {} GB → until {} GB",
+            self.drive(),
+            self.below_gb,
+            self.until_gb
+        )
+    }
+
+    pub fn options_display(&self) -> String {
+        let mut parts = vec![self.targets.join(", ")];
+        if let Some(age) = &self.older_than {
+            parts.push(format!("older than {}", age));
+        }
+        if self.include_user {
+            parts.push("include user".to_string());
+        }
+        parts.join("; ")
+    }
+
+    // 上次执行后是否已过冷却时间
+    pub fn cooled_down(&self, now: DateTime<Local>, state: Option<&ScheduleState>) -> bool {
+        state
+            .and_then(|s| s.last_run.as_deref())
+            .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+            .is_none_or(|last| now.signed_duration_since(last) >= chrono::Duration::minutes(COOLDOWN_MINUTES))
+    }
+}
+
+pub fn policies_path() -> Option<PathBuf> {
+    config::config_dir().map(|dir| dir.join("policies.toml"))
+}
+
+fn state_path() -> Option<PathBuf> {
+    config::data_dir().map(|dir| dir.join("policy-state.json"))
+}
+
+pub fn load() -> Result<Vec<Policy>> {
+    let path = match policies_path() {
+        Some(p) if p.exists() => p,
+        _ => return Ok(Vec::new()),
+    };
+    let content = fs::read_to_string(&path).with_context(|| format!("Cannot read {}", path.display()))?;
+    let file: PolicyFile =
+        toml::from_str(&content).with_context(|| format!("Invalid policy file {}", path.display()))?;
+    Ok(file.policy)
+}
+
+fn save(policies: &[Policy]) -> Result<()> {
+    let path = policies_path().ok_or_else(|| anyhow::anyhow!("Cannot determine config directory"))?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("Cannot create {}", dir.display()))?;
+    }
+    let content = toml::to_string(&PolicyFile {
+        policy: policies.to_vec(),
+    })?;
+    fs::write(&path, content).with_context(|| format!("Cannot write {}", path.display()))
+}
+
+pub fn load_state() -> BTreeMap<u32, ScheduleState> {
+    schedule::read_state_file(state_path())
+}
+
+pub fn save_state(state: &BTreeMap<u32, ScheduleState>) -> Result<()> {
+    schedule::write_state_file(state_path(), state)
+}
+
+// 新策略参数（来自 `lemo policy add`）
+pub struct NewPolicy {
+    pub drive: Option<String>,
+    pub below_gb: f64,
+    pub until_gb: f64,
+    pub targets: Vec<String>,
+    pub older_than: Option<Duration>,
+    pub include_user: bool,
+}
+
+pub fn add(new: NewPolicy) -> Result<Policy> {
+    let mut policies = load()?;
+    let policy = Policy {
+        id: policies.iter().map(|p| p.id).max().unwrap_or(0) + 1,
+        drive: new.drive,
+        below_gb: new.below_gb,
+        until_gb: new.until_gb,
+        targets: new.targets,
+        older_than: new.older_than.map(format_age),
+        include_user: new.include_user,
+    };
+    policy.validate()?;
+    policies.push(policy.clone());
+    save(&policies)?;
+    schedule::forget_state(state_path(), policy.id)?;
+    Ok(policy)
+}
+
+pub fn remove(id: u32) -> Result<()> {
+    let mut policies = load()?;
+    let before = policies.len();
+    policies.retain(|p| p.id != id);
+    if policies.len() == before {
+        return Err(anyhow::anyhow!("No policy with id {}", id));
+    }
+    save(&policies)?;
+    schedule::forget_state(state_path(), id)
+}
+
+fn free_display(disk: Option<&DiskSnapshot>) -> String {
+    match disk {
+        Some(disk) => format!("{} free", format_bytes(disk.available)),
+        None => "drive not found".to_string(),
+    }
+}
+
+// 无人值守执行策略（daemon）：返回输出行和结果摘要（含清理前后的可用空间）
+pub fn enforce(policy: &Policy, disk: &DiskSnapshot, needed: u64) -> (Vec<String>, Result<String>) {
+    let options = match policy.clean_options(needed) {
+        Ok(options) => options,
+        Err(e) => return (Vec::new(), Err(e)),
+    };
+    let (lines, result) = run_until_freed(&policy.targets, &options);
+    let after = find_disk(&policy.drive());
+    (lines, result.map(|()| policy.outcome(disk.available, after.as_ref())))
+}
+
+// lemo policy list
+pub fn print_policies() -> Result<()> {
+    let policies = load()?;
+    if policies.is_empty() {
+        println!(
+            "No policies. Add one with `lemo policy add --below 15 --until 25 --targets clean-temp,clean-dev-caches`."
+        );
+        return Ok(());
+    }
+
+    let state = load_state();
+    println!(
+        "{:<4} {:<32} {:<17} {:<10} {:<17} Last result",
+        "ID", "Rule", "Free now", "Status", "Last run"
+    );
+    for policy in &policies {
+        let disk = find_disk(&policy.drive());
+        let status = match disk.as_ref().map(|d| policy.needed_bytes(d)) {
+            Some(Some(_)) => "⚠️ Low",
+            Some(None) => "✅ OK",
+            None => "-",
+        };
+        let entry = state.get(&policy.id);
+        println!(
+            "{:<4} {:<32} {:<17} {:<10} {:<17} {}",
+            policy.id,
+            policy.rule_display(),
+            free_display(disk.as_ref()),
+            status,
+            entry
+                .and_then(|s| s.last_run.as_deref())
+                .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+                .map(|t| t.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_else(|| "-".to_string()),
+            entry.and_then(|s| s.last_result.as_deref()).unwrap_or("-")
+        );
+        println!("     targets: {}", policy.options_display());
+    }
+    println!();
+    println!("Policies are checked while `lemo daemon` is running, or now with `lemo policy check`.");
+    Ok(())
+}
+
+// lemo policy check：立即评估所有策略，可用空间不足的立即清理（忽略冷却时间）
+pub fn check_policies(dry_run: bool) -> Result<()> {
+    let policies = load()?;
+    if policies.is_empty() {
+        println!("No policies configured.");
+        return Ok(());
+    }
+
+    let mut state = load_state();
+    for policy in &policies {
+        println!("#{} {}", policy.id, policy.rule_display());
+        let Some(disk) = find_disk(&policy.drive()) else {
+            println!("   ⚠️ Drive {} not found", policy.drive());
+            continue;
+        };
+        let Some(needed) = policy.needed_bytes(&disk) else {
+            println!("   ✅ {} free, nothing to do", format_bytes(disk.available));
+            continue;
+        };
+        println!(
+            "   ⚠️ {} free, need to free {} ({})",
+            format_bytes(disk.available),
+            format_bytes(needed),
+            policy.options_display()
+        );
+        if dry_run {
+            continue;
+        }
+
+        let result = task::run_console(clean_until_freed_task(policy.targets.clone(), policy.clean_options(needed)?));
+        let after = find_disk(&policy.drive());
+        let summary = match &result {
+            Ok(()) => policy.outcome(disk.available, after.as_ref()),
+            Err(e) => format!("❌ {:#}", e),
+        };
+        println!("   {}", summary);
+        let entry = state.entry(policy.id).or_default();
+        entry.last_run = Some(Local::now().to_rfc3339());
+        entry.last_result = Some(summary);
+    }
+    save_state(&state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(below_gb: f64, until_gb: f64) -> Policy {
+        Policy {
+            id: 1,
+            drive: None,
+            below_gb,
+            until_gb,
+            targets: vec!["clean-temp".to_string()],
+            older_than: None,
+            include_user: false,
+        }
+    }
+
+    #[test]
+    fn validates_thresholds() {
+        assert!(policy(10.0, 20.0).validate().is_ok());
+        assert!(policy(10.0, 10.0).validate().is_ok());
+        for (below, until) in [
+            (0.0, 10.0),
+            (-1.0, 10.0),
+            (20.0, 10.0),
+            (f64::NAN, 10.0),
+            (10.0, f64::NAN),
+            (f64::NAN, f64::NAN),
+            (10.0, f64::INFINITY),
+            (f64::NEG_INFINITY, 10.0),
+        ] {
+            assert!(policy(below, until).validate().is_err(), "{} → {}", below, until);
+        }
+    }
+
+    #[test]
+    fn validates_targets() {
+        let mut unknown = policy(10.0, 20.0);
+        unknown.targets = vec!["clean-everything".to_string()];
+        assert!(unknown.validate().is_err());
+        let mut empty = policy(10.0, 20.0);
+        empty.targets.clear();
+        assert!(empty.validate().is_err());
+    }
+}
@@ -0,0 +1,331 @@
+// Cleaning profiles: named presets selecting clean-temp targets and options
+//
+// 内置 standard / safe / aggressive / privacy，用户可在 <配置目录>/profiles.toml 中添加或覆盖：
+//
+// [[profile]]
+// name = "nightly"
+// description = "Temp folders only, keep the last 3 days"
+// targets = ["windows-temp", "user-temp", "user-local-temp"]
+// older_than = "3d"
+use crate::apps;
+use crate::config;
+use crate::theme::{self, theme};
+use crate::utils::{canonical_target, parse_age, validate_targets, CleanOptions, CLEAN_TARGETS};
+use anyhow::{Context, Result};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::{
+    backend::Backend,
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
+    Frame, Terminal,
+};
+use serde::Deserialize;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Profile {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub targets: Vec<String>,
+    #[serde(default)]
+    pub older_than: Option<String>,
+    // 内置配置（不从 profiles.toml 读取）
+    #[serde(skip)]
+    pub builtin: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ProfileFile {
+    #[serde(default)]
+    profile: Vec<Profile>,
+}
+
+fn builtin(name: &str, description: &str, targets: &[&str], older_than: Option<&str>) -> Profile {
+    Profile {
+        name: name.to_string(),
+        description: description.to_string(),
+        targets: targets.iter().map(|t| t.to_string()).collect(),
+        older_than: older_than.map(|age| age.to_string()),
+        builtin: true,
+    }
+}
+
+pub fn builtin_profiles() -> Vec<Profile> {
+    // 与 clean-temp 的默认目标一致：Linux 的 Trash 不在其中（见 recycle-bin 目标）
+    let standard: Vec<&str> = ["windows-temp", "prefetch", "windows-bak", "recycle-bin", "system-drive-temp"]
+        .into_iter()
+        .filter(|id| cfg!(windows) || *id != "recycle-bin")
+        .collect();
+    vec![
+        builtin(
+            "standard",
+            "System temp locations, the same as `lemo clean-temp` without options",
+            &standard,
+            None,
+        ),
+        builtin(
+            "safe",
+            "Temp folders only, skipping anything modified in the last 2 days",
+            &["windows-temp", "user-temp", "user-local-temp"],
+            Some("2d"),
+        ),
+        builtin(
+            "aggressive",
+            "Every temp and cache location, including the recycle bin and a full system drive scan",
+            &[
+                "windows-temp",
+                "prefetch",
+                "windows-bak",
+                "recycle-bin",
+                "system-drive-temp",
+                "windows-update-cache",
+                "delivery-optimization",
+                "user-temp",
+                "user-local-temp",
+                "browser-cache",
+                "browser-code-cache",
+                "browser-gpu-cache",
+                "browser-service-workers",
+                "crash-dumps",
+                "error-reports",
+                "cbs-logs",
+                "jetbrains-caches",
+                "vscode-caches",
+                "teams-cache",
+                "slack-cache",
+            ],
+            None,
+        ),
+        builtin(
+            "privacy",
+            "Browsing and usage traces: cookies, recent items and browser cookies and history",
+            &["cookies", "recent", "browser-cookies", "browser-history"],
+            None,
+        ),
+    ]
+}
+
+impl Profile {
+    // older_than 覆盖配置中的时间（命令行 --older-than）
+    pub fn clean_options(&self, older_than: Option<Duration>) -> Result<CleanOptions> {
+        let profile_age = self.older_than.as_deref().map(parse_age).transpose()?;
+        Ok(CleanOptions {
+            profile: Some(self.name.clone()),
+            targets: self.targets.clone(),
+            older_than: older_than.or(profile_age),
+            ..Default::default()
+        })
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.targets.is_empty() {
+            return Err(anyhow::anyhow!("Profile '{}' has no targets", self.name));
+        }
+        validate_targets(&self.targets).with_context(|| format!("Profile '{}'", self.name))?;
+        self.clean_options(None)?;
+        Ok(())
+    }
+
+    pub fn has_privacy_targets(&self) -> bool {
+        CLEAN_TARGETS
+            .iter()
+            .any(|t| t.privacy && self.targets.iter().any(|id| canonical_target(id) == t.id))
+    }
+}
+
+pub fn profiles_path() -> Option<PathBuf> {
+    config::config_dir().map(|dir| dir.join("profiles.toml"))
+}
+
+// 内置配置 + 用户配置（同名时用户配置覆盖内置配置）
+pub fn load() -> Result<Vec<Profile>> {
+    let mut profiles = builtin_profiles();
+    let path = match profiles_path() {
+        Some(p) if p.exists() => p,
+        _ => return Ok(profiles),
+    };
+    let content = fs::read_to_string(&path).with_context(|| format!("Cannot read {}", path.display()))?;
+    let file: ProfileFile =
+        toml::from_str(&content).with_context(|| format!("Invalid profile file {}", path.display()))?;
+    for profile in file.profile {
+        profile.validate().with_context(|| format!("Invalid profile file {}", path.display()))?;
+        match profiles.iter_mut().find(|p| p.name == profile.name) {
+            Some(existing) => *existing = profile,
+            None => profiles.push(profile),
+        }
+    }
+    Ok(profiles)
+}
+
+pub fn find(name: &str) -> Result<Profile> {
+    let profiles = load()?;
+    let names: Vec<&str> = profiles.iter().map(|p| p.name.as_str()).collect();
+    profiles
+        .iter()
+        .find(|p| p.name == name)
+        .cloned()
+        .ok_or_else(|| anyhow::anyhow!("Unknown profile '{}' (available: {})", name, names.join(", ")))
+}
+
+// lemo profiles
+pub fn print_profiles() -> Result<()> {
+    for profile in load()? {
+        let source = if profile.builtin { "built-in" } else { "user" };
+        println!("{} ({})", profile.name, source);
+        if !profile.description.is_empty() {
+            println!("   {}", profile.description);
+        }
+        println!("   targets: {}", profile.targets.join(", "));
+        if let Some(age) = &profile.older_than {
+            println!("   older than: {}", age);
+        }
+        println!();
+    }
+
+    println!("Targets:");
+    for (index, target) in CLEAN_TARGETS.iter().enumerate() {
+        if index == 0 || CLEAN_TARGETS[index - 1].group != target.group {
+            println!("  {}", target.group);
+        }
+        let mut note = if target.privacy { " (privacy data)" } else { "" };
+        if apps::find(target.id).is_some_and(|app| !app.installed()) {
+            note = " (not installed)";
+        }
+        println!("   {:<24} {}{}", target.id, target.name, note);
+    }
+    if let Some(path) = profiles_path() {
+        println!();
+        println!("Define your own profiles in {}", path.display());
+    }
+    Ok(())
+}
+
+// 运行前选择清理配置；Esc 返回 None
+pub fn pick_profile<B: Backend>(terminal: &mut Terminal<B>) -> Result<Option<Profile>> {
+    // profiles.toml 有错误时仍可使用内置配置
+    let (profiles, error) = match load() {
+        Ok(profiles) => (profiles, None),
+        Err(e) => (builtin_profiles(), Some(format!("❌ {:#}", e))),
+    };
+    let mut selected = 0;
+
+    loop {
+        terminal.draw(|f| {
+            render_picker(f, &profiles, selected, error.as_deref());
+            theme::finish_frame(f);
+        })?;
+
+        let key = match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => key,
+            _ => continue,
+        };
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(None),
+            KeyCode::Up | KeyCode::Char('k') => selected = selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => selected = (selected + 1).min(profiles.len().saturating_sub(1)),
+            KeyCode::Enter => return Ok(profiles.get(selected).cloned()),
+            _ => {}
+        }
+    }
+}
+
+fn render_picker(f: &mut Frame, profiles: &[Profile], selected: usize, message: Option<&str>) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0), Constraint::Length(3)])
+        .split(f.area());
+
+    let title = Paragraph::new("🧹 Clean Temp Files: choose a profile")
+        .style(Style::default().fg(theme().accent).add_modifier(Modifier::BOLD))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(title, chunks[0]);
+
+    let body = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(35), Constraint::Percentage(65)])
+        .split(chunks[1]);
+
+    let items: Vec<ListItem> = profiles
+        .iter()
+        .enumerate()
+        .map(|(index, profile)| {
+            let style = if index == selected {
+                Style::default()
+                    .fg(theme().selected_fg)
+                    .bg(theme().selected_bg)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            let source = if profile.builtin { "" } else { " (user)" };
+            ListItem::new(Line::from(Span::styled(format!(" {}{}", profile.name, source), style)))
+        })
+        .collect();
+    let list = List::new(items).block(
+        Block::default()
+            .title(" Profiles ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme().accent)),
+    );
+    f.render_widget(list, body[0]);
+
+    let mut lines = Vec::new();
+    if let Some(profile) = profiles.get(selected) {
+        lines.push(Line::from(Span::styled(
+            profile.name.clone(),
+            Style::default().fg(theme().title).add_modifier(Modifier::BOLD),
+        )));
+        lines.push(Line::from(profile.description.clone()));
+        lines.push(Line::from(""));
+        for (index, target) in CLEAN_TARGETS.iter().enumerate() {
+            if index == 0 || CLEAN_TARGETS[index - 1].group != target.group {
+                lines.push(Line::from(Span::styled(
+                    target.group,
+                    Style::default().fg(theme().title),
+                )));
+            }
+            let included = profile.targets.iter().any(|id| canonical_target(id) == target.id);
+            let mark = if included { "[x]" } else { "[ ]" };
+            let note = if target.privacy { "  🔒 privacy data" } else { "" };
+            let style = if included {
+                Style::default()
+            } else {
+                Style::default().fg(theme().muted)
+            };
+            lines.push(Line::from(Span::styled(format!("  {} {}{}", mark, target.name, note), style)));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(format!(
+            "Only items older than: {}",
+            profile.older_than.as_deref().unwrap_or("-")
+        )));
+        if profile.has_privacy_targets() {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                "⚠️ Deletes privacy data: you will be signed out of websites",
+                Style::default().fg(theme().warn),
+            )));
+        }
+    }
+    let details = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .title(" Targets ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme().title)),
+    );
+    f.render_widget(details, body[1]);
+
+    let footer = match message {
+        Some(message) => Paragraph::new(message).style(Style::default().fg(theme().critical)),
+        None => Paragraph::new("↑/↓: Select | Enter: Clean | Q/Esc: Return").style(Style::default().fg(theme().muted)),
+    }
+    .alignment(Alignment::Center)
+    .block(Block::default().borders(Borders::ALL));
+    f.render_widget(footer, chunks[2]);
+}
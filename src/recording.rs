@@ -0,0 +1,435 @@
+// Monitor session recording (.lemorec) and replay
+//
+// 文件格式为 JSON Lines：第一行是文件头，之后每行是一个采样点
+// {"format":"lemorec","version":1,...}
+// {"elapsed_ms":0,"snapshot":{...}}
+use crate::snapshot::Snapshot;
+use anyhow::{Context, Result};
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+const FORMAT_NAME: &str = "lemorec";
+const FORMAT_VERSION: u32 = 1;
+
+// 回放速度档位
+const SPEEDS: [f64; 7] = [0.25, 0.5, 1.0, 2.0, 4.0, 8.0, 16.0];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordingHeader {
+    pub format: String,
+    pub version: u32,
+    pub lemo_version: String,
+    pub started: String,
+    pub hostname: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedSample {
+    // 相对于录制开始的毫秒数
+    pub elapsed_ms: u64,
+    pub snapshot: Snapshot,
+}
+
+// 录制器：每个采样点立即写入并 flush，异常退出时已录制的数据不会丢失
+pub struct Recorder {
+    writer: BufWriter<File>,
+    started: Option<Instant>,
+}
+
+impl Recorder {
+    pub fn create(path: &Path) -> Result<Self> {
+        let file = File::create(path).with_context(|| format!("Cannot create {}", path.display()))?;
+        Ok(Self {
+            writer: BufWriter::new(file),
+            started: None,
+        })
+    }
+
+    pub fn record(&mut self, snapshot: &Snapshot) -> Result<()> {
+        let started = match self.started {
+            Some(started) => started,
+            None => {
+                let header = RecordingHeader {
+                    format: FORMAT_NAME.to_string(),
+                    version: FORMAT_VERSION,
+                    lemo_version: env!("CARGO_PKG_VERSION").to_string(),
+                    started: Local::now().to_rfc3339(),
+                    hostname: snapshot.system.hostname.clone(),
+                };
+                serde_json::to_writer(&mut self.writer, &header)?;
+                self.writer.write_all(b"\n")?;
+                *self.started.insert(Instant::now())
+            }
+        };
+
+        let sample = RecordedSample {
+            elapsed_ms: started.elapsed().as_millis() as u64,
+            snapshot: snapshot.clone(),
+        };
+        serde_json::to_writer(&mut self.writer, &sample)?;
+        self.writer.write_all(b"\n")?;
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+pub struct Recording {
+    pub header: RecordingHeader,
+    pub samples: Vec<RecordedSample>,
+}
+
+impl Recording {
+    pub fn load(path: &Path) -> Result<Self> {
+        let file = File::open(path).with_context(|| format!("Cannot open {}", path.display()))?;
+        let lines: Vec<String> = BufReader::new(file)
+            .lines()
+            .collect::<std::io::Result<_>>()?;
+
+        let header_line = lines
+            .first()
+            .ok_or_else(|| anyhow::anyhow!("{} is empty", path.display()))?;
+        let header: RecordingHeader = serde_json::from_str(header_line)
+            .with_context(|| format!("{} is not a lemo recording", path.display()))?;
+        if header.format != FORMAT_NAME || header.version > FORMAT_VERSION {
+            return Err(anyhow::anyhow!(
+                "Unsupported recording format {} v{}",
+                header.format,
+                header.version
+            ));
+        }
+
+        let mut samples = Vec::new();
+        let last_line = lines.len() - 1;
+        for (index, line) in lines.iter().enumerate().skip(1) {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<RecordedSample>(line) {
+                Ok(sample) => samples.push(sample),
+                // 录制被中断时最后一行可能不完整，忽略即可
+                Err(_) if index == last_line => break,
+                Err(e) => {
+                    return Err(e).with_context(|| format!("Invalid sample on line {}", index + 1))
+                }
+            }
+        }
+
+        if samples.is_empty() {
+            return Err(anyhow::anyhow!("{} contains no samples", path.display()));
+        }
+
+        Ok(Self { header, samples })
+    }
+}
+
+// 回放控制：播放/暂停、跳转和变速
+pub struct ReplayPlayer {
+    recording: Recording,
+    position: usize,
+    playhead_ms: f64,
+    playing: bool,
+    speed_index: usize,
+    last_tick: Instant,
+}
+
+impl ReplayPlayer {
+    pub fn new(recording: Recording) -> Self {
+        Self {
+            recording,
+            position: 0,
+            playhead_ms: 0.0,
+            playing: true,
+            speed_index: 2, // 1x
+            last_tick: Instant::now(),
+        }
+    }
+
+    pub fn header(&self) -> &RecordingHeader {
+        &self.recording.header
+    }
+
+    pub fn current(&self) -> &Snapshot {
+        &self.recording.samples[self.position].snapshot
+    }
+
+    // 当前位置之前（含）的所有采样点，用于重建历史趋势
+    pub fn history(&self) -> impl DoubleEndedIterator<Item = &Snapshot> {
+        self.recording.samples[..=self.position].iter().map(|s| &s.snapshot)
+    }
+
+    pub fn speed(&self) -> f64 {
+        SPEEDS[self.speed_index]
+    }
+
+    fn duration_ms(&self) -> f64 {
+        self.recording.samples.last().map(|s| s.elapsed_ms).unwrap_or(0) as f64
+    }
+
+    // 推进播放进度，返回当前采样点是否发生变化
+    pub fn tick(&mut self) -> bool {
+        self.tick_at(Instant::now())
+    }
+
+    fn tick_at(&mut self, now: Instant) -> bool {
+        if self.playing {
+            self.playhead_ms += now.duration_since(self.last_tick).as_secs_f64() * 1000.0 * self.speed();
+            if self.playhead_ms >= self.duration_ms() {
+                self.playhead_ms = self.duration_ms();
+                self.playing = false;
+            }
+        }
+        self.last_tick = now;
+        self.sync_position()
+    }
+
+    fn sync_position(&mut self) -> bool {
+        let playhead = self.playhead_ms as u64;
+        let position = self
+            .recording
+            .samples
+            .partition_point(|s| s.elapsed_ms <= playhead)
+            .saturating_sub(1);
+        let changed = position != self.position;
+        self.position = position;
+        changed
+    }
+
+    pub fn toggle_play(&mut self) {
+        // 播放结束后再次播放从头开始
+        if !self.playing && self.playhead_ms >= self.duration_ms() {
+            self.playhead_ms = 0.0;
+        }
+        self.playing = !self.playing;
+        self.last_tick = Instant::now();
+    }
+
+    pub fn seek(&mut self, delta_secs: f64) -> bool {
+        self.playhead_ms = (self.playhead_ms + delta_secs * 1000.0).clamp(0.0, self.duration_ms());
+        self.sync_position()
+    }
+
+    pub fn seek_start(&mut self) -> bool {
+        self.playhead_ms = 0.0;
+        self.sync_position()
+    }
+
+    pub fn seek_end(&mut self) -> bool {
+        self.playhead_ms = self.duration_ms();
+        self.sync_position()
+    }
+
+    pub fn faster(&mut self) {
+        self.speed_index = (self.speed_index + 1).min(SPEEDS.len() - 1);
+    }
+
+    pub fn slower(&mut self) {
+        self.speed_index = self.speed_index.saturating_sub(1);
+    }
+
+    // 例如 "▶ 2x 01:23 / 10:00 (sample 84/600)"
+    pub fn status(&self) -> String {
+        format!(
+            "{} {}x {} / {} (sample {}/{})",
+            if self.playing { "▶" } else { "⏸" },
+            self.speed(),
+            format_clock(Duration::from_millis(self.playhead_ms as u64)),
+            format_clock(Duration::from_millis(self.duration_ms() as u64)),
+            self.position + 1,
+            self.recording.samples.len()
+        )
+    }
+}
+
+fn format_clock(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, (secs % 3600) / 60, secs % 60)
+    } else {
+        format!("{:02}:{:02}", secs / 60, secs % 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn temp_file(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("lemo-recording-{}-{}.lemorec", name, std::process::id()))
+    }
+
+    fn write_lines(name: &str, lines: &[String]) -> PathBuf {
+        let path = temp_file(name);
+        std::fs::write(&path, lines.join("\n")).unwrap();
+        path
+    }
+
+    fn header_line(format: &str, version: u32) -> String {
+        serde_json::to_string(&RecordingHeader {
+            format: format.to_string(),
+            version,
+            lemo_version: "0.0.0".to_string(),
+            started: "2026-01-15T09:30:00+00:00".to_string(),
+            hostname: None,
+        })
+        .unwrap()
+    }
+
+    fn sample_line(elapsed_ms: u64) -> String {
+        serde_json::to_string(&RecordedSample {
+            elapsed_ms,
+            snapshot: Snapshot::sample(),
+        })
+        .unwrap()
+    }
+
+    // 采样点在 0、1000、2000、3000 毫秒
+    fn recording() -> Recording {
+        Recording {
+            header: serde_json::from_str(&header_line(FORMAT_NAME, FORMAT_VERSION)).unwrap(),
+            samples: (0..4)
+                .map(|i| RecordedSample {
+                    elapsed_ms: i * 1000,
+                    snapshot: Snapshot::sample(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn recorder_round_trip() {
+        let path = temp_file("round-trip");
+        let mut recorder = Recorder::create(&path).unwrap();
+        let mut snapshot = Snapshot::sample();
+        recorder.record(&snapshot).unwrap();
+        snapshot.cpu.usage = 99.0;
+        recorder.record(&snapshot).unwrap();
+        drop(recorder);
+
+        let recording = Recording::load(&path).unwrap();
+        assert_eq!(recording.header.format, FORMAT_NAME);
+        assert_eq!(recording.header.version, FORMAT_VERSION);
+        assert_eq!(recording.header.hostname.as_deref(), Some("fixture-host"));
+        assert_eq!(recording.samples.len(), 2);
+        assert_eq!(recording.samples[0].elapsed_ms, 0);
+        assert!(recording.samples[1].elapsed_ms >= recording.samples[0].elapsed_ms);
+        assert_eq!(recording.samples[1].snapshot.cpu.usage, 99.0);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn rejects_foreign_and_newer_files() {
+        let wrong_format = write_lines("format", &[header_line("other", 1), sample_line(0)]);
+        let newer = write_lines("version", &[header_line(FORMAT_NAME, FORMAT_VERSION + 1), sample_line(0)]);
+        let not_json = write_lines("garbage", &["hello".to_string(), sample_line(0)]);
+        let empty = write_lines("empty", &[]);
+        let no_samples = write_lines("no-samples", &[header_line(FORMAT_NAME, FORMAT_VERSION)]);
+        for path in [&wrong_format, &newer, &not_json, &empty, &no_samples] {
+            assert!(Recording::load(path).is_err(), "{} should be rejected", path.display());
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+
+    #[test]
+    fn tolerates_truncated_last_line_only() {
+        let mut truncated = sample_line(2000);
+        truncated.truncate(truncated.len() / 2);
+        let path = write_lines(
+            "truncated",
+            &[header_line(FORMAT_NAME, FORMAT_VERSION), sample_line(0), sample_line(1000), truncated.clone()],
+        );
+        let recording = Recording::load(&path).unwrap();
+        assert_eq!(recording.samples.len(), 2);
+        std::fs::remove_file(&path).unwrap();
+
+        // 中间的损坏行是错误
+        let path = write_lines(
+            "corrupt",
+            &[header_line(FORMAT_NAME, FORMAT_VERSION), sample_line(0), truncated, sample_line(2000)],
+        );
+        let error = Recording::load(&path).err().unwrap();
+        assert!(format!("{:#}", error).contains("line 3"));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn tick_follows_the_clock_and_speed() {
+        let mut player = ReplayPlayer::new(recording());
+        let start = Instant::now();
+        player.last_tick = start;
+
+        assert!(!player.tick_at(start + Duration::from_millis(500)));
+        assert_eq!(player.position, 0);
+        assert!(player.tick_at(start + Duration::from_millis(1200)));
+        assert_eq!(player.position, 1);
+
+        player.faster();
+        assert_eq!(player.speed(), 2.0);
+        // 再过 500 毫秒，2 倍速前进 1000 毫秒
+        assert!(player.tick_at(start + Duration::from_millis(1700)));
+        assert_eq!(player.position, 2);
+        assert_eq!(player.playhead_ms as u64, 2200);
+
+        // 播放到结尾后停止
+        player.tick_at(start + Duration::from_secs(10));
+        assert_eq!(player.position, 3);
+        assert!(!player.playing);
+        assert_eq!(player.playhead_ms, 3000.0);
+        assert!(player.status().starts_with("⏸ 2x 00:03 / 00:03 (sample 4/4)"));
+    }
+
+    #[test]
+    fn paused_player_does_not_advance() {
+        let mut player = ReplayPlayer::new(recording());
+        player.toggle_play();
+        assert!(!player.playing);
+        let start = Instant::now();
+        player.last_tick = start;
+        assert!(!player.tick_at(start + Duration::from_secs(5)));
+        assert_eq!(player.playhead_ms, 0.0);
+
+        // 播放结束后再次播放从头开始
+        player.seek_end();
+        player.toggle_play();
+        assert!(player.playing);
+        assert_eq!(player.playhead_ms, 0.0);
+    }
+
+    #[test]
+    fn seeks_are_clamped_and_sync_position() {
+        let mut player = ReplayPlayer::new(recording());
+        assert!(player.seek(2.5));
+        assert_eq!(player.position, 2);
+        assert_eq!(player.history().count(), 3);
+        assert!(!player.seek(0.4));
+        assert!(player.seek(-10.0));
+        assert_eq!((player.position, player.playhead_ms), (0, 0.0));
+        assert!(player.seek_end());
+        assert_eq!(player.position, 3);
+        assert!(!player.seek(10.0));
+        assert_eq!(player.playhead_ms, 3000.0);
+        assert!(player.seek_start());
+        assert_eq!(player.position, 0);
+
+        // 播放头在两个采样点之间时停留在前一个
+        player.playhead_ms = 1999.0;
+        assert!(player.sync_position());
+        assert_eq!(player.position, 1);
+        assert!(!player.sync_position());
+    }
+
+    #[test]
+    fn speed_steps_are_bounded() {
+        let mut player = ReplayPlayer::new(recording());
+        (0..10).for_each(|_| player.slower());
+        assert_eq!(player.speed(), 0.25);
+        (0..10).for_each(|_| player.faster());
+        assert_eq!(player.speed(), 16.0);
+        assert_eq!(format_clock(Duration::from_secs(3725)), "1:02:05");
+        assert_eq!(format_clock(Duration::from_secs(65)), "01:05");
+    }
+}
@@ -0,0 +1,450 @@
+// Remote monitoring: `lemo agent` streams snapshots, `lemo monitor --connect` renders them
+//
+// 协议为 TCP 上的 JSON Lines：
+// 客户端 → {"protocol":1,"token":"..."}
+// 服务端 → {"ok":true,"hostname":"...","lemo_version":"..."}（或 ok=false + error 后断开）
+// 服务端 → 每个采样间隔一行 Snapshot
+//
+// 连接本身不加密，跨网络使用时建议通过 SSH 隧道或 VPN
+use crate::config;
+use crate::snapshot::{LiveCollector, Snapshot};
+use anyhow::{Context, Result};
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, ErrorKind, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_PORT: u16 = 7878;
+const PROTOCOL_VERSION: u32 = 1;
+
+// 握手和单次写入的超时，避免卡住的客户端拖慢其他连接
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+// 认证前的限制：握手行的最大长度、同时进行的握手数
+const MAX_HELLO_LEN: usize = 4096;
+const MAX_PENDING_HANDSHAKES: usize = 16;
+// 客户端接收的单行（一个采样）最大长度
+const MAX_SNAPSHOT_LEN: usize = 16 * 1024 * 1024;
+
+// agent 共享状态：已认证的客户端和最近一次采样（新客户端连接后立即收到）
+// 写入在锁外进行，卡住的客户端不会阻塞其他客户端和新的握手
+#[derive(Default)]
+struct AgentState {
+    clients: Vec<(SocketAddr, Arc<TcpStream>)>,
+    latest: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Hello {
+    protocol: u32,
+    token: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Welcome {
+    ok: bool,
+    hostname: Option<String>,
+    lemo_version: String,
+    error: Option<String>,
+}
+
+// 比较 token 时不提前返回，避免通过响应时间猜测
+fn token_matches(expected: &str, given: &str) -> bool {
+    expected.len() == given.len()
+        && expected
+            .bytes()
+            .zip(given.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+fn log(message: &str) {
+    println!("[{}] {}", Local::now().format("%Y-%m-%d %H:%M:%S"), message);
+}
+
+// Run the agent: sample once per interval and stream to every authenticated client
+pub fn run_agent(listen: &str, token: &str, interval: Duration) -> Result<()> {
+    if token.is_empty() {
+        return Err(anyhow::anyhow!("--token must not be empty"));
+    }
+
+    let listener = TcpListener::bind(listen).with_context(|| format!("Cannot listen on {}", listen))?;
+    let state = Arc::new(Mutex::new(AgentState::default()));
+
+    let config = config::Config::load().unwrap_or_default();
+    let mut collector = LiveCollector::new(config.sensors);
+    let hostname = collector.snapshot().system.hostname;
+
+    log(&format!(
+        "🛰️ lemo agent {} listening on {} (interval {:.1}s)",
+        env!("CARGO_PKG_VERSION"),
+        listener.local_addr()?,
+        interval.as_secs_f64()
+    ));
+
+    // 采集线程：所有客户端共享同一份采样
+    let broadcast_state = Arc::clone(&state);
+    thread::spawn(move || loop {
+        thread::sleep(interval);
+        let snapshot = collector.sample();
+        let mut line = match serde_json::to_string(&snapshot) {
+            Ok(line) => line,
+            Err(_) => continue,
+        };
+        line.push('\n');
+
+        let clients = {
+            let mut state = broadcast_state.lock().unwrap_or_else(|e| e.into_inner());
+            state.latest = Some(line.clone());
+            state.clients.clone()
+        };
+        let failed: Vec<SocketAddr> = clients
+            .iter()
+            .filter(|(_, stream)| stream.as_ref().write_all(line.as_bytes()).is_err())
+            .map(|(addr, _)| *addr)
+            .collect();
+        if !failed.is_empty() {
+            let mut state = broadcast_state.lock().unwrap_or_else(|e| e.into_inner());
+            state.clients.retain(|(addr, _)| !failed.contains(addr));
+            failed.iter().for_each(|addr| log(&format!("Client {} disconnected", addr)));
+        }
+    });
+
+    let pending = Arc::new(AtomicUsize::new(0));
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                log(&format!("❌ Accept failed: {}", e));
+                continue;
+            }
+        };
+        let addr = match stream.peer_addr() {
+            Ok(addr) => addr,
+            Err(_) => continue,
+        };
+        // 未认证的连接过多时直接断开，每个握手都占用一个线程
+        if pending.fetch_add(1, Ordering::SeqCst) >= MAX_PENDING_HANDSHAKES {
+            pending.fetch_sub(1, Ordering::SeqCst);
+            log(&format!("❌ Rejected {}: too many pending handshakes", addr));
+            continue;
+        }
+        let state = Arc::clone(&state);
+        let pending = Arc::clone(&pending);
+        let token = token.to_string();
+        let hostname = hostname.clone();
+        thread::spawn(move || {
+            let result = handshake(stream, &token, hostname);
+            pending.fetch_sub(1, Ordering::SeqCst);
+            match result {
+                Ok(mut stream) => {
+                    log(&format!("✅ Client {} connected", addr));
+                    let latest = state.lock().unwrap_or_else(|e| e.into_inner()).latest.clone();
+                    if let Some(latest) = latest {
+                        if stream.write_all(latest.as_bytes()).is_err() {
+                            return;
+                        }
+                    }
+                    let mut state = state.lock().unwrap_or_else(|e| e.into_inner());
+                    state.clients.push((addr, Arc::new(stream)));
+                }
+                Err(e) => log(&format!("❌ Rejected {}: {}", addr, e)),
+            }
+        });
+    }
+
+    Ok(())
+}
+
+// 读取客户端的握手行：整个握手（而不是每次读取）限时 HANDSHAKE_TIMEOUT，长度不超过 MAX_HELLO_LEN
+fn read_hello(mut stream: &TcpStream) -> Result<String> {
+    let deadline = Instant::now() + HANDSHAKE_TIMEOUT;
+    let mut line = Vec::new();
+    let mut buffer = [0u8; 512];
+    while !line.contains(&b'\n') {
+        if line.len() > MAX_HELLO_LEN {
+            return Err(anyhow::anyhow!("Handshake longer than {} bytes", MAX_HELLO_LEN));
+        }
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(anyhow::anyhow!("Handshake timed out"));
+        }
+        stream.set_read_timeout(Some(remaining))?;
+        // 客户端发送握手后等待响应，不会有多读的数据
+        let limit = buffer.len().min(MAX_HELLO_LEN + 1 - line.len());
+        match stream.read(&mut buffer[..limit]) {
+            Ok(0) => return Err(anyhow::anyhow!("Connection closed during handshake")),
+            Ok(n) => line.extend_from_slice(&buffer[..n]),
+            Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {
+                return Err(anyhow::anyhow!("Handshake timed out"))
+            }
+            Err(e) if e.kind() == ErrorKind::Interrupted => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(String::from_utf8_lossy(&line).into_owned())
+}
+
+fn handshake(stream: TcpStream, token: &str, hostname: Option<String>) -> Result<TcpStream> {
+    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+
+    let line = read_hello(&stream)?;
+    let hello: Result<Hello> = serde_json::from_str(&line).context("Invalid handshake");
+
+    let error = match &hello {
+        Err(e) => Some(e.to_string()),
+        Ok(hello) if hello.protocol != PROTOCOL_VERSION => {
+            Some(format!("Unsupported protocol version {}", hello.protocol))
+        }
+        Ok(hello) if !token_matches(token, &hello.token) => Some("Invalid token".to_string()),
+        Ok(_) => None,
+    };
+
+    let welcome = Welcome {
+        ok: error.is_none(),
+        hostname,
+        lemo_version: env!("CARGO_PKG_VERSION").to_string(),
+        error: error.clone(),
+    };
+    let mut writer = &stream;
+    serde_json::to_writer(&mut writer, &welcome)?;
+    writer.write_all(b"\n")?;
+
+    match error {
+        Some(error) => Err(anyhow::anyhow!(error)),
+        None => {
+            stream.set_read_timeout(None)?;
+            Ok(stream)
+        }
+    }
+}
+
+// 客户端事件（由后台线程发送）
+pub enum RemoteEvent {
+    Connected { hostname: Option<String> },
+    Snapshot(Box<Snapshot>),
+    Failed(String),
+}
+
+// 连接到远程 agent，连接、握手和读取都在后台线程中进行
+pub struct RemoteClient {
+    address: String,
+    events: Receiver<RemoteEvent>,
+}
+
+impl RemoteClient {
+    pub fn connect(address: &str, token: &str) -> Self {
+        let address = with_default_port(address);
+
+        let (tx, rx) = mpsc::channel();
+        let target = address.clone();
+        let token = token.to_string();
+        thread::spawn(move || {
+            let result = (|| -> Result<()> {
+                let stream = open_stream(&target)?;
+                let mut writer = &stream;
+                serde_json::to_writer(
+                    &mut writer,
+                    &Hello {
+                        protocol: PROTOCOL_VERSION,
+                        token,
+                    },
+                )?;
+                writer.write_all(b"\n")?;
+
+                let mut reader = BufReader::new(&stream);
+                let line = read_line_limited(&mut reader, MAX_HELLO_LEN)?
+                    .ok_or_else(|| anyhow::anyhow!("Connection closed by agent"))?;
+                let welcome: Welcome = serde_json::from_str(&line)
+                    .with_context(|| format!("{} is not a lemo agent", target))?;
+                if !welcome.ok {
+                    return Err(anyhow::anyhow!(welcome.error.unwrap_or_else(|| "Rejected".to_string())));
+                }
+                if tx.send(RemoteEvent::Connected { hostname: welcome.hostname }).is_err() {
+                    return Ok(());
+                }
+
+                stream.set_read_timeout(None)?;
+                while let Some(line) = read_line_limited(&mut reader, MAX_SNAPSHOT_LEN)? {
+                    let snapshot: Snapshot = serde_json::from_str(&line).context("Invalid snapshot")?;
+                    if tx.send(RemoteEvent::Snapshot(Box::new(snapshot))).is_err() {
+                        // 界面已关闭
+                        return Ok(());
+                    }
+                }
+                Err(anyhow::anyhow!("Connection closed by agent"))
+            })();
+            if let Err(e) = result {
+                let _ = tx.send(RemoteEvent::Failed(format!("{:#}", e)));
+            }
+        });
+
+        Self { address, events: rx }
+    }
+
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    // 阻塞等待下一个事件
+    pub fn wait(&self, timeout: Duration) -> Option<RemoteEvent> {
+        self.events.recv_timeout(timeout).ok()
+    }
+
+    // 非阻塞读取所有待处理事件
+    pub fn drain(&self) -> Vec<RemoteEvent> {
+        let mut events = Vec::new();
+        loop {
+            match self.events.try_recv() {
+                Ok(event) => events.push(event),
+                Err(TryRecvError::Empty) | Err(TryRecvError::Disconnected) => return events,
+            }
+        }
+    }
+}
+
+// 省略端口时使用默认端口："host"、"192.168.1.2"、"::1" 和 "[::1]" 都补上端口
+fn with_default_port(address: &str) -> String {
+    if address.parse::<SocketAddr>().is_ok() {
+        return address.to_string();
+    }
+    if let Ok(ip) = address.parse::<std::net::IpAddr>() {
+        return SocketAddr::new(ip, DEFAULT_PORT).to_string();
+    }
+    if (address.starts_with('[') && address.ends_with(']')) || !address.contains(':') {
+        return format!("{}:{}", address, DEFAULT_PORT);
+    }
+    address.to_string()
+}
+
+// 读取一行（不含换行符），超过 limit 字节时返回错误；连接关闭时返回 None
+fn read_line_limited(reader: &mut impl BufRead, limit: usize) -> Result<Option<String>> {
+    let mut line = Vec::new();
+    reader.take(limit as u64 + 1).read_until(b'\n', &mut line)?;
+    if line.last() == Some(&b'\n') {
+        line.pop();
+    } else if line.len() > limit {
+        return Err(anyhow::anyhow!("Line longer than {} bytes", limit));
+    } else if line.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(String::from_utf8_lossy(&line).into_owned()))
+}
+
+fn open_stream(address: &str) -> Result<TcpStream> {
+    let addrs: Vec<SocketAddr> = address
+        .to_socket_addrs()
+        .with_context(|| format!("Cannot resolve {}", address))?
+        .collect();
+    let mut last_error = None;
+    for addr in addrs {
+        match TcpStream::connect_timeout(&addr, HANDSHAKE_TIMEOUT) {
+            Ok(stream) => {
+                stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+                return Ok(stream);
+            }
+            Err(e) => last_error = Some(e),
+        }
+    }
+    Err(match last_error {
+        Some(e) => anyhow::Error::new(e).context(format!("Cannot connect to {}", address)),
+        None => anyhow::anyhow!("Cannot resolve {}", address),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 在 127.0.0.1 的随机端口上接受一个连接并握手，返回地址和握手结果
+    fn agent_once(token: &'static str) -> (String, thread::JoinHandle<Result<TcpStream>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept()?;
+            handshake(stream, token, Some("loopback-host".to_string()))
+        });
+        (address, handle)
+    }
+
+    // 发送原始握手行，读取服务端的响应
+    fn send_hello(address: &str, hello: &[u8]) -> Welcome {
+        let mut stream = TcpStream::connect(address).unwrap();
+        stream.write_all(hello).unwrap();
+        let mut line = String::new();
+        BufReader::new(&stream).read_line(&mut line).unwrap();
+        serde_json::from_str(&line).unwrap()
+    }
+
+    #[test]
+    fn handshake_succeeds_with_matching_token() {
+        let (address, agent) = agent_once("secret");
+        let client = RemoteClient::connect(&address, "secret");
+        match client.wait(HANDSHAKE_TIMEOUT) {
+            Some(RemoteEvent::Connected { hostname }) => assert_eq!(hostname.as_deref(), Some("loopback-host")),
+            _ => panic!("expected a connected event"),
+        }
+        assert!(agent.join().unwrap().is_ok());
+    }
+
+    #[test]
+    fn wrong_token_is_rejected() {
+        let (address, agent) = agent_once("secret");
+        let client = RemoteClient::connect(&address, "guess");
+        match client.wait(HANDSHAKE_TIMEOUT) {
+            Some(RemoteEvent::Failed(error)) => assert_eq!(error, "Invalid token"),
+            _ => panic!("expected a failed event"),
+        }
+        let error = agent.join().unwrap().unwrap_err();
+        assert_eq!(error.to_string(), "Invalid token");
+    }
+
+    #[test]
+    fn protocol_mismatch_is_rejected() {
+        let (address, agent) = agent_once("secret");
+        let welcome = send_hello(&address, b"{\"protocol\":2,\"token\":\"secret\"}\n");
+        assert!(!welcome.ok);
+        assert_eq!(welcome.error.as_deref(), Some("Unsupported protocol version 2"));
+        assert!(agent.join().unwrap().is_err());
+    }
+
+    #[test]
+    fn default_port_is_added_when_missing() {
+        assert_eq!(with_default_port("example.com"), "example.com:7878");
+        assert_eq!(with_default_port("example.com:9000"), "example.com:9000");
+        assert_eq!(with_default_port("192.168.1.2"), "192.168.1.2:7878");
+        assert_eq!(with_default_port("192.168.1.2:9000"), "192.168.1.2:9000");
+        assert_eq!(with_default_port("::1"), "[::1]:7878");
+        assert_eq!(with_default_port("fe80::1:2"), "[fe80::1:2]:7878");
+        assert_eq!(with_default_port("[::1]"), "[::1]:7878");
+        assert_eq!(with_default_port("[::1]:9000"), "[::1]:9000");
+    }
+
+    #[test]
+    fn lines_are_length_limited() {
+        let mut reader = BufReader::new(&b"short\nthis line is too long\nlast"[..]);
+        assert_eq!(read_line_limited(&mut reader, 8).unwrap().as_deref(), Some("short"));
+        assert!(read_line_limited(&mut reader, 8).is_err());
+
+        // 连接关闭前的最后一行没有换行符
+        let mut reader = BufReader::new(&b"a\nlast"[..]);
+        assert_eq!(read_line_limited(&mut reader, 8).unwrap().as_deref(), Some("a"));
+        assert_eq!(read_line_limited(&mut reader, 8).unwrap().as_deref(), Some("last"));
+        assert_eq!(read_line_limited(&mut reader, 8).unwrap(), None);
+    }
+
+    #[test]
+    fn oversized_handshake_is_rejected() {
+        let (address, agent) = agent_once("secret");
+        let mut stream = TcpStream::connect(&address).unwrap();
+        // 没有换行的超长数据：服务端读满上限后断开
+        let _ = stream.write_all(&vec![b'x'; MAX_HELLO_LEN * 2]);
+        let error = agent.join().unwrap().unwrap_err();
+        assert!(error.to_string().contains("longer than"), "{}", error);
+    }
+}
@@ -0,0 +1,526 @@
+// Scheduled cleanups: `lemo schedule add/list/remove`, executed by `lemo daemon`
+//
+// 计划保存在 <配置目录>/schedules.toml：
+//
+// [[schedule]]
+// id = 1
+// task = "clean-temp"
+// every = "weekly"
+// at = "03:00"
+// on = "mon"            # 仅 weekly 使用，默认周一
+// older_than = "14d"
+//
+// 上次运行时间等状态由 daemon 写入 <数据目录>/schedule-state.json，不修改用户的配置文件
+use crate::config;
+use crate::utils::{format_age, parse_age, CleanOptions, SCHEDULABLE_TASKS};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Datelike, Local, Months, NaiveDate, TimeZone, Timelike, Weekday};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Every {
+    Hourly,
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+impl Every {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Every::Hourly => "hourly",
+            Every::Daily => "daily",
+            Every::Weekly => "weekly",
+            Every::Monthly => "monthly",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Schedule {
+    pub id: u32,
+    pub task: String,
+    pub every: Every,
+    // HH:MM，hourly 只使用分钟
+    #[serde(default = "default_at")]
+    pub at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub older_than: Option<String>,
+    #[serde(default)]
+    pub include_user: bool,
+    // 创建时间（RFC 3339），从未运行过的计划从这之后的第一个时间点开始
+    pub created: String,
+}
+
+fn default_at() -> String {
+    "03:00".to_string()
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ScheduleFile {
+    #[serde(default)]
+    schedule: Vec<Schedule>,
+}
+
+// daemon 记录的运行状态
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScheduleState {
+    pub last_run: Option<String>,
+    pub last_result: Option<String>,
+    pub last_report: Option<PathBuf>,
+}
+
+fn parse_time(text: &str) -> Result<(u32, u32)> {
+    let invalid = || anyhow::anyhow!("Invalid time '{}' (use HH:MM)", text);
+    let (hour, minute) = text.split_once(':').ok_or_else(invalid)?;
+    let hour: u32 = hour.parse().map_err(|_| invalid())?;
+    let minute: u32 = minute.parse().map_err(|_| invalid())?;
+    if hour > 23 || minute > 59 {
+        return Err(invalid());
+    }
+    Ok((hour, minute))
+}
+
+fn parse_weekday(text: &str) -> Result<Weekday> {
+    text.parse()
+        .map_err(|_| anyhow::anyhow!("Invalid weekday '{}' (use mon, tue, ...)", text))
+}
+
+// 指定日期的本地时间；夏令时跳过的时间（如 02:30）改为跳过后的第一个有效时间
+fn local_at<Tz: TimeZone>(tz: &Tz, date: NaiveDate, hour: u32, minute: u32) -> Option<DateTime<Tz>> {
+    let time = date.and_hms_opt(hour, minute, 0)?;
+    (0..=24 * 60).find_map(|offset| tz.from_local_datetime(&(time + chrono::Duration::minutes(offset))).earliest())
+}
+
+impl Schedule {
+    pub fn clean_options(&self) -> Result<CleanOptions> {
+        Ok(CleanOptions {
+            include_user: self.include_user,
+            older_than: self.older_than.as_deref().map(parse_age).transpose()?,
+            ..Default::default()
+        })
+    }
+
+    fn validate(&self) -> Result<()> {
+        if !SCHEDULABLE_TASKS.contains(&self.task.as_str()) {
+            return Err(anyhow::anyhow!(
+                "Unknown task '{}' (available: {})",
+                self.task,
+                SCHEDULABLE_TASKS.join(", ")
+            ));
+        }
+        parse_time(&self.at)?;
+        if let Some(on) = &self.on {
+            parse_weekday(on)?;
+        }
+        self.clean_options()?;
+        Ok(())
+    }
+
+    // 不晚于 now 的最近一个计划时间点
+    pub fn last_slot<Tz: TimeZone>(&self, now: DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let tz = now.timezone();
+        let (hour, minute) = parse_time(&self.at).ok()?;
+        let today = now.date_naive();
+        match self.every {
+            Every::Hourly => {
+                let slot = local_at(&tz, today, now.hour(), minute)?;
+                Some(if slot <= now { slot } else { slot - chrono::Duration::hours(1) })
+            }
+            Every::Daily => {
+                let slot = local_at(&tz, today, hour, minute)?;
+                if slot <= now {
+                    Some(slot)
+                } else {
+                    local_at(&tz, today.pred_opt()?, hour, minute)
+                }
+            }
+            Every::Weekly => {
+                let weekday = match &self.on {
+                    Some(on) => parse_weekday(on).ok()?,
+                    None => Weekday::Mon,
+                };
+                let days_back = (today.weekday().num_days_from_monday() + 7 - weekday.num_days_from_monday()) % 7;
+                let date = today - chrono::Duration::days(days_back as i64);
+                let slot = local_at(&tz, date, hour, minute)?;
+                if slot <= now {
+                    Some(slot)
+                } else {
+                    local_at(&tz, date - chrono::Duration::days(7), hour, minute)
+                }
+            }
+            Every::Monthly => {
+                let first = today.with_day(1)?;
+                let slot = local_at(&tz, first, hour, minute)?;
+                if slot <= now {
+                    Some(slot)
+                } else {
+                    local_at(&tz, first.checked_sub_months(Months::new(1))?, hour, minute)
+                }
+            }
+        }
+    }
+
+    pub fn next_slot<Tz: TimeZone>(&self, now: DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let tz = now.timezone();
+        let last = self.last_slot(now)?;
+        // 不用 last 的时间：它可能因夏令时被推后
+        let (hour, minute) = parse_time(&self.at).ok()?;
+        match self.every {
+            Every::Hourly => Some(last + chrono::Duration::hours(1)),
+            Every::Daily => local_at(&tz, last.date_naive().succ_opt()?, hour, minute),
+            Every::Weekly => local_at(&tz, last.date_naive() + chrono::Duration::days(7), hour, minute),
+            Every::Monthly => local_at(&tz, last.date_naive().checked_add_months(Months::new(1))?, hour, minute),
+        }
+    }
+
+    // 上次运行（或创建）之后又到了一个计划时间点
+    pub fn is_due(&self, now: DateTime<Local>, state: Option<&ScheduleState>) -> bool {
+        let since = state
+            .and_then(|s| s.last_run.as_deref())
+            .unwrap_or(&self.created);
+        let Ok(since) = DateTime::parse_from_rfc3339(since) else {
+            return true;
+        };
+        self.last_slot(now).is_some_and(|slot| slot > since)
+    }
+
+    pub fn when_display(&self) -> String {
+        match (self.every, &self.on) {
+            (Every::Hourly, _) => format!("hourly at :{}", self.at.split(':').nth(1).unwrap_or("00")),
+            (Every::Weekly, on) => format!("weekly on {} at {}", on.as_deref().unwrap_or("mon"), self.at),
+            (every, _) => format!("{} at {}", every.label(), self.at),
+        }
+    }
+
+    pub fn options_display(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(age) = &self.older_than {
+            parts.push(format!("older than {}", age));
+        }
+        if self.include_user {
+            parts.push("include user".to_string());
+        }
+        if parts.is_empty() {
+            "-".to_string()
+        } else {
+            parts.join(", ")
+        }
+    }
+}
+
+pub fn schedules_path() -> Option<PathBuf> {
+    config::config_dir().map(|dir| dir.join("schedules.toml"))
+}
+
+fn state_path() -> Option<PathBuf> {
+    config::data_dir().map(|dir| dir.join("schedule-state.json"))
+}
+
+pub fn load() -> Result<Vec<Schedule>> {
+    let path = match schedules_path() {
+        Some(p) if p.exists() => p,
+        _ => return Ok(Vec::new()),
+    };
+    let content = fs::read_to_string(&path).with_context(|| format!("Cannot read {}", path.display()))?;
+    let file: ScheduleFile =
+        toml::from_str(&content).with_context(|| format!("Invalid schedule file {}", path.display()))?;
+    Ok(file.schedule)
+}
+
+fn save(schedules: &[Schedule]) -> Result<()> {
+    let path = schedules_path().ok_or_else(|| anyhow::anyhow!("Cannot determine config directory"))?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("Cannot create {}", dir.display()))?;
+    }
+    let content = toml::to_string(&ScheduleFile {
+        schedule: schedules.to_vec(),
+    })?;
+    fs::write(&path, content).with_context(|| format!("Cannot write {}", path.display()))
+}
+
+// 运行状态文件（计划和磁盘空间策略共用格式）：id → 状态
+pub fn read_state_file(path: Option<PathBuf>) -> BTreeMap<u32, ScheduleState> {
+    path.and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub fn write_state_file(path: Option<PathBuf>, state: &BTreeMap<u32, ScheduleState>) -> Result<()> {
+    let path = path.ok_or_else(|| anyhow::anyhow!("Cannot determine data directory"))?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&path, serde_json::to_string_pretty(state)?).with_context(|| format!("Cannot write {}", path.display()))
+}
+
+// 删除某个 id 的状态：id 会被新添加的条目重新使用，不能让它继承上次运行时间
+pub fn forget_state(path: Option<PathBuf>, id: u32) -> Result<()> {
+    let mut state = read_state_file(path.clone());
+    if state.remove(&id).is_some() {
+        write_state_file(path, &state)?;
+    }
+    Ok(())
+}
+
+pub fn load_state() -> BTreeMap<u32, ScheduleState> {
+    read_state_file(state_path())
+}
+
+pub fn save_state(state: &BTreeMap<u32, ScheduleState>) -> Result<()> {
+    write_state_file(state_path(), state)
+}
+
+// 新计划参数（来自 `lemo schedule add`）
+pub struct NewSchedule {
+    pub task: String,
+    pub every: Every,
+    pub at: String,
+    pub on: Option<String>,
+    pub older_than: Option<Duration>,
+    pub include_user: bool,
+}
+
+pub fn add(new: NewSchedule) -> Result<Schedule> {
+    let mut schedules = load()?;
+    let schedule = Schedule {
+        id: schedules.iter().map(|s| s.id).max().unwrap_or(0) + 1,
+        task: new.task,
+        every: new.every,
+        at: new.at,
+        on: new.on,
+        older_than: new.older_than.map(format_age),
+        include_user: new.include_user,
+        created: Local::now().to_rfc3339(),
+    };
+    schedule.validate()?;
+    schedules.push(schedule.clone());
+    save(&schedules)?;
+    forget_state(state_path(), schedule.id)?;
+    Ok(schedule)
+}
+
+pub fn remove(id: u32) -> Result<()> {
+    let mut schedules = load()?;
+    let before = schedules.len();
+    schedules.retain(|s| s.id != id);
+    if schedules.len() == before {
+        return Err(anyhow::anyhow!("No schedule with id {}", id));
+    }
+    save(&schedules)?;
+    forget_state(state_path(), id)
+}
+
+pub fn time_display(text: Option<&str>) -> String {
+    text.and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+        .map(|t| t.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+// lemo schedule list
+pub fn print_schedules() -> Result<()> {
+    let schedules = load()?;
+    if schedules.is_empty() {
+        println!("No schedules. Add one with `lemo schedule add clean-temp --every weekly --at 03:00`.");
+        return Ok(());
+    }
+
+    let state = load_state();
+    let now = Local::now();
+    println!(
+        "{:<4} {:<17} {:<24} {:<22} {:<17} {:<17} Last result",
+        "ID", "Task", "When", "Options", "Last run", "Next run"
+    );
+    for schedule in &schedules {
+        let entry = state.get(&schedule.id);
+        println!(
+            "{:<4} {:<17} {:<24} {:<22} {:<17} {:<17} {}",
+            schedule.id,
+            schedule.task,
+            schedule.when_display(),
+            schedule.options_display(),
+            time_display(entry.and_then(|s| s.last_run.as_deref())),
+            schedule
+                .next_slot(now)
+                .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_else(|| "-".to_string()),
+            entry.and_then(|s| s.last_result.as_deref()).unwrap_or("-")
+        );
+    }
+    println!();
+    println!("Schedules run while `lemo daemon` is running.");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(day: u32, hour: u32, minute: u32) -> DateTime<Local> {
+        Local.with_ymd_and_hms(2026, 1, day, hour, minute, 0).unwrap()
+    }
+
+    fn schedule(every: Every, at: &str, on: Option<&str>) -> Schedule {
+        Schedule {
+            id: 1,
+            task: "clean-temp".to_string(),
+            every,
+            at: at.to_string(),
+            on: on.map(str::to_string),
+            older_than: None,
+            include_user: false,
+            created: at_rfc3339(1, 0, 0),
+        }
+    }
+
+    fn at_rfc3339(day: u32, hour: u32, minute: u32) -> String {
+        at(day, hour, minute).to_rfc3339()
+    }
+
+    // 测试用时区：UTC+1，2026-03-29 02:00 起为 UTC+2（本地时间 02:00–02:59 不存在）
+    #[derive(Clone, Copy, Debug)]
+    struct GapZone;
+
+    impl GapZone {
+        fn change() -> chrono::NaiveDateTime {
+            NaiveDate::from_ymd_opt(2026, 3, 29).unwrap().and_hms_opt(2, 0, 0).unwrap()
+        }
+    }
+
+    impl TimeZone for GapZone {
+        type Offset = chrono::FixedOffset;
+
+        fn from_offset(_: &Self::Offset) -> Self {
+            GapZone
+        }
+
+        fn offset_from_local_date(&self, local: &NaiveDate) -> chrono::MappedLocalTime<Self::Offset> {
+            self.offset_from_local_datetime(&local.and_hms_opt(0, 0, 0).unwrap())
+        }
+
+        fn offset_from_local_datetime(&self, local: &chrono::NaiveDateTime) -> chrono::MappedLocalTime<Self::Offset> {
+            let hours = if *local < Self::change() {
+                1
+            } else if *local < Self::change() + chrono::Duration::hours(1) {
+                return chrono::MappedLocalTime::None;
+            } else {
+                2
+            };
+            chrono::MappedLocalTime::Single(chrono::FixedOffset::east_opt(hours * 3600).unwrap())
+        }
+
+        fn offset_from_utc_date(&self, utc: &NaiveDate) -> Self::Offset {
+            self.offset_from_utc_datetime(&utc.and_hms_opt(0, 0, 0).unwrap())
+        }
+
+        fn offset_from_utc_datetime(&self, utc: &chrono::NaiveDateTime) -> Self::Offset {
+            let hours = if *utc < Self::change() - chrono::Duration::hours(1) { 1 } else { 2 };
+            chrono::FixedOffset::east_opt(hours * 3600).unwrap()
+        }
+    }
+
+    fn gap_at(day: u32, hour: u32, minute: u32) -> DateTime<GapZone> {
+        GapZone.with_ymd_and_hms(2026, 3, day, hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn slots_in_a_dst_gap_move_to_the_end_of_the_gap() {
+        let daily = schedule(Every::Daily, "02:30", None);
+        assert_eq!(daily.last_slot(gap_at(29, 12, 0)), Some(gap_at(29, 3, 0)));
+        assert_eq!(daily.next_slot(gap_at(29, 1, 0)), Some(gap_at(29, 3, 0)));
+        assert_eq!(daily.last_slot(gap_at(29, 3, 0)), Some(gap_at(29, 3, 0)));
+        // 下一天恢复原来的时间
+        assert_eq!(daily.next_slot(gap_at(29, 12, 0)), Some(gap_at(30, 2, 30)));
+        assert_eq!(daily.last_slot(gap_at(30, 1, 0)), Some(gap_at(29, 3, 0)));
+
+        let weekly = schedule(Every::Weekly, "02:00", Some("sun"));
+        assert_eq!(weekly.last_slot(gap_at(30, 0, 0)), Some(gap_at(29, 3, 0)));
+        assert_eq!(
+            weekly.next_slot(gap_at(30, 0, 0)),
+            Some(GapZone.with_ymd_and_hms(2026, 4, 5, 2, 0, 0).unwrap())
+        );
+    }
+
+    // 2026-01-14 是周三
+    #[test]
+    fn hourly_slots_use_only_the_minute() {
+        let hourly = schedule(Every::Hourly, "03:15", None);
+        assert_eq!(hourly.last_slot(at(14, 10, 30)), Some(at(14, 10, 15)));
+        assert_eq!(hourly.next_slot(at(14, 10, 30)), Some(at(14, 11, 15)));
+        assert_eq!(hourly.last_slot(at(14, 10, 15)), Some(at(14, 10, 15)));
+        assert_eq!(hourly.last_slot(at(14, 10, 10)), Some(at(14, 9, 15)));
+        // 跨过午夜
+        assert_eq!(hourly.last_slot(at(14, 0, 5)), Some(at(13, 23, 15)));
+    }
+
+    #[test]
+    fn daily_slots() {
+        let daily = schedule(Every::Daily, "03:00", None);
+        assert_eq!(daily.last_slot(at(14, 10, 30)), Some(at(14, 3, 0)));
+        assert_eq!(daily.next_slot(at(14, 10, 30)), Some(at(15, 3, 0)));
+        assert_eq!(daily.last_slot(at(14, 2, 59)), Some(at(13, 3, 0)));
+        assert_eq!(daily.next_slot(at(14, 2, 59)), Some(at(14, 3, 0)));
+    }
+
+    #[test]
+    fn weekly_slots_default_to_monday() {
+        let monday = schedule(Every::Weekly, "03:00", None);
+        assert_eq!(monday.last_slot(at(14, 10, 30)), Some(at(12, 3, 0)));
+        assert_eq!(monday.next_slot(at(14, 10, 30)), Some(at(19, 3, 0)));
+
+        // 当天的时间点还没到：上周同一天
+        let wednesday = schedule(Every::Weekly, "12:00", Some("wed"));
+        assert_eq!(wednesday.last_slot(at(14, 10, 30)), Some(at(7, 12, 0)));
+        assert_eq!(wednesday.next_slot(at(14, 10, 30)), Some(at(14, 12, 0)));
+        assert_eq!(wednesday.last_slot(at(14, 12, 0)), Some(at(14, 12, 0)));
+
+        let invalid = schedule(Every::Weekly, "03:00", Some("someday"));
+        assert_eq!(invalid.last_slot(at(14, 10, 30)), None);
+    }
+
+    #[test]
+    fn monthly_slots_run_on_the_first() {
+        let monthly = schedule(Every::Monthly, "03:00", None);
+        assert_eq!(monthly.last_slot(at(14, 10, 30)), Some(at(1, 3, 0)));
+        assert_eq!(
+            monthly.next_slot(at(14, 10, 30)),
+            Some(Local.with_ymd_and_hms(2026, 2, 1, 3, 0, 0).unwrap())
+        );
+        // 1 日的时间点之前：上个月（跨年）
+        assert_eq!(
+            monthly.last_slot(at(1, 2, 0)),
+            Some(Local.with_ymd_and_hms(2025, 12, 1, 3, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn due_after_a_slot_since_last_run_or_creation() {
+        let mut daily = schedule(Every::Daily, "03:00", None);
+        daily.created = at_rfc3339(13, 12, 0);
+        let now = at(14, 10, 30);
+        assert!(daily.is_due(now, None));
+
+        let ran = ScheduleState {
+            last_run: Some(at_rfc3339(14, 3, 1)),
+            ..Default::default()
+        };
+        assert!(!daily.is_due(now, Some(&ran)));
+        assert!(daily.is_due(at(15, 3, 0), Some(&ran)));
+
+        // 创建于今天的时间点之后：等到明天
+        daily.created = at_rfc3339(14, 5, 0);
+        assert!(!daily.is_due(now, None));
+        assert!(!daily.is_due(now, Some(&ScheduleState::default())));
+
+        daily.created = "not a date".to_string();
+        assert!(daily.is_due(now, None));
+    }
+}
@@ -0,0 +1,112 @@
+// Temperature sensor tracking for the real-time monitor
+use crate::config::SensorConfig;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use sysinfo::Components;
+
+// 未配置分组的传感器归入此分组
+pub const DEFAULT_GROUP: &str = "Sensors";
+
+// 单个传感器的当前读数与会话统计
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SensorReading {
+    pub label: String,
+    pub name: String,
+    pub group: String,
+    pub current: Option<f32>,
+    pub session_min: Option<f32>,
+    pub session_max: Option<f32>,
+    pub max: Option<f32>,
+    pub critical: Option<f32>,
+}
+
+// 跟踪每个传感器在本次监控会话中的最低/最高温度
+pub struct SensorTracker {
+    components: Components,
+    stats: HashMap<String, (f32, f32)>,
+    aliases: BTreeMap<String, SensorConfig>,
+}
+
+impl SensorTracker {
+    pub fn new(aliases: BTreeMap<String, SensorConfig>) -> Self {
+        let mut tracker = Self {
+            components: Components::new_with_refreshed_list(),
+            stats: HashMap::new(),
+            aliases,
+        };
+        tracker.record();
+        tracker
+    }
+
+    pub fn refresh(&mut self) {
+        self.components.refresh();
+        self.record();
+    }
+
+    fn record(&mut self) {
+        for (key, component) in keyed_components(&self.components) {
+            let temp = component.temperature();
+            if !temp.is_finite() {
+                continue;
+            }
+            let entry = self.stats.entry(key).or_insert((temp, temp));
+            entry.0 = entry.0.min(temp);
+            entry.1 = entry.1.max(temp);
+        }
+    }
+
+    // 所有传感器读数，按分组和名称排序
+    pub fn readings(&self) -> Vec<SensorReading> {
+        let mut readings: Vec<SensorReading> = keyed_components(&self.components)
+            .into_iter()
+            .map(|(key, component)| {
+                let label = component.label().to_string();
+                let alias = self.aliases.get(&label);
+                let (session_min, session_max) = match self.stats.get(&key) {
+                    Some(&(min, max)) => (Some(min), Some(max)),
+                    None => (None, None),
+                };
+
+                SensorReading {
+                    name: alias
+                        .and_then(|a| a.name.clone())
+                        .unwrap_or_else(|| label.clone()),
+                    group: alias
+                        .and_then(|a| a.group.clone())
+                        .unwrap_or_else(|| DEFAULT_GROUP.to_string()),
+                    label,
+                    current: finite(component.temperature()),
+                    session_min,
+                    session_max,
+                    max: finite(component.max()),
+                    critical: component.critical().and_then(finite),
+                }
+            })
+            .collect();
+
+        readings.sort_by(|a, b| a.group.cmp(&b.group).then_with(|| a.name.cmp(&b.name)));
+        readings
+    }
+}
+
+// 同名传感器（例如多个芯片上的 "Core 0"）用出现序号区分
+fn keyed_components(components: &Components) -> Vec<(String, &sysinfo::Component)> {
+    let mut seen: HashMap<&str, usize> = HashMap::new();
+    components
+        .iter()
+        .map(|component| {
+            let count = seen.entry(component.label()).or_insert(0);
+            let key = format!("{}#{}", component.label(), count);
+            *count += 1;
+            (key, component)
+        })
+        .collect()
+}
+
+fn finite(value: f32) -> Option<f32> {
+    if value.is_finite() {
+        Some(value)
+    } else {
+        None
+    }
+}
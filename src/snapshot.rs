@@ -0,0 +1,406 @@
+// Point-in-time snapshot of all monitor readings
+use crate::battery::{self, PowerStatus};
+use crate::config::{self, SensorConfig};
+use crate::sensors::{SensorReading, SensorTracker};
+use crate::system_report::SystemReport;
+use anyhow::{Context, Result};
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use sysinfo::{Disks, Networks, ProcessesToUpdate, System};
+
+// 快照中保留的进程数量
+const TOP_PROCESS_COUNT: usize = 20;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub timestamp: String,
+    pub cpu: CpuSnapshot,
+    pub memory: MemorySnapshot,
+    pub disks: Vec<DiskSnapshot>,
+    pub networks: Vec<NetworkSnapshot>,
+    pub sensors: Vec<SensorReading>,
+    pub power: PowerStatus,
+    pub system: SystemReport,
+    pub top_cpu_processes: Vec<ProcessSnapshot>,
+    pub top_memory_processes: Vec<ProcessSnapshot>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CpuSnapshot {
+    pub brand: String,
+    pub usage: f32,
+    pub per_core: Vec<f32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemorySnapshot {
+    pub total: u64,
+    pub used: u64,
+    pub available: u64,
+    pub free: u64,
+    pub swap_total: u64,
+    pub swap_used: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiskSnapshot {
+    pub mount_point: String,
+    pub name: String,
+    pub file_system: String,
+    pub total: u64,
+    pub available: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkSnapshot {
+    pub name: String,
+    pub received_per_sec: u64,
+    pub transmitted_per_sec: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessSnapshot {
+    pub pid: u32,
+    pub name: String,
+    pub cpu_usage: f32,
+    pub memory: u64,
+}
+
+// 固定内容的快照，用于界面和序列化测试
+#[cfg(test)]
+impl Snapshot {
+    pub fn sample() -> Self {
+        const GB: u64 = 1024 * 1024 * 1024;
+        Snapshot {
+            timestamp: "2026-01-15 09:30:00".to_string(),
+            cpu: CpuSnapshot {
+                brand: "Test CPU 8-Core".to_string(),
+                usage: 42.5,
+                per_core: vec![40.0, 45.0],
+            },
+            memory: MemorySnapshot {
+                total: 16 * GB,
+                used: 8 * GB,
+                available: 8 * GB,
+                free: 4 * GB,
+                swap_total: 0,
+                swap_used: 0,
+            },
+            disks: vec![DiskSnapshot {
+                mount_point: "/data".to_string(),
+                name: "sda1".to_string(),
+                file_system: "ext4".to_string(),
+                total: 500 * GB,
+                available: 125 * GB,
+            }],
+            networks: vec![NetworkSnapshot {
+                name: "eth0".to_string(),
+                received_per_sec: 2048,
+                transmitted_per_sec: 1024,
+            }],
+            sensors: Vec::new(),
+            power: PowerStatus::default(),
+            system: SystemReport {
+                hostname: Some("fixture-host".to_string()),
+                os_name: Some("TestOS".to_string()),
+                os_version: Some("1.0".to_string()),
+                long_os_version: None,
+                kernel_version: None,
+                arch: "x86_64".to_string(),
+                cpu_brand: Some("Test CPU 8-Core".to_string()),
+                physical_cores: Some(1),
+                logical_cores: 2,
+                total_memory: 16 * GB,
+                boot_time: 0,
+                uptime: 3600,
+                load_average: None,
+                users: Vec::new(),
+                process_count: 1,
+                lemo_version: "0.0.0".to_string(),
+            },
+            top_cpu_processes: Vec::new(),
+            top_memory_processes: vec![ProcessSnapshot {
+                pid: 1,
+                name: "init".to_string(),
+                cpu_usage: 0.5,
+                memory: 64 * 1024 * 1024,
+            }],
+        }
+    }
+}
+
+// 实时采集器：持有 sysinfo 对象，每次采样时刷新并生成快照
+pub struct LiveCollector {
+    sys: System,
+    networks: Networks,
+    sensors: SensorTracker,
+    report: SystemReport,
+    // 网络数据是两次刷新之间的增量，需要除以间隔得到速度
+    network_interval: Duration,
+    last_network_refresh: Instant,
+}
+
+impl LiveCollector {
+    pub fn new(sensor_aliases: BTreeMap<String, SensorConfig>) -> Self {
+        let sys = System::new_all();
+        let report = SystemReport::collect(&sys);
+        Self {
+            sys,
+            networks: Networks::new_with_refreshed_list(),
+            sensors: SensorTracker::new(sensor_aliases),
+            report,
+            network_interval: Duration::from_secs(1),
+            last_network_refresh: Instant::now(),
+        }
+    }
+
+    // 刷新所有数据源并生成新的快照
+    pub fn sample(&mut self) -> Snapshot {
+        self.sys.refresh_cpu_usage();
+        self.sys.refresh_memory();
+        self.sys.refresh_processes(ProcessesToUpdate::All, true);
+        self.networks.refresh(); // 刷新网络数据以计算速度
+        self.network_interval = self.last_network_refresh.elapsed();
+        self.last_network_refresh = Instant::now();
+        self.sensors.refresh();
+        self.report.refresh(&self.sys);
+        self.snapshot()
+    }
+
+    // 不刷新，直接用当前数据生成快照
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot::capture(
+            &self.sys,
+            &self.networks,
+            self.network_interval,
+            &self.sensors,
+            &battery::read_power_status(),
+            &self.report,
+        )
+    }
+}
+
+// 当前所有磁盘的容量和可用空间（按挂载点排序）
+pub fn read_disks() -> Vec<DiskSnapshot> {
+    let mut disks: Vec<DiskSnapshot> = Disks::new_with_refreshed_list()
+        .iter()
+        .map(|disk| DiskSnapshot {
+            mount_point: disk.mount_point().display().to_string(),
+            name: disk.name().to_string_lossy().to_string(),
+            file_system: disk.file_system().to_string_lossy().to_string(),
+            total: disk.total_space(),
+            available: disk.available_space(),
+        })
+        .collect();
+    disks.sort_by(|a, b| a.mount_point.cmp(&b.mount_point));
+    disks
+}
+
+impl Snapshot {
+    pub fn capture(
+        sys: &System,
+        networks: &Networks,
+        network_interval: Duration,
+        sensors: &SensorTracker,
+        power: &PowerStatus,
+        report: &SystemReport,
+    ) -> Self {
+        let per_core: Vec<f32> = sys.cpus().iter().map(|cpu| cpu.cpu_usage()).collect();
+        let usage = if per_core.is_empty() {
+            0.0
+        } else {
+            per_core.iter().sum::<f32>() / per_core.len() as f32
+        };
+
+        let secs = network_interval.as_secs_f64().max(0.001);
+        let mut network_list: Vec<NetworkSnapshot> = networks
+            .iter()
+            .map(|(name, data)| NetworkSnapshot {
+                name: name.clone(),
+                received_per_sec: (data.received() as f64 / secs) as u64,
+                transmitted_per_sec: (data.transmitted() as f64 / secs) as u64,
+            })
+            .collect();
+        network_list.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let processes: Vec<ProcessSnapshot> = sys
+            .processes()
+            .values()
+            .map(|p| ProcessSnapshot {
+                pid: p.pid().as_u32(),
+                name: p.name().to_string_lossy().to_string(),
+                cpu_usage: p.cpu_usage(),
+                memory: p.memory(),
+            })
+            .collect();
+
+        let mut top_cpu_processes = processes.clone();
+        top_cpu_processes.sort_by(|a, b| b.cpu_usage.total_cmp(&a.cpu_usage));
+        top_cpu_processes.truncate(TOP_PROCESS_COUNT);
+
+        let mut top_memory_processes = processes;
+        top_memory_processes.sort_by_key(|p| std::cmp::Reverse(p.memory));
+        top_memory_processes.truncate(TOP_PROCESS_COUNT);
+
+        Self {
+            timestamp: Local::now().to_rfc3339(),
+            cpu: CpuSnapshot {
+                brand: sys
+                    .cpus()
+                    .first()
+                    .map(|cpu| cpu.brand().trim().to_string())
+                    .unwrap_or_default(),
+                usage,
+                per_core,
+            },
+            memory: MemorySnapshot {
+                total: sys.total_memory(),
+                used: sys.used_memory(),
+                available: sys.available_memory(),
+                free: sys.free_memory(),
+                swap_total: sys.total_swap(),
+                swap_used: sys.used_swap(),
+            },
+            disks: read_disks(),
+            networks: network_list,
+            sensors: sensors.readings(),
+            power: power.clone(),
+            system: report.clone(),
+            top_cpu_processes,
+            top_memory_processes,
+        }
+    }
+
+    // 纯文本格式，便于直接粘贴到 bug 报告中
+    pub fn to_text(&self) -> String {
+        const GB: f64 = 1024.0 * 1024.0 * 1024.0;
+        const MB: f64 = 1024.0 * 1024.0;
+
+        let mut out = String::new();
+        let _ = writeln!(out, "lemo {} monitor snapshot - {}", self.system.lemo_version, self.timestamp);
+        let _ = writeln!(out, "═══════════════════════════════════════════════════");
+
+        let _ = writeln!(out, "\n[System]");
+        let _ = writeln!(out, "Host:      {}", self.system.hostname.as_deref().unwrap_or("Unknown"));
+        let _ = writeln!(out, "OS:        {}", self.system.os_display());
+        let _ = writeln!(out, "Kernel:    {}", self.system.kernel_version.as_deref().unwrap_or("Unknown"));
+        let _ = writeln!(out, "Uptime:    {}", self.system.uptime_display());
+        if let Some(load) = self.system.load_display() {
+            let _ = writeln!(out, "Load:      {}", load);
+        }
+        let _ = writeln!(out, "Processes: {}", self.system.process_count);
+
+        let _ = writeln!(out, "\n[CPU]");
+        let _ = writeln!(out, "{} - {:.1}%", self.cpu.brand, self.cpu.usage);
+        let cores: Vec<String> = self.cpu.per_core.iter().map(|u| format!("{:.0}%", u)).collect();
+        let _ = writeln!(out, "Per core: {}", cores.join(" "));
+
+        let _ = writeln!(out, "\n[Memory]");
+        let _ = writeln!(
+            out,
+            "Used: {:.2} / {:.2} GB, Available: {:.2} GB, Free: {:.2} GB",
+            self.memory.used as f64 / GB,
+            self.memory.total as f64 / GB,
+            self.memory.available as f64 / GB,
+            self.memory.free as f64 / GB
+        );
+        let _ = writeln!(
+            out,
+            "Swap/Page file: {:.2} / {:.2} GB",
+            self.memory.swap_used as f64 / GB,
+            self.memory.swap_total as f64 / GB
+        );
+
+        let _ = writeln!(out, "\n[Disks]");
+        for disk in &self.disks {
+            let _ = writeln!(
+                out,
+                "{:<12} {:.1} / {:.1} GB used ({})",
+                disk.mount_point,
+                disk.total.saturating_sub(disk.available) as f64 / GB,
+                disk.total as f64 / GB,
+                disk.file_system
+            );
+        }
+
+        let _ = writeln!(out, "\n[Network] (bytes/s)");
+        for network in &self.networks {
+            let _ = writeln!(
+                out,
+                "{:<24} down {:>10}  up {:>10}",
+                network.name, network.received_per_sec, network.transmitted_per_sec
+            );
+        }
+
+        let _ = writeln!(out, "\n[Sensors]");
+        for sensor in &self.sensors {
+            let temp = sensor
+                .current
+                .map(|t| format!("{:.1}°C", t))
+                .unwrap_or_else(|| "-".to_string());
+            let _ = writeln!(out, "{:<10} {:<28} {}", sensor.group, sensor.name, temp);
+        }
+
+        let _ = writeln!(out, "\n[Power]");
+        if self.power.batteries.is_empty() {
+            let _ = writeln!(out, "No battery");
+        }
+        for battery in &self.power.batteries {
+            let _ = writeln!(
+                out,
+                "{}: {} {}",
+                battery.name,
+                battery.percent.map(|p| format!("{:.0}%", p)).unwrap_or_else(|| "?%".to_string()),
+                battery.state.label()
+            );
+        }
+
+        let _ = writeln!(out, "\n[Top processes by CPU]");
+        for p in &self.top_cpu_processes {
+            let _ = writeln!(out, "{:<8} {:<32} {:>6.1}% {:>10.1} MB", p.pid, p.name, p.cpu_usage, p.memory as f64 / MB);
+        }
+
+        let _ = writeln!(out, "\n[Top processes by memory]");
+        for p in &self.top_memory_processes {
+            let _ = writeln!(out, "{:<8} {:<32} {:>6.1}% {:>10.1} MB", p.pid, p.name, p.cpu_usage, p.memory as f64 / MB);
+        }
+
+        out
+    }
+}
+
+// 将快照写入 <数据目录>/snapshots，同时生成 .txt 和 .json，返回文本文件路径
+pub fn save_snapshot(snapshot: &Snapshot) -> Result<PathBuf> {
+    let dir = config::data_dir()
+        .map(|d| d.join("snapshots"))
+        .ok_or_else(|| anyhow::anyhow!("Cannot determine lemo data directory"))?;
+    fs::create_dir_all(&dir).with_context(|| format!("Cannot create {}", dir.display()))?;
+
+    let stem = format!("snapshot-{}", Local::now().format("%Y%m%d-%H%M%S"));
+    let text_path = dir.join(format!("{}.txt", stem));
+    let json_path = dir.join(format!("{}.json", stem));
+
+    fs::write(&text_path, snapshot.to_text())?;
+    fs::write(&json_path, serde_json::to_string_pretty(snapshot)?)?;
+
+    Ok(text_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_report_tolerates_inconsistent_disk_sizes() {
+        let mut snapshot = Snapshot::sample();
+        // 远程或回放的快照可能包含可用空间大于总空间的磁盘
+        snapshot.disks[0].available = snapshot.disks[0].total + 1;
+        let text = snapshot.to_text();
+        assert!(text.contains("/data        0.0 / 500.0 GB used (ext4)"), "{}", text);
+    }
+}
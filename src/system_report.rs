@@ -0,0 +1,176 @@
+// System information report (monitor panel and `lemo sysinfo`)
+use anyhow::Result;
+use chrono::{Local, TimeZone};
+use serde::{Deserialize, Serialize};
+use sysinfo::System;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SystemReport {
+    pub hostname: Option<String>,
+    pub os_name: Option<String>,
+    pub os_version: Option<String>,
+    pub long_os_version: Option<String>,
+    pub kernel_version: Option<String>,
+    pub arch: String,
+    pub cpu_brand: Option<String>,
+    pub physical_cores: Option<usize>,
+    pub logical_cores: usize,
+    pub total_memory: u64,
+    pub boot_time: u64,
+    pub uptime: u64,
+    // Windows 不提供负载均值
+    pub load_average: Option<[f64; 3]>,
+    pub users: Vec<String>,
+    pub process_count: usize,
+    pub lemo_version: String,
+}
+
+impl SystemReport {
+    // 需要 sys 中已经刷新过 CPU 和进程列表
+    pub fn collect(sys: &System) -> Self {
+        let mut report = Self {
+            hostname: System::host_name(),
+            os_name: System::name(),
+            os_version: System::os_version(),
+            long_os_version: System::long_os_version(),
+            kernel_version: System::kernel_version(),
+            arch: System::cpu_arch().unwrap_or_else(|| std::env::consts::ARCH.to_string()),
+            cpu_brand: sys.cpus().first().map(|cpu| cpu.brand().trim().to_string()),
+            physical_cores: sys.physical_core_count(),
+            logical_cores: sys.cpus().len(),
+            total_memory: sys.total_memory(),
+            boot_time: System::boot_time(),
+            uptime: 0,
+            load_average: None,
+            users: logged_in_users(sys),
+            process_count: 0,
+            lemo_version: env!("CARGO_PKG_VERSION").to_string(),
+        };
+        report.refresh(sys);
+        report
+    }
+
+    // 只更新会随时间变化的字段（监控界面每秒调用）
+    pub fn refresh(&mut self, sys: &System) {
+        self.uptime = System::uptime();
+        self.process_count = sys.processes().len();
+        self.load_average = if cfg!(windows) {
+            None
+        } else {
+            let load = System::load_average();
+            Some([load.one, load.five, load.fifteen])
+        };
+    }
+
+    pub fn os_display(&self) -> String {
+        match (&self.long_os_version, &self.os_name, &self.os_version) {
+            (Some(long), _, Some(version)) if !long.contains(version.as_str()) => {
+                format!("{} ({})", long, version)
+            }
+            (Some(long), _, _) => long.clone(),
+            (None, Some(name), Some(version)) => format!("{} {}", name, version),
+            (None, Some(name), None) => name.clone(),
+            _ => std::env::consts::OS.to_string(),
+        }
+    }
+
+    pub fn boot_time_display(&self) -> String {
+        match Local.timestamp_opt(self.boot_time as i64, 0).single() {
+            Some(time) => time.format("%Y-%m-%d %H:%M").to_string(),
+            None => "Unknown".to_string(),
+        }
+    }
+
+    pub fn uptime_display(&self) -> String {
+        let days = self.uptime / 86400;
+        let hours = (self.uptime % 86400) / 3600;
+        let minutes = (self.uptime % 3600) / 60;
+        format!("{}d {}h {}m", days, hours, minutes)
+    }
+
+    pub fn load_display(&self) -> Option<String> {
+        self.load_average
+            .map(|[one, five, fifteen]| format!("{:.2} {:.2} {:.2}", one, five, fifteen))
+    }
+
+    pub fn users_display(&self) -> String {
+        if self.users.is_empty() {
+            "-".to_string()
+        } else {
+            self.users.join(", ")
+        }
+    }
+}
+
+// 当前登录的用户
+// Windows：拥有 explorer.exe 进程的用户即为交互式登录用户
+#[cfg(windows)]
+fn logged_in_users(sys: &System) -> Vec<String> {
+    let users = sysinfo::Users::new_with_refreshed_list();
+    let mut names: Vec<String> = sys
+        .processes()
+        .values()
+        .filter(|p| p.name().eq_ignore_ascii_case("explorer.exe"))
+        .filter_map(|p| p.user_id())
+        .filter_map(|uid| users.get_user_by_id(uid))
+        .map(|user| user.name().to_string())
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+// 其他平台：使用 `who` 列出登录会话
+#[cfg(not(windows))]
+fn logged_in_users(_sys: &System) -> Vec<String> {
+    use std::process::Command;
+
+    let output = match Command::new("who").output() {
+        Ok(output) if output.status.success() => output,
+        _ => return Vec::new(),
+    };
+
+    let mut names: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.split_whitespace().next())
+        .map(|name| name.to_string())
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+// Print the system report to the console (`lemo sysinfo`)
+pub fn print_system_report(json: bool) -> Result<()> {
+    let sys = System::new_all();
+    let report = SystemReport::collect(&sys);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    let unknown = || "Unknown".to_string();
+    println!("🍋 lemo {} - System Information", report.lemo_version);
+    println!("═══════════════════════════════════════════════════");
+    println!("Hostname:     {}", report.hostname.clone().unwrap_or_else(unknown));
+    println!("OS:           {}", report.os_display());
+    println!("Kernel:       {}", report.kernel_version.clone().unwrap_or_else(unknown));
+    println!("Arch:         {}", report.arch);
+    println!("CPU:          {}", report.cpu_brand.clone().unwrap_or_else(unknown));
+    println!(
+        "Cores:        {} physical, {} logical",
+        report.physical_cores.unwrap_or(0),
+        report.logical_cores
+    );
+    println!("Memory:       {:.1} GB", report.total_memory as f64 / 1024.0 / 1024.0 / 1024.0);
+    println!("Boot time:    {}", report.boot_time_display());
+    println!("Uptime:       {}", report.uptime_display());
+    if let Some(load) = report.load_display() {
+        println!("Load average: {}", load);
+    }
+    println!("Users:        {}", report.users_display());
+    println!("Processes:    {}", report.process_count);
+
+    Ok(())
+}
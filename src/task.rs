@@ -0,0 +1,389 @@
+// Task runner: every long-running action streams events from a worker thread
+//
+// 任务在后台线程中执行，通过 TaskContext 发送输出行和进度，并轮询取消标志。
+// 命令行（run_console）和 TUI（run_in_tui）消费的是同一组事件，因此两者行为一致。
+use crate::theme::{self, theme};
+use crate::viewer::show_scrollable_viewer;
+use anyhow::Result;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::{
+    backend::Backend,
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Gauge, Paragraph, Wrap},
+    Terminal,
+};
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+pub enum TaskEvent {
+    Line(String),
+    Progress(Progress),
+}
+
+#[derive(Debug, Clone)]
+pub struct Progress {
+    pub done: usize,
+    pub total: usize,
+    pub label: String,
+}
+
+impl Progress {
+    pub fn ratio(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            (self.done as f64 / self.total as f64).clamp(0.0, 1.0)
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TaskStatus {
+    Running,
+    Succeeded,
+    Failed(String),
+    Cancelled,
+}
+
+// 任务被用户取消时返回的错误
+#[derive(Debug)]
+pub struct Cancelled;
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Cancelled by user")
+    }
+}
+
+impl std::error::Error for Cancelled {}
+
+// 传给任务函数：发送输出和进度，检查是否已被取消
+pub struct TaskContext {
+    events: Sender<TaskEvent>,
+    cancel: Arc<AtomicBool>,
+}
+
+impl TaskContext {
+    pub fn println(&self, line: impl Into<String>) {
+        let _ = self.events.send(TaskEvent::Line(line.into()));
+    }
+
+    pub fn report_progress(&self, done: usize, total: usize, label: &str) {
+        let _ = self.events.send(TaskEvent::Progress(Progress {
+            done,
+            total,
+            label: label.to_string(),
+        }));
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.load(Ordering::Relaxed)
+    }
+}
+
+type TaskFn = Box<dyn FnOnce(&mut TaskContext) -> Result<()> + Send>;
+
+pub struct Task {
+    pub title: String,
+    run: TaskFn,
+}
+
+impl Task {
+    pub fn new(title: &str, run: impl FnOnce(&mut TaskContext) -> Result<()> + Send + 'static) -> Self {
+        Self {
+            title: title.to_string(),
+            run: Box::new(run),
+        }
+    }
+}
+
+// 正在执行（或已结束）的任务，主线程通过 update() 收集事件
+pub struct TaskHandle {
+    pub title: String,
+    pub lines: Vec<String>,
+    pub progress: Option<Progress>,
+    pub status: TaskStatus,
+    started: Instant,
+    elapsed: Option<Duration>,
+    events: Receiver<TaskEvent>,
+    cancel: Arc<AtomicBool>,
+    worker: Option<JoinHandle<Result<()>>>,
+}
+
+impl TaskHandle {
+    pub fn spawn(task: Task) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let mut context = TaskContext {
+            events: tx,
+            cancel: Arc::clone(&cancel),
+        };
+        let run = task.run;
+        let worker = thread::spawn(move || run(&mut context));
+
+        Self {
+            title: task.title,
+            lines: Vec::new(),
+            progress: None,
+            status: TaskStatus::Running,
+            started: Instant::now(),
+            elapsed: None,
+            events: rx,
+            cancel,
+            worker: Some(worker),
+        }
+    }
+
+    // 请求取消，任务在下一个检查点停止
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    pub fn cancel_requested(&self) -> bool {
+        self.cancel.load(Ordering::Relaxed)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.status != TaskStatus::Running
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed.unwrap_or_else(|| self.started.elapsed())
+    }
+
+    // 收集待处理事件，返回新增的事件（命令行模式直接打印）
+    pub fn update(&mut self) -> Vec<TaskEvent> {
+        let mut received = Vec::new();
+        while let Ok(event) = self.events.try_recv() {
+            match &event {
+                TaskEvent::Line(line) => self.lines.push(line.clone()),
+                TaskEvent::Progress(progress) => self.progress = Some(progress.clone()),
+            }
+            received.push(event);
+        }
+
+        if self.worker.as_ref().is_some_and(|w| w.is_finished()) {
+            let result = self.worker.take().map(|w| w.join());
+            // 线程结束后再取一次，避免丢失最后几行
+            received.extend(self.update());
+            self.elapsed = Some(self.started.elapsed());
+            self.status = match result {
+                Some(Ok(Ok(()))) => TaskStatus::Succeeded,
+                Some(Ok(Err(e))) if e.downcast_ref::<Cancelled>().is_some() => TaskStatus::Cancelled,
+                Some(Ok(Err(e))) => TaskStatus::Failed(format!("{:#}", e)),
+                Some(Err(_)) | None => TaskStatus::Failed("Task thread panicked".to_string()),
+            };
+        }
+        received
+    }
+
+    pub fn status_line(&self) -> String {
+        let seconds = self.elapsed().as_secs_f64();
+        match &self.status {
+            TaskStatus::Running if self.cancel_requested() => format!("⏳ Cancelling... [{:.1}s]", seconds),
+            TaskStatus::Running => format!("⏳ Running... [{:.1}s]", seconds),
+            TaskStatus::Succeeded => format!("✅ Operation completed in {:.2}s", seconds),
+            TaskStatus::Failed(error) => format!("❌ Error: {}", error),
+            TaskStatus::Cancelled => format!("⚠️ Cancelled after {:.2}s", seconds),
+        }
+    }
+}
+
+// 命令行模式：输出直接打印到终端，进度只在阶段变化时打印一行
+pub fn run_console(task: Task) -> Result<()> {
+    let mut handle = TaskHandle::spawn(task);
+    let mut last_label = String::new();
+    loop {
+        for event in handle.update() {
+            match event {
+                TaskEvent::Line(line) => println!("{}", line),
+                TaskEvent::Progress(progress) if progress.label != last_label && progress.done < progress.total => {
+                    println!("⏳ [{}/{}] {}", progress.done + 1, progress.total, progress.label);
+                    last_label = progress.label;
+                }
+                TaskEvent::Progress(_) => {}
+            }
+        }
+        if handle.is_finished() {
+            break;
+        }
+        thread::sleep(Duration::from_millis(50));
+    }
+
+    match handle.status {
+        TaskStatus::Failed(error) => Err(anyhow::anyhow!(error)),
+        TaskStatus::Cancelled => Err(Cancelled.into()),
+        _ => Ok(()),
+    }
+}
+
+// TUI 模式：实时显示输出和进度，Esc / C / Ctrl+C 取消，结束后进入可滚动查看器
+pub fn run_in_tui<B: Backend>(terminal: &mut Terminal<B>, task: Task) -> Result<TaskStatus> {
+    let mut handle = TaskHandle::spawn(task);
+
+    loop {
+        handle.update();
+        if handle.is_finished() {
+            break;
+        }
+
+        terminal.draw(|f| {
+            render_task(f, &handle);
+            theme::finish_frame(f);
+        })?;
+
+        if event::poll(Duration::from_millis(100))? {
+            if let Event::Key(key) = event::read()? {
+                // Ctrl+C 在 raw 模式下同样以 'c' 键到达
+                if key.kind == KeyEventKind::Press
+                    && matches!(key.code, KeyCode::Esc | KeyCode::Char('c') | KeyCode::Char('C'))
+                {
+                    handle.cancel();
+                }
+            }
+        }
+    }
+
+    let mut lines = vec![format!("▶ {}", handle.title), String::new()];
+    lines.append(&mut handle.lines);
+    lines.push(String::new());
+    lines.push(handle.status_line());
+    show_scrollable_viewer(terminal, &lines)?;
+    Ok(handle.status)
+}
+
+pub fn render_task(f: &mut ratatui::Frame, handle: &TaskHandle) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(3)])
+        .split(f.area());
+    render_task_in(f, chunks[0], handle);
+
+    let footer = Paragraph::new("Esc/C: Cancel")
+        .style(Style::default().fg(theme().muted))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(footer, chunks[1]);
+}
+
+// 输出区 + 进度条，footer 由调用方决定（任务队列界面复用）
+pub fn render_task_in(f: &mut ratatui::Frame, area: ratatui::layout::Rect, handle: &TaskHandle) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(3), Constraint::Length(3)])
+        .split(area);
+
+    // 自动滚动到最新内容
+    let visible_height = chunks[0].height.saturating_sub(2) as usize;
+    let visible_lines: Vec<Line> = handle
+        .lines
+        .iter()
+        .skip(handle.lines.len().saturating_sub(visible_height))
+        .map(|s| Line::from(s.clone()))
+        .collect();
+    let output = Paragraph::new(visible_lines)
+        .block(
+            Block::default()
+                .title(format!(" {} ", handle.title))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme().title)),
+        )
+        .wrap(Wrap { trim: false });
+    f.render_widget(output, chunks[0]);
+
+    let (ratio, label) = match &handle.progress {
+        Some(progress) if progress.done < progress.total => (
+            progress.ratio(),
+            format!("{}/{} {}", progress.done + 1, progress.total, progress.label),
+        ),
+        Some(progress) => (progress.ratio(), progress.label.clone()),
+        None => (0.0, String::new()),
+    };
+    let status_color = match handle.status {
+        TaskStatus::Running => theme().title,
+        TaskStatus::Succeeded => theme().ok,
+        TaskStatus::Failed(_) => theme().critical,
+        TaskStatus::Cancelled => theme().warn,
+    };
+    let gauge = Gauge::default()
+        .block(Block::default().title(" Progress ").borders(Borders::ALL))
+        .gauge_style(Style::default().fg(theme().primary))
+        .ratio(ratio)
+        .label(label);
+    f.render_widget(gauge, chunks[1]);
+
+    let status = Paragraph::new(handle.status_line())
+        .style(Style::default().fg(status_color).add_modifier(Modifier::BOLD))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL));
+    f.render_widget(status, chunks[2]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 等待任务结束（最多 5 秒）
+    fn finish(handle: &mut TaskHandle) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !handle.is_finished() && Instant::now() < deadline {
+            handle.update();
+            thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    #[test]
+    fn cancelling_a_running_task_reports_cancelled() {
+        let (started_tx, started_rx) = mpsc::channel();
+        let mut handle = TaskHandle::spawn(Task::new("Cancel me", move |ctx| {
+            ctx.println("started");
+            ctx.report_progress(1, 2, "working");
+            started_tx.send(()).unwrap();
+            while !ctx.is_cancelled() {
+                thread::sleep(Duration::from_millis(1));
+            }
+            ctx.println("stopping");
+            Err(Cancelled.into())
+        }));
+
+        started_rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        handle.update();
+        assert_eq!(handle.status, TaskStatus::Running);
+        assert!(handle.status_line().starts_with("⏳ Running..."));
+
+        handle.cancel();
+        assert!(handle.cancel_requested());
+        assert!(handle.status_line().starts_with("⏳ Cancelling..."));
+        finish(&mut handle);
+
+        assert_eq!(handle.status, TaskStatus::Cancelled);
+        assert_eq!(handle.lines, vec!["started", "stopping"]);
+        assert_eq!(handle.progress.as_ref().map(|p| p.label.as_str()), Some("working"));
+        assert!(handle.status_line().starts_with("⚠️ Cancelled after"));
+    }
+
+    #[test]
+    fn reports_success_and_failure() {
+        let mut handle = TaskHandle::spawn(Task::new("Ok", |ctx| {
+            ctx.report_progress(1, 2, "half");
+            Ok(())
+        }));
+        finish(&mut handle);
+        assert_eq!(handle.status, TaskStatus::Succeeded);
+        assert_eq!(handle.progress.as_ref().map(Progress::ratio), Some(0.5));
+
+        let mut handle = TaskHandle::spawn(Task::new("Fails", |_| Err(anyhow::anyhow!("disk full"))));
+        finish(&mut handle);
+        assert_eq!(handle.status, TaskStatus::Failed("disk full".to_string()));
+        assert_eq!(handle.status_line(), "❌ Error: disk full");
+        // 取消标志只在任务检查时生效，已结束的任务不受影响
+        handle.cancel();
+        assert_eq!(handle.status, TaskStatus::Failed("disk full".to_string()));
+    }
+}
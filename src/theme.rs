@@ -0,0 +1,288 @@
+// TUI themes: colors, severity levels and the ASCII fallback
+//
+// 内置主题：dark（默认）、light、high-contrast、ascii（不使用 emoji 和框线字符）
+// 自定义主题写在 config.toml 中：
+//
+// theme = "mine"
+//
+// [themes.mine]
+// base = "light"        # 从哪个内置主题继承，默认 dark
+// accent = "#005f87"    # 颜色名（red、dark gray）、#rrggbb 或 0-255 索引
+// ascii = true
+use crate::config::{Config, ThemeConfig};
+use anyhow::Result;
+use ratatui::buffer::Buffer;
+use ratatui::style::Color;
+use ratatui::Frame;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+pub const BUILTIN_THEMES: [&str; 4] = ["dark", "light", "high-contrast", "ascii"];
+
+static CURRENT: OnceLock<Theme> = OnceLock::new();
+
+#[derive(Debug, Clone)]
+pub struct Theme {
+    // 边框和标题
+    pub accent: Color,
+    // 界面标题、提示信息
+    pub title: Color,
+    pub text: Color,
+    pub muted: Color,
+    pub selected_fg: Color,
+    pub selected_bg: Color,
+    // 图表曲线、上传/下载速度等数据系列
+    pub primary: Color,
+    pub secondary: Color,
+    pub ok: Color,
+    pub warn: Color,
+    pub critical: Color,
+    // 为 None 时使用终端自身的背景色
+    pub background: Option<Color>,
+    // 把 emoji 和框线字符替换为 ASCII
+    pub ascii: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Ok,
+    Warn,
+    Critical,
+}
+
+impl Severity {
+    // 数值超过 warn / critical 阈值时升级
+    pub fn from_value(value: f64, warn: f64, critical: f64) -> Self {
+        if value > critical {
+            Severity::Critical
+        } else if value > warn {
+            Severity::Warn
+        } else {
+            Severity::Ok
+        }
+    }
+}
+
+impl Theme {
+    pub fn dark() -> Self {
+        Self {
+            accent: Color::Cyan,
+            title: Color::Yellow,
+            text: Color::White,
+            muted: Color::Gray,
+            selected_fg: Color::Yellow,
+            selected_bg: Color::DarkGray,
+            primary: Color::Green,
+            secondary: Color::Magenta,
+            ok: Color::Green,
+            warn: Color::Yellow,
+            critical: Color::Red,
+            background: None,
+            ascii: false,
+        }
+    }
+
+    pub fn light() -> Self {
+        Self {
+            accent: Color::Blue,
+            title: Color::Rgb(135, 0, 135),
+            text: Color::Black,
+            muted: Color::Rgb(88, 88, 88),
+            selected_fg: Color::Black,
+            selected_bg: Color::Rgb(255, 215, 135),
+            primary: Color::Rgb(0, 135, 0),
+            secondary: Color::Rgb(135, 0, 175),
+            ok: Color::Rgb(0, 135, 0),
+            warn: Color::Rgb(175, 95, 0),
+            critical: Color::Rgb(215, 0, 0),
+            background: Some(Color::Rgb(250, 250, 250)),
+            ascii: false,
+        }
+    }
+
+    pub fn high_contrast() -> Self {
+        Self {
+            accent: Color::White,
+            title: Color::LightYellow,
+            text: Color::White,
+            muted: Color::White,
+            selected_fg: Color::Black,
+            selected_bg: Color::White,
+            primary: Color::LightGreen,
+            secondary: Color::LightMagenta,
+            ok: Color::LightGreen,
+            warn: Color::LightYellow,
+            critical: Color::LightRed,
+            background: Some(Color::Black),
+            ascii: false,
+        }
+    }
+
+    pub fn ascii() -> Self {
+        Self {
+            ascii: true,
+            ..Self::dark()
+        }
+    }
+
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name {
+            "dark" => Some(Self::dark()),
+            "light" => Some(Self::light()),
+            "high-contrast" => Some(Self::high_contrast()),
+            "ascii" | "no-emoji" => Some(Self::ascii()),
+            _ => None,
+        }
+    }
+
+    // 按名称查找主题：先找 config.toml 中的自定义主题，再找内置主题
+    pub fn resolve(name: &str, config: &Config) -> Result<Self> {
+        if let Some(custom) = config.themes.get(name) {
+            return Self::from_config(name, custom);
+        }
+        Self::builtin(name).ok_or_else(|| {
+            let mut names: Vec<&str> = BUILTIN_THEMES.to_vec();
+            names.extend(config.themes.keys().map(|k| k.as_str()));
+            anyhow::anyhow!("Unknown theme '{}' (available: {})", name, names.join(", "))
+        })
+    }
+
+    fn from_config(name: &str, custom: &ThemeConfig) -> Result<Self> {
+        let base = custom.base.as_deref().unwrap_or("dark");
+        let mut theme = Self::builtin(base)
+            .ok_or_else(|| anyhow::anyhow!("Theme '{}': unknown base theme '{}'", name, base))?;
+
+        let parse = |field: &str, value: &Option<String>| -> Result<Option<Color>> {
+            value
+                .as_deref()
+                .map(|v| {
+                    Color::from_str(v)
+                        .map_err(|_| anyhow::anyhow!("Theme '{}': invalid color '{}' for {}", name, v, field))
+                })
+                .transpose()
+        };
+
+        let fields: [(&str, &Option<String>, &mut Color); 11] = [
+            ("accent", &custom.accent, &mut theme.accent),
+            ("title", &custom.title, &mut theme.title),
+            ("text", &custom.text, &mut theme.text),
+            ("muted", &custom.muted, &mut theme.muted),
+            ("selected_fg", &custom.selected_fg, &mut theme.selected_fg),
+            ("selected_bg", &custom.selected_bg, &mut theme.selected_bg),
+            ("primary", &custom.primary, &mut theme.primary),
+            ("secondary", &custom.secondary, &mut theme.secondary),
+            ("ok", &custom.ok, &mut theme.ok),
+            ("warn", &custom.warn, &mut theme.warn),
+            ("critical", &custom.critical, &mut theme.critical),
+        ];
+        for (field, value, target) in fields {
+            if let Some(color) = parse(field, value)? {
+                *target = color;
+            }
+        }
+        if let Some(background) = &custom.background {
+            // "none" 表示使用终端背景色
+            theme.background = if background.eq_ignore_ascii_case("none") {
+                None
+            } else {
+                parse("background", &Some(background.clone()))?
+            };
+        }
+        if let Some(ascii) = custom.ascii {
+            theme.ascii = ascii;
+        }
+        Ok(theme)
+    }
+
+    pub fn severity(&self, severity: Severity) -> Color {
+        match severity {
+            Severity::Ok => self.ok,
+            Severity::Warn => self.warn,
+            Severity::Critical => self.critical,
+        }
+    }
+
+    // 按阈值取严重程度颜色，例如 level(cpu, 50.0, 80.0)
+    pub fn level(&self, value: f64, warn: f64, critical: f64) -> Color {
+        self.severity(Severity::from_value(value, warn, critical))
+    }
+}
+
+// 设置当前主题（启动时调用一次）
+pub fn init(theme: Theme) {
+    let _ = CURRENT.set(theme);
+}
+
+pub fn theme() -> &'static Theme {
+    CURRENT.get_or_init(Theme::dark)
+}
+
+// 在每一帧绘制完成后调用：填充主题默认前景/背景色，ASCII 主题下替换特殊字符
+pub fn finish_frame(f: &mut Frame) {
+    apply(theme(), f.buffer_mut());
+}
+
+fn apply(theme: &Theme, buffer: &mut Buffer) {
+    for cell in buffer.content.iter_mut() {
+        if cell.fg == Color::Reset {
+            cell.fg = theme.text;
+        }
+        if let (Color::Reset, Some(background)) = (cell.bg, theme.background) {
+            cell.bg = background;
+        }
+        // Latin-1 符号（如 °）在各类终端上都能正常显示，保留不动
+        if theme.ascii && cell.symbol().chars().any(|c| c as u32 > 0xFF) {
+            let replacement = ascii_symbol(cell.symbol());
+            cell.set_symbol(replacement);
+        }
+    }
+}
+
+// 单个字符格的 ASCII 替代（只有双宽 emoji 才替换为两个字符，保持布局不变）
+fn ascii_symbol(symbol: &str) -> &'static str {
+    let symbol = symbol.trim_end_matches('\u{FE0F}');
+    let ch = match symbol.chars().next() {
+        Some(ch) => ch,
+        None => return " ",
+    };
+    match ch {
+        // 框线
+        '─' | '━' | '╌' | '┄' => "-",
+        '═' => "=",
+        '│' | '┃' | '║' | '╎' | '┆' => "|",
+        '\u{2500}'..='\u{257F}' => "+",
+        // 进度条与图表
+        '█' | '▉' | '▊' | '▋' | '▌' | '▍' | '▎' | '▏' | '▇' | '▆' | '▅' | '▄' | '▃' | '▂' | '▁' => "#",
+        '░' | '▒' | '▓' => ".",
+        '\u{2800}' => " ",
+        '\u{2801}'..='\u{28FF}' => "*",
+        '•' | '·' => "*",
+        '…' => "...",
+        '↑' => "^",
+        '↓' => "v",
+        '←' => "<",
+        '→' => ">",
+        '▶' => ">",
+        '⏸' => "||",
+        '⏺' => "()",
+        '➡' => ">",
+        '✅' => "OK",
+        '❌' => "X",
+        '⚠' => "!",
+        '⏳' => "..",
+        'ℹ' => "i",
+        '📁' | '📄' | '📦' | '📼' | '📋' => "[]",
+        '📊' => "##",
+        '🔧' | '✨' | '🍋' | '📸' => "*",
+        '🌐' | '🛰' | '📡' => "@",
+        '🌡' => "T",
+        '🔄' => "<>",
+        '🗑' | '🧹' => "x",
+        '🛡' => "#",
+        '💾' | '💿' => "o",
+        '🔋' => "B",
+        '🔌' => "AC",
+        _ => "?",
+    }
+}
+
@@ -0,0 +1,530 @@
+// Recycle bin (Windows) and freedesktop Trash (Linux), per drive
+//
+// Windows：每个驱动器上当前用户的 $Recycle.Bin\<SID> 中，$I<名称> 记录删除时间，$R<名称> 是被删除的内容。
+// 整个清空时先用 SHQueryRecycleBinW 查询项目数和大小，再用 SHEmptyRecycleBinW 清空；
+// 只删除较早的项目（或有释放上限）时按 $I 文件逐项删除。
+//
+// Linux：主目录的 ~/.local/share/Trash，以及各挂载点的 .Trash/<uid> 和 .Trash-<uid>；
+// info/<名称>.trashinfo 中的 DeletionDate 为删除时间，files/<名称> 是被删除的内容。
+// Linux 上清空回收站需要明确选择（lemo empty-trash，或在 clean-temp 中指定 recycle-bin 目标）。
+use crate::history::format_bytes;
+use crate::policy::same_mount;
+use crate::snapshot::read_disks;
+use anyhow::Result;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+// 回收站中的一项：内容和对应的删除记录
+pub struct TrashItem {
+    pub path: PathBuf,
+    info: PathBuf,
+    pub deleted: Option<SystemTime>,
+}
+
+// 一个驱动器（挂载点）上的回收站
+pub struct Bin {
+    pub drive: String,
+    dirs: Vec<PathBuf>,
+}
+
+// 文件或目录的大小（不跟随链接）
+pub fn tree_size(path: &Path) -> u64 {
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+    fs::read_dir(path)
+        .map(|entries| entries.flatten().map(|entry| tree_size(&entry.path())).sum())
+        .unwrap_or(0)
+}
+
+// 删除回收站中的内容：链接只删除链接本身，不跟随
+fn remove_tree(path: &Path) -> io::Result<()> {
+    let metadata = fs::symlink_metadata(path)?;
+    if !metadata.is_dir() {
+        // 指向目录的符号链接在 Windows 上需要 remove_dir
+        return fs::remove_file(path).or_else(|_| fs::remove_dir(path));
+    }
+    for entry in fs::read_dir(path)?.flatten() {
+        remove_tree(&entry.path())?;
+    }
+    fs::remove_dir(path)
+}
+
+impl TrashItem {
+    pub fn size(&self) -> u64 {
+        tree_size(&self.path)
+    }
+
+    // 删除内容和删除记录，返回释放的字节数
+    pub fn remove(&self) -> io::Result<u64> {
+        let size = self.size();
+        if fs::symlink_metadata(&self.path).is_ok() {
+            remove_tree(&self.path)?;
+        }
+        fs::remove_file(&self.info)?;
+        Ok(size)
+    }
+
+    pub fn older_than(&self, age: Option<Duration>) -> bool {
+        let Some(age) = age else {
+            return true;
+        };
+        let Some(cutoff) = SystemTime::now().checked_sub(age) else {
+            return false;
+        };
+        // 删除时间未知的项目不按时间删除
+        self.deleted.is_some_and(|deleted| deleted <= cutoff)
+    }
+}
+
+// 按驱动器筛选：drives 为空时选择全部
+fn selected(drives: &[String], drive: &str) -> bool {
+    drives.is_empty() || drives.iter().any(|d| same_mount(d, drive))
+}
+
+#[cfg(windows)]
+pub fn bins(drives: &[String]) -> Vec<Bin> {
+    let Some(sid) = current_sid() else {
+        return Vec::new();
+    };
+    read_disks()
+        .into_iter()
+        .filter(|disk| selected(drives, &disk.mount_point))
+        .filter_map(|disk| {
+            // 每个用户一个子目录：只使用当前用户的，以管理员身份运行时也不删除其他用户的项目
+            let dir = Path::new(&disk.mount_point).join("$Recycle.Bin").join(&sid);
+            fs::read_dir(&dir).ok()?;
+            Some(Bin {
+                drive: disk.mount_point,
+                dirs: vec![dir],
+            })
+        })
+        .collect()
+}
+
+#[cfg(not(windows))]
+pub fn bins(drives: &[String]) -> Vec<Bin> {
+    let disks = read_disks();
+    // 路径所在的挂载点（最长前缀）
+    let mount_of = |path: &Path| {
+        disks
+            .iter()
+            .map(|disk| disk.mount_point.as_str())
+            .filter(|mount| path.starts_with(mount))
+            .max_by_key(|mount| mount.len())
+            .unwrap_or("/")
+            .to_string()
+    };
+
+    let mut trash_dirs = Vec::new();
+    if let Some(data_home) = crate::config::xdg_data_home() {
+        trash_dirs.push(data_home.join("Trash"));
+    }
+    if let Some(uid) = current_uid() {
+        for disk in &disks {
+            trash_dirs.extend(mount_trash_dirs(Path::new(&disk.mount_point), &uid));
+        }
+    }
+    trash_dirs.retain(|dir| is_trash_dir(dir));
+    trash_dirs.dedup();
+
+    let mut bins: Vec<Bin> = Vec::new();
+    for dir in trash_dirs {
+        let drive = mount_of(&dir);
+        if !selected(drives, &drive) {
+            continue;
+        }
+        match bins.iter_mut().find(|bin| bin.drive == drive) {
+            Some(bin) => bin.dirs.push(dir),
+            None => bins.push(Bin { drive, dirs: vec![dir] }),
+        }
+    }
+    bins
+}
+
+// 不跟随链接的 Trash 目录（含 info 子目录）
+#[cfg(not(windows))]
+fn is_trash_dir(dir: &Path) -> bool {
+    !crate::walk::is_link(dir) && fs::symlink_metadata(dir.join("info")).is_ok_and(|m| m.is_dir())
+}
+
+// 挂载点上属于 uid 的 Trash 目录。$topdir/.Trash 由所有用户共用，
+// 是链接或没有粘滞位时不使用（freedesktop 规范），否则其中的链接可以把删除引到别处
+#[cfg(not(windows))]
+fn mount_trash_dirs(mount: &Path, uid: &str) -> Vec<PathBuf> {
+    use std::os::unix::fs::PermissionsExt;
+    const STICKY: u32 = 0o1000;
+
+    let mut dirs = Vec::new();
+    let shared = mount.join(".Trash");
+    if fs::symlink_metadata(&shared).is_ok_and(|m| m.is_dir() && m.permissions().mode() & STICKY != 0) {
+        dirs.push(shared.join(uid));
+    }
+    dirs.push(mount.join(format!(".Trash-{}", uid)));
+    dirs
+}
+
+// 当前用户的 uid（/proc/self/status 的 Uid 行）
+#[cfg(not(windows))]
+fn current_uid() -> Option<String> {
+    fs::read_to_string("/proc/self/status")
+        .ok()?
+        .lines()
+        .find(|line| line.starts_with("Uid:"))?
+        .split_whitespace()
+        .nth(1)
+        .map(str::to_string)
+}
+
+// 当前用户的 SID（如 S-1-5-21-...-1001），即 $Recycle.Bin 下的子目录名
+#[cfg(windows)]
+fn current_sid() -> Option<String> {
+    use winapi::shared::sddl::ConvertSidToStringSidW;
+    use winapi::um::processthreadsapi::{GetCurrentProcess, OpenProcessToken};
+    use winapi::um::securitybaseapi::GetTokenInformation;
+    use winapi::um::winbase::LocalFree;
+    use winapi::um::winnt::{TokenUser, TOKEN_QUERY, TOKEN_USER};
+
+    unsafe {
+        let mut token = std::ptr::null_mut();
+        if OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &mut token) == 0 {
+            return None;
+        }
+        // TOKEN_USER 后面紧跟 SID，先查询需要的长度
+        let mut length = 0;
+        GetTokenInformation(token, TokenUser, std::ptr::null_mut(), 0, &mut length);
+        let mut buffer = vec![0u64; (length as usize).div_ceil(8)];
+        let ok = GetTokenInformation(token, TokenUser, buffer.as_mut_ptr() as *mut _, length, &mut length);
+        winapi::um::handleapi::CloseHandle(token);
+        if ok == 0 {
+            return None;
+        }
+        let user = &*(buffer.as_ptr() as *const TOKEN_USER);
+        let mut text = std::ptr::null_mut();
+        if ConvertSidToStringSidW(user.User.Sid, &mut text) == 0 {
+            return None;
+        }
+        let len = (0..).take_while(|&i| *text.add(i) != 0).count();
+        let sid = String::from_utf16_lossy(std::slice::from_raw_parts(text, len));
+        LocalFree(text as *mut _);
+        Some(sid)
+    }
+}
+
+// $I 文件：版本（8 字节）、原始大小（8 字节）、删除时间 FILETIME（8 字节）、原路径
+#[cfg(windows)]
+fn parse_recycle_info(data: &[u8]) -> Option<SystemTime> {
+    // FILETIME：自 1601-01-01 起的 100 纳秒数
+    const UNIX_EPOCH_FILETIME: u64 = 116_444_736_000_000_000;
+    let filetime = u64::from_le_bytes(data.get(16..24)?.try_into().ok()?);
+    let since_epoch = filetime.checked_sub(UNIX_EPOCH_FILETIME)?;
+    SystemTime::UNIX_EPOCH.checked_add(Duration::from_nanos(since_epoch.saturating_mul(100)))
+}
+
+// DeletionDate=2024-01-31T18:30:00（本地时间）
+#[cfg(not(windows))]
+fn parse_trash_info(content: &str) -> Option<SystemTime> {
+    use chrono::{Local, NaiveDateTime, TimeZone};
+    let value = content
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("DeletionDate="))?;
+    let naive = NaiveDateTime::parse_from_str(value.trim(), "%Y-%m-%dT%H:%M:%S").ok()?;
+    Local.from_local_datetime(&naive).earliest().map(SystemTime::from)
+}
+
+impl Bin {
+    #[cfg(windows)]
+    pub fn items(&self) -> Vec<TrashItem> {
+        let mut items = Vec::new();
+        for dir in &self.dirs {
+            for entry in fs::read_dir(dir).into_iter().flatten().flatten() {
+                let name = entry.file_name().to_string_lossy().to_string();
+                let Some(suffix) = name.strip_prefix("$I") else {
+                    continue;
+                };
+                let info = entry.path();
+                items.push(TrashItem {
+                    path: dir.join(format!("$R{}", suffix)),
+                    deleted: fs::read(&info).ok().and_then(|data| parse_recycle_info(&data)),
+                    info,
+                });
+            }
+        }
+        items
+    }
+
+    #[cfg(not(windows))]
+    pub fn items(&self) -> Vec<TrashItem> {
+        let mut items = Vec::new();
+        for dir in &self.dirs {
+            for entry in fs::read_dir(dir.join("info")).into_iter().flatten().flatten() {
+                let name = entry.file_name().to_string_lossy().to_string();
+                let Some(stem) = name.strip_suffix(".trashinfo") else {
+                    continue;
+                };
+                let info = entry.path();
+                items.push(TrashItem {
+                    path: dir.join("files").join(stem),
+                    deleted: fs::read_to_string(&info).ok().and_then(|content| parse_trash_info(&content)),
+                    info,
+                });
+            }
+        }
+        items
+    }
+
+    // (项目数, 字节数)：Windows 使用 SHQueryRecycleBinW，其他平台统计 Trash 目录
+    #[cfg(windows)]
+    pub fn query(&self) -> Result<(usize, u64)> {
+        use winapi::um::shellapi::{SHQueryRecycleBinW, SHQUERYRBINFO};
+
+        let root: Vec<u16> = format!("{}\0", self.drive).encode_utf16().collect();
+        let mut info = SHQUERYRBINFO {
+            cbSize: std::mem::size_of::<SHQUERYRBINFO>() as u32,
+            i64Size: 0,
+            i64NumItems: 0,
+        };
+        let result = unsafe { SHQueryRecycleBinW(root.as_ptr(), &mut info) };
+        if result != 0 {
+            return Err(anyhow::anyhow!("Cannot query the recycle bin (error code: 0x{:X})", result));
+        }
+        Ok((info.i64NumItems.max(0) as usize, info.i64Size.max(0) as u64))
+    }
+
+    #[cfg(not(windows))]
+    pub fn query(&self) -> Result<(usize, u64)> {
+        let items = self.items();
+        Ok((items.len(), items.iter().map(TrashItem::size).sum()))
+    }
+
+    // 清空整个回收站
+    #[cfg(windows)]
+    pub fn empty(&self) -> Result<()> {
+        use winapi::um::shellapi::SHEmptyRecycleBinW;
+
+        // SHERB_NOCONFIRMATION | SHERB_NOPROGRESSUI | SHERB_NOSOUND
+        let flags = 0x00000001 | 0x00000002 | 0x00000004;
+        let root: Vec<u16> = format!("{}\0", self.drive).encode_utf16().collect();
+        let result = unsafe { SHEmptyRecycleBinW(std::ptr::null_mut(), root.as_ptr(), flags) };
+        if result != 0 {
+            return Err(anyhow::anyhow!("Failed to empty recycle bin (error code: 0x{:X})", result));
+        }
+        Ok(())
+    }
+
+    #[cfg(not(windows))]
+    pub fn empty(&self) -> Result<()> {
+        for item in self.items() {
+            item.remove()?;
+        }
+        Ok(())
+    }
+}
+
+// lemo empty-trash --dry-run：每个驱动器回收站的大小，以及符合时间条件的部分
+pub fn print_bins(drives: &[String], older_than: Option<Duration>) -> Result<()> {
+    let bins = bins(drives);
+    if bins.is_empty() {
+        println!("No recycle bin found{}.", if drives.is_empty() { "" } else { " on the selected drives" });
+        return Ok(());
+    }
+    for bin in &bins {
+        let (count, size) = bin.query()?;
+        print!("{:<12} {:>6} items  {:>10}", bin.drive, count, format_bytes(size));
+        if older_than.is_some() {
+            let old: Vec<TrashItem> = bin.items().into_iter().filter(|item| item.older_than(older_than)).collect();
+            print!(
+                "   ({} items, {} deleted before the cutoff)",
+                old.len(),
+                format_bytes(old.iter().map(TrashItem::size).sum())
+            );
+        }
+        println!();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_root(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("lemo-trash-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        root
+    }
+
+    // freedesktop Trash：files/<名称> 和 info/<名称>.trashinfo
+    #[cfg(not(windows))]
+    fn trash_item(trash: &Path, name: &str, content: &[u8], deleted: &str) {
+        fs::create_dir_all(trash.join("files")).unwrap();
+        fs::create_dir_all(trash.join("info")).unwrap();
+        fs::write(trash.join("files").join(name), content).unwrap();
+        fs::write(
+            trash.join("info").join(format!("{}.trashinfo", name)),
+            format!("[Trash Info]\nPath=/home/me/{}\nDeletionDate={}\n", name, deleted),
+        )
+        .unwrap();
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn parses_trash_info_dates() {
+        use chrono::{Local, TimeZone};
+        let expected = SystemTime::from(Local.with_ymd_and_hms(2024, 1, 31, 18, 30, 0).unwrap());
+        assert_eq!(
+            parse_trash_info("[Trash Info]\nPath=/tmp/a\nDeletionDate=2024-01-31T18:30:00\n"),
+            Some(expected)
+        );
+        assert_eq!(parse_trash_info("[Trash Info]\nPath=/tmp/a\n"), None);
+        assert_eq!(parse_trash_info("DeletionDate=yesterday"), None);
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn summarizes_items_by_deletion_date() {
+        let root = temp_root("items");
+        let trash = root.join("Trash");
+        trash_item(&trash, "old.txt", b"12345", "2020-01-01T00:00:00");
+        trash_item(&trash, "recent.txt", b"123", &chrono::Local::now().format("%Y-%m-%dT%H:%M:%S").to_string());
+        trash_item(&trash, "undated.txt", b"12", "not a date");
+        // 目录项按整个目录统计
+        fs::create_dir_all(trash.join("files").join("folder").join("sub")).unwrap();
+        fs::write(trash.join("files").join("folder").join("sub").join("a"), b"1234").unwrap();
+        fs::write(trash.join("info").join("folder.trashinfo"), "DeletionDate=2021-06-01T12:00:00\n").unwrap();
+        // 不是 .trashinfo 的文件不算
+        fs::write(trash.join("info").join("notes"), b"x").unwrap();
+
+        let bin = Bin {
+            drive: "/".to_string(),
+            dirs: vec![trash.clone()],
+        };
+        let mut items = bin.items();
+        items.sort_by(|a, b| a.path.cmp(&b.path));
+        let names: Vec<String> = items
+            .iter()
+            .map(|item| item.path.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(names, ["folder", "old.txt", "recent.txt", "undated.txt"]);
+        assert!(items.iter().all(|item| item.older_than(None)));
+
+        let week = Some(Duration::from_secs(7 * 24 * 3600));
+        let old: Vec<&str> = items
+            .iter()
+            .zip(&names)
+            .filter(|(item, _)| item.older_than(week))
+            .map(|(_, name)| name.as_str())
+            .collect();
+        // 删除时间未知的项目不按时间删除
+        assert_eq!(old, ["folder", "old.txt"]);
+
+        assert_eq!(bin.query().unwrap(), (4, 5 + 3 + 2 + 4));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn removing_an_item_deletes_content_and_info() {
+        let root = temp_root("remove");
+        let trash = root.join("Trash");
+        trash_item(&trash, "a.txt", b"12345", "2020-01-01T00:00:00");
+        trash_item(&trash, "b.txt", b"123", "2020-01-01T00:00:00");
+        let bin = Bin {
+            drive: "/".to_string(),
+            dirs: vec![trash.clone()],
+        };
+        let item = bin.items().into_iter().find(|item| item.path.ends_with("a.txt")).unwrap();
+        assert_eq!(item.remove().unwrap(), 5);
+        assert!(!trash.join("info").join("a.txt.trashinfo").exists());
+        assert_eq!(bin.query().unwrap(), (1, 3));
+
+        bin.empty().unwrap();
+        assert_eq!(bin.query().unwrap(), (0, 0));
+        assert!(!trash.join("files").join("b.txt").exists());
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn shared_trash_needs_sticky_bit_and_no_link() {
+        use std::os::unix::fs::{symlink, PermissionsExt};
+        let root = temp_root("shared");
+        let uid = "1000";
+        fs::create_dir_all(root.join(".Trash-1000").join("info")).unwrap();
+
+        // 没有粘滞位：只使用 .Trash-<uid>
+        fs::create_dir_all(root.join(".Trash").join(uid).join("info")).unwrap();
+        fs::set_permissions(root.join(".Trash"), fs::Permissions::from_mode(0o777)).unwrap();
+        assert_eq!(mount_trash_dirs(&root, uid), vec![root.join(".Trash-1000")]);
+
+        fs::set_permissions(root.join(".Trash"), fs::Permissions::from_mode(0o1777)).unwrap();
+        assert_eq!(
+            mount_trash_dirs(&root, uid),
+            vec![root.join(".Trash").join(uid), root.join(".Trash-1000")]
+        );
+
+        // .Trash 是指向其他目录的链接：即使目标有粘滞位也不使用
+        let elsewhere = root.join("elsewhere");
+        fs::create_dir_all(elsewhere.join(uid).join("info")).unwrap();
+        fs::set_permissions(&elsewhere, fs::Permissions::from_mode(0o1777)).unwrap();
+        fs::remove_dir_all(root.join(".Trash")).unwrap();
+        symlink(&elsewhere, root.join(".Trash")).unwrap();
+        assert_eq!(mount_trash_dirs(&root, uid), vec![root.join(".Trash-1000")]);
+
+        // 链接形式的 .Trash-<uid> 由 is_trash_dir 排除
+        assert!(is_trash_dir(&root.join(".Trash-1000")));
+        fs::remove_dir_all(root.join(".Trash-1000")).unwrap();
+        symlink(elsewhere.join(uid), root.join(".Trash-1000")).unwrap();
+        assert!(!is_trash_dir(&root.join(".Trash-1000")));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn parses_recycle_info_deletion_time() {
+        // 版本 2：版本、原始大小、FILETIME、路径长度和路径
+        let filetime: u64 = 116_444_736_000_000_000 + 1_700_000_000 * 10_000_000;
+        let mut data = Vec::new();
+        data.extend_from_slice(&2u64.to_le_bytes());
+        data.extend_from_slice(&1234u64.to_le_bytes());
+        data.extend_from_slice(&filetime.to_le_bytes());
+        data.extend_from_slice(&[0; 8]);
+        assert_eq!(
+            parse_recycle_info(&data),
+            SystemTime::UNIX_EPOCH.checked_add(Duration::from_secs(1_700_000_000))
+        );
+        assert_eq!(parse_recycle_info(&data[..20]), None);
+        assert_eq!(parse_recycle_info(&[0; 24]), None);
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn items_pair_info_and_content_files() {
+        let root = temp_root("recycle");
+        let filetime: u64 = 116_444_736_000_000_000 + 1_600_000_000 * 10_000_000;
+        let mut info = vec![0u8; 16];
+        info.extend_from_slice(&filetime.to_le_bytes());
+        fs::write(root.join("$IABC123.txt"), &info).unwrap();
+        fs::write(root.join("$RABC123.txt"), b"12345").unwrap();
+        fs::write(root.join("desktop.ini"), b"x").unwrap();
+
+        let bin = Bin {
+            drive: "C:\\".to_string(),
+            dirs: vec![root.clone()],
+        };
+        let items = bin.items();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].path, root.join("$RABC123.txt"));
+        assert_eq!(items[0].size(), 5);
+        assert!(items[0].older_than(Some(Duration::from_secs(24 * 3600))));
+        assert_eq!(items[0].remove().unwrap(), 5);
+        assert!(!root.join("$IABC123.txt").exists());
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
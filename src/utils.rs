@@ -1,15 +1,26 @@
 // Utility functions module
+use crate::apps;
+use crate::browser::{self, Category};
+use crate::components::{self, DismResult};
+use crate::config;
+use crate::exclusions::{count_files, Exclusions};
+use crate::history::{self, TargetResult};
+use crate::policy;
+use crate::task::{Cancelled, Task, TaskContext};
+use crate::trash;
+use crate::walk::{self, Boundary, EntryKind, SkippedLinks};
 use anyhow::Result;
 use std::env;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::thread;
-use std::time::Duration;
-use winapi::um::shellapi::ShellExecuteW;
-use winapi::um::winuser::SW_SHOWNORMAL;
+use std::time::{Duration, SystemTime};
 
 // Check if running as administrator
+#[cfg(windows)]
 pub fn is_admin() -> bool {
     use winapi::um::processthreadsapi::*;
     use winapi::um::securitybaseapi::*;
@@ -38,8 +49,26 @@ pub fn is_admin() -> bool {
     }
 }
 
+// 非 Windows 平台：检查有效用户 ID 是否为 root
+#[cfg(not(windows))]
+pub fn is_admin() -> bool {
+    fs::read_to_string("/proc/self/status")
+        .ok()
+        .and_then(|status| {
+            status
+                .lines()
+                .find(|line| line.starts_with("Uid:"))
+                .and_then(|line| line.split_whitespace().nth(2).map(|euid| euid == "0"))
+        })
+        .unwrap_or(false)
+}
+
 // Run as administrator
+#[cfg(windows)]
 pub fn run_as_admin() -> Result<()> {
+    use winapi::um::shellapi::ShellExecuteW;
+    use winapi::um::winuser::SW_SHOWNORMAL;
+
     let exe_path = env::current_exe()?;
     let exe_path_str = exe_path
         .to_str()
@@ -70,18 +99,34 @@ pub fn run_as_admin() -> Result<()> {
     Ok(())
 }
 
+#[cfg(not(windows))]
+pub fn run_as_admin() -> Result<()> {
+    Err(anyhow::anyhow!("Privilege elevation is only supported on Windows"))
+}
+
 // Fix icon cache
-pub fn fix_icon_cache(restart_explorer: bool) -> Result<()> {
-    println!("🔧 Fixing icon cache...");
+pub fn fix_icon_cache_task(restart_explorer: bool) -> Task {
+    Task::new("Fix Icon Cache", move |ctx| {
+        history::track(
+            "fix-icon-cache",
+            &[("restart_explorer", restart_explorer.to_string())],
+            |targets| fix_icon_cache_core(restart_explorer, ctx, targets),
+        )
+    })
+}
+
+fn fix_icon_cache_core(restart_explorer: bool, ctx: &mut TaskContext, targets: &mut Vec<TargetResult>) -> Result<()> {
+    ctx.println("🔧 Fixing icon cache...");
+    ctx.println("");
 
-    println!("⏳ Closing Windows Explorer...");
+    ctx.println("⏳ Closing Windows Explorer...");
     let _ = Command::new("taskkill")
-        .args(&["/f", "/im", "explorer.exe"])
+        .args(["/f", "/im", "explorer.exe"])
         .output();
 
     thread::sleep(Duration::from_secs(2));
 
-    let user_profile = env::var("USERPROFILE")?;
+    let user_profile = env::var("USERPROFILE").map_err(|_| anyhow::anyhow!("Cannot get USERPROFILE"))?;
     let mut cache_files = Vec::new();
 
     let icon_cache = PathBuf::from(&user_profile).join(r"AppData\Local\IconCache.db");
@@ -105,65 +150,297 @@ pub fn fix_icon_cache(restart_explorer: bool) -> Result<()> {
 
     let mut deleted_count = 0;
     let mut skipped_count = 0;
+    let mut freed = 0u64;
 
     for file in cache_files {
+        if ctx.is_cancelled() {
+            break;
+        }
+        let size = fs::metadata(&file).map(|m| m.len()).unwrap_or(0);
         match fs::remove_file(&file) {
             Ok(_) => {
-                println!("✅ Deleted: {:?}", file);
+                ctx.println(format!("✅ Deleted: {:?}", file));
                 deleted_count += 1;
+                freed += size;
             }
             Err(e) => {
-                println!("⚠️  Skipped: {:?} ({})", file, e);
+                ctx.println(format!("⚠️  Skipped: {:?} ({})", file, e));
                 skipped_count += 1;
             }
         }
     }
 
-    println!(
-        "\n📊 Summary: Deleted {} files, Skipped {} files",
+    ctx.println("");
+    ctx.println(format!(
+        "📊 Summary: Deleted {} files, Skipped {} files",
         deleted_count, skipped_count
-    );
+    ));
 
-    if restart_explorer {
-        println!("🔄 Restarting Windows Explorer...");
-        Command::new("explorer.exe").spawn()?;
-        println!("✨ Fix completed! Desktop will restore in a few seconds.");
+    // 已经关闭了 Explorer，取消时也要把它启动回来
+    if restart_explorer || ctx.is_cancelled() {
+        ctx.println("");
+        ctx.println("🔄 Restarting Windows Explorer...");
+        match Command::new("explorer.exe").spawn() {
+            Ok(_) => ctx.println("✨ Fix completed! Desktop will restore in a few seconds."),
+            Err(e) => ctx.println(format!("⚠️  Warning: Failed to restart Explorer: {}", e)),
+        }
         thread::sleep(Duration::from_secs(3));
     } else {
-        println!("✨ Fix completed! Please restart Explorer manually.");
+        ctx.println("✨ Fix completed! Please restart Explorer manually.");
     }
 
+    targets.push(TargetResult::new("Icon cache", deleted_count, skipped_count, freed));
+    if ctx.is_cancelled() {
+        return Err(Cancelled.into());
+    }
     Ok(())
 }
 
+// clean-temp 的清理目标
+pub struct TargetInfo {
+    pub id: &'static str,
+    pub name: &'static str,
+    // 目标选择界面中的分组
+    pub group: &'static str,
+    // 位于当前用户目录下
+    pub user: bool,
+    // 隐私数据（Cookies、最近使用记录），不是临时文件
+    pub privacy: bool,
+    // 只在明确选择（--profile、配置或界面勾选）时清理，-i 不包含
+    pub opt_in: bool,
+}
+
+pub const CLEAN_TARGETS: [TargetInfo; 25] = [
+    TargetInfo { id: "windows-temp", name: "Windows Temp", group: "System", user: false, privacy: false, opt_in: false },
+    TargetInfo { id: "prefetch", name: "Prefetch", group: "System", user: false, privacy: false, opt_in: false },
+    TargetInfo { id: "windows-bak", name: "Windows .bak files", group: "System", user: false, privacy: false, opt_in: false },
+    // Linux 的 Trash 只在明确选择时清空
+    TargetInfo { id: "recycle-bin", name: "Recycle Bin", group: "System", user: false, privacy: false, opt_in: !cfg!(windows) },
+    TargetInfo { id: "system-drive-temp", name: "System drive temp files", group: "System", user: false, privacy: false, opt_in: false },
+    // 需要管理员权限，清理时暂停相关服务（见 components.rs）
+    TargetInfo { id: "windows-update-cache", name: "Windows Update downloads", group: "System components", user: false, privacy: false, opt_in: true },
+    TargetInfo { id: "delivery-optimization", name: "Delivery Optimization cache", group: "System components", user: false, privacy: false, opt_in: true },
+    TargetInfo { id: "component-store", name: "Component store cleanup (DISM)", group: "System components", user: false, privacy: false, opt_in: true },
+    TargetInfo { id: "user-temp", name: "User Temp", group: "User", user: true, privacy: false, opt_in: false },
+    TargetInfo { id: "cookies", name: "Cookies", group: "User", user: true, privacy: true, opt_in: false },
+    TargetInfo { id: "recent", name: "Recent items", group: "User", user: true, privacy: true, opt_in: false },
+    TargetInfo { id: "user-local-temp", name: "User local Temp", group: "User", user: true, privacy: false, opt_in: false },
+    TargetInfo { id: "browser-cache", name: "Browser cache", group: "Browsers", user: true, privacy: false, opt_in: false },
+    TargetInfo { id: "browser-code-cache", name: "Browser code cache", group: "Browsers", user: true, privacy: false, opt_in: false },
+    TargetInfo { id: "browser-gpu-cache", name: "Browser GPU cache", group: "Browsers", user: true, privacy: false, opt_in: false },
+    TargetInfo { id: "browser-service-workers", name: "Browser service workers", group: "Browsers", user: true, privacy: false, opt_in: false },
+    TargetInfo { id: "browser-cookies", name: "Browser cookies", group: "Browsers", user: true, privacy: true, opt_in: true },
+    TargetInfo { id: "browser-history", name: "Browser history", group: "Browsers", user: true, privacy: true, opt_in: true },
+    // 应用清理（定义见 apps.rs）
+    TargetInfo { id: "crash-dumps", name: "Crash dumps", group: "Crash reports & logs", user: true, privacy: false, opt_in: true },
+    TargetInfo { id: "error-reports", name: "Windows Error Reporting", group: "Crash reports & logs", user: false, privacy: false, opt_in: true },
+    TargetInfo { id: "cbs-logs", name: "Windows servicing logs (CBS)", group: "Crash reports & logs", user: false, privacy: false, opt_in: true },
+    TargetInfo { id: "jetbrains-caches", name: "JetBrains IDE caches", group: "Developer tools", user: true, privacy: false, opt_in: true },
+    TargetInfo { id: "vscode-caches", name: "VS Code caches", group: "Developer tools", user: true, privacy: false, opt_in: true },
+    TargetInfo { id: "teams-cache", name: "Microsoft Teams cache", group: "Chat apps", user: true, privacy: false, opt_in: true },
+    TargetInfo { id: "slack-cache", name: "Slack cache", group: "Chat apps", user: true, privacy: false, opt_in: true },
+];
+
+// 已改名的目标 id（旧的配置文件仍然可用）
+pub fn canonical_target(id: &str) -> &str {
+    match id {
+        "ie-cache" => "browser-cache",
+        id => id,
+    }
+}
+
+pub fn validate_targets(targets: &[String]) -> Result<()> {
+    match targets
+        .iter()
+        .find(|id| !CLEAN_TARGETS.iter().any(|t| t.id == canonical_target(id)))
+    {
+        Some(id) => Err(anyhow::anyhow!(
+            "Unknown target '{}' (available: {})",
+            id,
+            CLEAN_TARGETS.iter().map(|t| t.id).collect::<Vec<_>>().join(", ")
+        )),
+        None => Ok(()),
+    }
+}
+
+// 清理选项（命令行、TUI 和计划任务共用）
+#[derive(Debug, Clone, Default)]
+pub struct CleanOptions {
+    // 使用的清理配置（只用于显示和操作历史）
+    pub profile: Option<String>,
+    // 指定清理目标（CLEAN_TARGETS 的 id）；为空时清理系统目标，include_user 时再加上用户目录
+    pub targets: Vec<String>,
+    // 同时清理用户目录（Temp、Cookies、Recent 等）
+    pub include_user: bool,
+    // 只删除在这段时间内没有修改过的文件
+    pub older_than: Option<Duration>,
+    // 释放这么多字节后停止（磁盘空间策略），设置后每个目录从最旧的文件开始删除
+    pub max_bytes: Option<u64>,
+    // 只统计会删除的文件，不实际删除（清理前预估可释放空间）
+    pub dry_run: bool,
+    // 排除规则（内置保护 + config.toml），清理开始时由 prepare_run 读取
+    pub exclusions: Arc<Exclusions>,
+    // 允许进入其他文件系统（链接始终不跟随）
+    pub cross_filesystems: bool,
+    // 本次清理跳过的链接和挂载点
+    pub skipped_links: Arc<SkippedLinks>,
+    // 只清空这些驱动器的回收站；为空时清空全部
+    pub trash_drives: Vec<String>,
+    // 只清理这个驱动器上的文件（磁盘空间策略：释放的空间必须来自空间不足的驱动器）
+    pub drive: Option<String>,
+}
+
+impl CleanOptions {
+    // 写入操作历史的选项
+    pub fn history_options(&self) -> Vec<(&'static str, String)> {
+        let mut options = Vec::new();
+        if let Some(profile) = &self.profile {
+            options.push(("profile", profile.clone()));
+        }
+        if self.targets.is_empty() {
+            options.push(("include_user", self.include_user.to_string()));
+        } else {
+            options.push(("targets", self.targets.join(",")));
+        }
+        if let Some(age) = self.older_than {
+            options.push(("older_than", format_age(age)));
+        }
+        if let Some(max) = self.max_bytes {
+            options.push(("max_bytes", max.to_string()));
+        }
+        if !self.trash_drives.is_empty() {
+            options.push(("drives", self.trash_drives.join(",")));
+        }
+        if let Some(drive) = &self.drive {
+            options.push(("drive", drive.clone()));
+        }
+        options
+    }
+
+    // 设置了 drive 时，路径是否在这个驱动器上
+    fn allows(&self, path: &Path) -> bool {
+        self.drive.as_deref().is_none_or(|drive| walk::on_drive(path, drive))
+    }
+
+    // 每次清理开始时：读取排除规则，重置跳过的链接
+    pub fn prepare_run(&self) -> Result<CleanOptions> {
+        Ok(CleanOptions {
+            exclusions: Arc::new(Exclusions::load()?),
+            skipped_links: Arc::default(),
+            ..self.clone()
+        })
+    }
+
+    pub fn selected_targets(&self) -> Vec<&'static TargetInfo> {
+        CLEAN_TARGETS
+            .iter()
+            .filter(|target| {
+                if self.targets.is_empty() {
+                    !target.opt_in && (!target.user || self.include_user)
+                } else {
+                    self.targets.iter().any(|id| canonical_target(id) == target.id)
+                }
+            })
+            .collect()
+    }
+
+    // 已释放 freed 字节后剩余的选项；预算已用完时返回 None
+    pub fn after_freeing(&self, freed: u64) -> Option<CleanOptions> {
+        match self.max_bytes {
+            Some(max) if freed >= max => None,
+            max_bytes => Some(CleanOptions {
+                max_bytes: max_bytes.map(|max| max - freed),
+                ..self.clone()
+            }),
+        }
+    }
+}
+
+// 解析时长，如 "14d"、"12h"、"2w"、"30m"
+pub fn parse_age(text: &str) -> Result<Duration> {
+    let text = text.trim();
+    let invalid = || anyhow::anyhow!("Invalid duration '{}' (use e.g. 30m, 12h, 14d, 2w)", text);
+    // 单位是最后一个字符（可能不是 ASCII）
+    let split = text.char_indices().last().map(|(index, _)| index).ok_or_else(invalid)?;
+    let (number, unit) = text.split_at(split);
+    let seconds: u64 = match unit {
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        _ => return Err(invalid()),
+    };
+    let number: u64 = number.parse().map_err(|_| invalid())?;
+    let total = number
+        .checked_mul(seconds)
+        .ok_or_else(|| anyhow::anyhow!("Duration '{}' is too long", text))?;
+    Ok(Duration::from_secs(total))
+}
+
+pub fn format_age(age: Duration) -> String {
+    let seconds = age.as_secs();
+    for (unit, size) in [("w", 7 * 24 * 3600), ("d", 24 * 3600), ("h", 3600)] {
+        if seconds >= size && seconds.is_multiple_of(size) {
+            return format!("{}{}", seconds / size, unit);
+        }
+    }
+    format!("{}m", seconds / 60)
+}
+
+// 文件（目录则取其中最新的文件）在 age 时间内没有修改过
+fn is_older_than(path: &Path, age: Option<Duration>) -> bool {
+    let Some(age) = age else {
+        return true;
+    };
+    let Some(cutoff) = SystemTime::now().checked_sub(age) else {
+        return false;
+    };
+    newest_modification(path).is_some_and(|modified| modified <= cutoff)
+}
+
+fn newest_modification(path: &Path) -> Option<SystemTime> {
+    let metadata = fs::symlink_metadata(path).ok()?;
+    let mut newest = metadata.modified().ok()?;
+    if metadata.is_dir() {
+        if let Ok(entries) = fs::read_dir(path) {
+            for entry in entries.flatten() {
+                if let Some(modified) = newest_modification(&entry.path()) {
+                    newest = newest.max(modified);
+                }
+            }
+        }
+    }
+    Some(newest)
+}
+
 // Output trait for different output methods
 trait CleanOutput {
     fn print(&mut self, msg: &str);
     fn print_empty(&mut self) {
         self.print("");
     }
-}
-
-// Console output
-struct ConsoleOutput;
-impl CleanOutput for ConsoleOutput {
-    fn print(&mut self, msg: &str) {
-        println!("{}", msg);
+    // 当前进行到第几个清理目标
+    fn progress(&mut self, _done: usize, _total: usize, _label: &str) {}
+    fn cancelled(&self) -> bool {
+        false
     }
 }
 
-// Callback output
-struct CallbackOutput<'a> {
-    callback: &'a mut Box<dyn FnMut(String) + Send>,
-}
-impl<'a> CleanOutput for CallbackOutput<'a> {
+// 任务输出（命令行和 TUI 共用）
+impl CleanOutput for TaskContext {
     fn print(&mut self, msg: &str) {
-        (self.callback)(msg.to_string());
+        self.println(msg);
+    }
+    fn progress(&mut self, done: usize, total: usize, label: &str) {
+        self.report_progress(done, total, label);
+    }
+    fn cancelled(&self) -> bool {
+        self.is_cancelled()
     }
 }
 
 // 子函数：清理 Windows Temp 目录
-fn clean_windows_temp<O: CleanOutput>(output: &mut O) -> Result<(usize, usize, u64)> {
+fn clean_windows_temp<O: CleanOutput>(options: &CleanOptions, output: &mut O) -> Result<(usize, usize, u64)> {
     let windows_temp = PathBuf::from(r"C:\Windows\Temp");
     if !windows_temp.exists() {
         return Ok((0, 0, 0));
@@ -171,7 +448,7 @@ fn clean_windows_temp<O: CleanOutput>(output: &mut O) -> Result<(usize, usize, u
 
     output.print_empty();
     output.print(&format!("📁 Cleaning Windows temp directory: {}", windows_temp.display()));
-    let (deleted, failed, size) = clean_directory_with_output(&windows_temp, output)?;
+    let (deleted, failed, size) = clean_directory_with_output(&windows_temp, options, output)?;
     output.print(&format!(
         "   Deleted: {} items, Skipped: {}, Freed: {:.2} MB",
         deleted, failed, size as f64 / 1024.0 / 1024.0
@@ -181,7 +458,7 @@ fn clean_windows_temp<O: CleanOutput>(output: &mut O) -> Result<(usize, usize, u
 }
 
 // 子函数：清理 Windows Prefetch
-fn clean_windows_prefetch<O: CleanOutput>(output: &mut O) -> Result<(usize, usize, u64)> {
+fn clean_windows_prefetch<O: CleanOutput>(options: &CleanOptions, output: &mut O) -> Result<(usize, usize, u64)> {
     let prefetch = PathBuf::from(r"C:\Windows\Prefetch");
     if !prefetch.exists() {
         return Ok((0, 0, 0));
@@ -189,7 +466,7 @@ fn clean_windows_prefetch<O: CleanOutput>(output: &mut O) -> Result<(usize, usiz
 
     output.print_empty();
     output.print(&format!("📁 Cleaning Windows prefetch: {}", prefetch.display()));
-    let (deleted, failed, size) = clean_directory_with_output(&prefetch, output)?;
+    let (deleted, failed, size) = clean_directory_with_output(&prefetch, options, output)?;
     output.print(&format!(
         "   Deleted: {} items, Skipped: {}, Freed: {:.2} MB",
         deleted, failed, size as f64 / 1024.0 / 1024.0
@@ -199,7 +476,7 @@ fn clean_windows_prefetch<O: CleanOutput>(output: &mut O) -> Result<(usize, usiz
 }
 
 // 子函数：清理 Windows 目录下的 .bak 文件
-fn clean_windows_bak_files<O: CleanOutput>(output: &mut O) -> Result<(usize, usize, u64)> {
+fn clean_windows_bak_files<O: CleanOutput>(options: &CleanOptions, output: &mut O) -> Result<(usize, usize, u64)> {
     let windir = match env::var("windir") {
         Ok(dir) => dir,
         Err(_) => return Ok((0, 0, 0)),
@@ -214,7 +491,8 @@ fn clean_windows_bak_files<O: CleanOutput>(output: &mut O) -> Result<(usize, usi
         &windows_dir,
         &bak_extensions,
         &mut |_, _, _, _| {},
-        0,
+        options,
+        &|| output.cancelled(),
     )?;
     
     output.print(&format!(
@@ -225,40 +503,94 @@ fn clean_windows_bak_files<O: CleanOutput>(output: &mut O) -> Result<(usize, usi
     Ok((deleted, failed, size))
 }
 
-// 子函数：清理回收站
-fn clean_recycle_bin<O: CleanOutput>(output: &mut O) -> Result<(usize, usize, u64)> {
-    use winapi::um::shellapi::SHEmptyRecycleBinW;
-    
+// 子函数：清理回收站（每个驱动器分别统计）
+// 没有时间条件和释放上限时整个清空，否则按删除时间从最早的开始逐项删除
+fn clean_recycle_bin<O: CleanOutput>(options: &CleanOptions, output: &mut O) -> Result<(usize, usize, u64)> {
+    let mut bins = trash::bins(&options.trash_drives);
+    if let Some(drive) = &options.drive {
+        bins.retain(|bin| policy::same_mount(&bin.drive, drive));
+    }
+    if bins.is_empty() {
+        return Ok((0, 0, 0));
+    }
+
     output.print_empty();
     output.print("🗑️  Emptying recycle bin...");
-    
-    // 使用 Windows API 清空回收站
-    // SHERB_NOCONFIRMATION = 0x00000001 (不显示确认对话框)
-    // SHERB_NOPROGRESSUI = 0x00000002 (不显示进度对话框)
-    // SHERB_NOSOUND = 0x00000004 (不播放声音)
-    let flags = 0x00000001 | 0x00000002 | 0x00000004;
-    
-    unsafe {
-        // NULL 表示清空所有驱动器的回收站
-        let result = SHEmptyRecycleBinW(
-            std::ptr::null_mut(),
-            std::ptr::null(),
-            flags
-        );
-        
-        if result == 0 {
-            output.print("   ✅ Recycle bin emptied successfully");
-            // 注意：无法准确获取删除的文件数量和大小，返回估计值
-            Ok((1, 0, 0))
+    if let Some(age) = options.older_than {
+        output.print(&format!("   Only items deleted more than {} ago", format_age(age)));
+    }
+    let (mut deleted, mut failed, mut size) = (0, 0, 0u64);
+    for bin in bins {
+        if output.cancelled() {
+            break;
+        }
+        let Some(step_options) = options.after_freeing(size) else {
+            break;
+        };
+        let (d, f, s) = if step_options.older_than.is_none() && step_options.max_bytes.is_none() {
+            empty_whole_bin(&bin, &step_options)
         } else {
-            output.print(&format!("   ⚠️  Failed to empty recycle bin (error code: 0x{:X})", result));
-            Ok((0, 1, 0))
+            empty_bin_items(&bin, &step_options, output)
+        };
+        if d + f > 0 {
+            output.print(&format!(
+                "   {}: Deleted: {} items, Skipped: {}, Freed: {:.2} MB",
+                bin.drive, d, f, s as f64 / 1024.0 / 1024.0
+            ));
         }
+        deleted += d;
+        failed += f;
+        size += s;
+    }
+    if deleted + failed == 0 {
+        output.print("   ✅ Nothing to delete");
     }
+
+    Ok((deleted, failed, size))
+}
+
+// 先查询项目数和大小，再清空
+fn empty_whole_bin(bin: &trash::Bin, options: &CleanOptions) -> (usize, usize, u64) {
+    let (count, bytes) = match bin.query() {
+        Ok(summary) => summary,
+        Err(_) => return (0, 1, 0),
+    };
+    if count == 0 || options.dry_run {
+        return (count, 0, bytes);
+    }
+    match bin.empty() {
+        Ok(()) => (count, 0, bytes),
+        Err(_) => (0, count, 0),
+    }
+}
+
+fn empty_bin_items<O: CleanOutput>(bin: &trash::Bin, options: &CleanOptions, output: &mut O) -> (usize, usize, u64) {
+    let mut items: Vec<trash::TrashItem> = bin
+        .items()
+        .into_iter()
+        .filter(|item| item.older_than(options.older_than))
+        .collect();
+    items.sort_by_key(|item| item.deleted);
+
+    let (mut deleted, mut failed, mut size) = (0, 0, 0u64);
+    for item in items {
+        if output.cancelled() || options.max_bytes.is_some_and(|max| size >= max) {
+            break;
+        }
+        let result = if options.dry_run { Ok(item.size()) } else { item.remove() };
+        match result {
+            Ok(freed) => {
+                deleted += 1;
+                size += freed;
+            }
+            Err(_) => failed += 1,
+        }
+    }
+    (deleted, failed, size)
 }
 
 // 子函数：清理系统驱动器临时文件
-fn clean_system_drive_temp_files<O: CleanOutput>(output: &mut O) -> Result<(usize, usize, u64)> {
+fn clean_system_drive_temp_files<O: CleanOutput>(options: &CleanOptions, output: &mut O) -> Result<(usize, usize, u64)> {
     output.print_empty();
     output.print("📁 Scanning system drive for temp files (this may take a while)...");
     
@@ -269,7 +601,8 @@ fn clean_system_drive_temp_files<O: CleanOutput>(output: &mut O) -> Result<(usiz
         &PathBuf::from(&system_drive),
         &extensions,
         &mut |_, _, _, _| {},
-        0,
+        options,
+        &|| output.cancelled(),
     )?;
     
     output.print(&format!(
@@ -281,7 +614,7 @@ fn clean_system_drive_temp_files<O: CleanOutput>(output: &mut O) -> Result<(usiz
 }
 
 // 子函数：清理用户临时目录
-fn clean_user_temp<O: CleanOutput>(output: &mut O) -> Result<(usize, usize, u64)> {
+fn clean_user_temp<O: CleanOutput>(options: &CleanOptions, output: &mut O) -> Result<(usize, usize, u64)> {
     let temp = match env::var("TEMP") {
         Ok(t) => t,
         Err(_) => return Ok((0, 0, 0)),
@@ -294,7 +627,7 @@ fn clean_user_temp<O: CleanOutput>(output: &mut O) -> Result<(usize, usize, u64)
 
     output.print_empty();
     output.print(&format!("📁 Cleaning user temp directory: {}", user_temp.display()));
-    let (deleted, failed, size) = clean_directory_with_output(&user_temp, output)?;
+    let (deleted, failed, size) = clean_directory_with_output(&user_temp, options, output)?;
     output.print(&format!(
         "   Deleted: {} items, Skipped: {}, Freed: {:.2} MB",
         deleted, failed, size as f64 / 1024.0 / 1024.0
@@ -304,7 +637,7 @@ fn clean_user_temp<O: CleanOutput>(output: &mut O) -> Result<(usize, usize, u64)
 }
 
 // 子函数：清理用户 Cookies
-fn clean_user_cookies<O: CleanOutput>(output: &mut O) -> Result<(usize, usize, u64)> {
+fn clean_user_cookies<O: CleanOutput>(options: &CleanOptions, output: &mut O) -> Result<(usize, usize, u64)> {
     let userprofile = match env::var("USERPROFILE") {
         Ok(p) => p,
         Err(_) => return Ok((0, 0, 0)),
@@ -317,7 +650,7 @@ fn clean_user_cookies<O: CleanOutput>(output: &mut O) -> Result<(usize, usize, u
 
     output.print_empty();
     output.print(&format!("📁 Cleaning user cookies: {}", cookies.display()));
-    let (deleted, failed, size) = clean_directory_with_output(&cookies, output)?;
+    let (deleted, failed, size) = clean_directory_with_output(&cookies, options, output)?;
     output.print(&format!(
         "   Deleted: {} items, Skipped: {}, Freed: {:.2} MB",
         deleted, failed, size as f64 / 1024.0 / 1024.0
@@ -327,7 +660,7 @@ fn clean_user_cookies<O: CleanOutput>(output: &mut O) -> Result<(usize, usize, u
 }
 
 // 子函数：清理用户最近文件
-fn clean_user_recent<O: CleanOutput>(output: &mut O) -> Result<(usize, usize, u64)> {
+fn clean_user_recent<O: CleanOutput>(options: &CleanOptions, output: &mut O) -> Result<(usize, usize, u64)> {
     let userprofile = match env::var("USERPROFILE") {
         Ok(p) => p,
         Err(_) => return Ok((0, 0, 0)),
@@ -340,7 +673,7 @@ fn clean_user_recent<O: CleanOutput>(output: &mut O) -> Result<(usize, usize, u6
 
     output.print_empty();
     output.print(&format!("📁 Cleaning user recent files: {}", recent.display()));
-    let (deleted, failed, size) = clean_directory_with_output(&recent, output)?;
+    let (deleted, failed, size) = clean_directory_with_output(&recent, options, output)?;
     output.print(&format!(
         "   Deleted: {} items, Skipped: {}, Freed: {:.2} MB",
         deleted, failed, size as f64 / 1024.0 / 1024.0
@@ -349,31 +682,165 @@ fn clean_user_recent<O: CleanOutput>(output: &mut O) -> Result<(usize, usize, u6
     Ok((deleted, failed, size))
 }
 
-// 子函数：清理 IE 临时文件
-fn clean_ie_temp_files<O: CleanOutput>(output: &mut O) -> Result<(usize, usize, u64)> {
-    let userprofile = match env::var("USERPROFILE") {
-        Ok(p) => p,
-        Err(_) => return Ok((0, 0, 0)),
+// 子函数：清理浏览器数据（一个类别），按浏览器分别统计；正在运行的浏览器跳过
+fn clean_browser_data<O: CleanOutput>(
+    category: Category,
+    options: &CleanOptions,
+    output: &mut O,
+) -> Result<(usize, usize, u64)> {
+    let browsers = browser::installed();
+    if browsers.is_empty() {
+        return Ok((0, 0, 0));
+    }
+
+    output.print_empty();
+    output.print(&format!("🌐 Cleaning browser {}", category.label()));
+    let running = browser::running_processes();
+    let (mut deleted, mut failed, mut size) = (0, 0, 0u64);
+    for browser in browsers {
+        let paths = browser.paths(category);
+        if paths.is_empty() {
+            continue;
+        }
+        // 预估时不受影响，只统计
+        if !options.dry_run && browser.is_running(&running) {
+            output.print(&format!("   ⚠️  {}: running, skipped (close it and try again)", browser.name));
+            failed += 1;
+            continue;
+        }
+        let Some(step_options) = options.after_freeing(size) else {
+            break;
+        };
+        let (d, f, s) = clean_paths_with_output(&paths, &step_options, &mut QuietOutput(output))?;
+        output.print(&format!(
+            "   {}: Deleted: {} items, Skipped: {}, Freed: {:.2} MB",
+            browser.name, d, f, s as f64 / 1024.0 / 1024.0
+        ));
+        deleted += d;
+        failed += f;
+        size += s;
+    }
+
+    Ok((deleted, failed, size))
+}
+
+// 子函数：清理被系统服务占用的下载缓存：停止服务，清理，再启动停止过的服务
+fn clean_service_cache<O: CleanOutput>(id: &str, options: &CleanOptions, output: &mut O) -> Result<(usize, usize, u64)> {
+    let Some(cache) = components::find_cache(id) else {
+        return Ok((0, 0, 0));
+    };
+    let Some(dir) = (cache.dir)().filter(|dir| dir.exists()) else {
+        return Ok((0, 0, 0));
     };
 
-    let ie_temp = PathBuf::from(&userprofile).join(r"Local Settings\Temporary Internet Files");
-    if !ie_temp.exists() {
+    output.print_empty();
+    output.print(&format!("📁 Cleaning {}", dir.display()));
+    // 预估时不停止服务
+    if options.dry_run {
+        return clean_directory_with_output(&dir, options, output);
+    }
+    if !is_admin() {
+        output.print("   ⚠️  Requires administrator rights, skipped");
+        return Ok((0, 1, 0));
+    }
+
+    let mut runner = components::SystemRunner;
+    let stopped = match components::stop_services(&mut runner, cache.services, &mut |line| output.print(line)) {
+        Ok(stopped) => stopped,
+        Err(e) => {
+            output.print(&format!("   ⚠️  {}, skipped", e));
+            return Ok((0, 1, 0));
+        }
+    };
+    // 无论清理是否成功都要启动服务
+    let result = clean_directory_with_output(&dir, options, output);
+    components::start_services(&mut runner, &stopped, &mut |line| output.print(line));
+    let (deleted, failed, size) = result?;
+    output.print(&format!(
+        "   Deleted: {} items, Skipped: {}, Freed: {:.2} MB",
+        deleted, failed, size as f64 / 1024.0 / 1024.0
+    ));
+
+    Ok((deleted, failed, size))
+}
+
+// 子函数：DISM 组件存储清理，输出逐行显示；释放的空间按系统盘可用空间的变化计算
+fn clean_component_store<O: CleanOutput>(options: &CleanOptions, output: &mut O) -> Result<(usize, usize, u64)> {
+    // 只在 Windows 上执行，预估时不执行（分析组件存储本身就需要几分钟）
+    if !cfg!(windows) || options.dry_run {
+        return Ok((0, 0, 0));
+    }
+    // 组件存储在系统盘上
+    let drive = policy::system_drive();
+    if options.drive.as_deref().is_some_and(|d| !policy::same_mount(d, &drive)) {
         return Ok((0, 0, 0));
     }
 
     output.print_empty();
-    output.print(&format!("📁 Cleaning IE temporary files: {}", ie_temp.display()));
-    let (deleted, failed, size) = clean_directory_with_output(&ie_temp, output)?;
+    output.print("🧩 Cleaning up the component store (DISM StartComponentCleanup, may take several minutes)...");
+    if !is_admin() {
+        output.print("   ⚠️  Requires administrator rights, skipped");
+        return Ok((0, 1, 0));
+    }
+
+    let before = policy::find_disk(&drive).map(|disk| disk.available);
+    let result = components::start_component_cleanup(&mut components::SystemRunner, &mut |line| {
+        output.print(&format!("   {}", line))
+    });
+    let after = policy::find_disk(&drive).map(|disk| disk.available);
+    let freed = match (before, after) {
+        (Some(before), Some(after)) => after.saturating_sub(before),
+        _ => 0,
+    };
+
+    match result {
+        Ok(DismResult::Completed { restart_required }) => {
+            output.print(&format!("   ✅ Component store cleaned, {} freed", history::format_bytes(freed)));
+            if restart_required {
+                output.print("   🔄 Restart Windows to finish the cleanup");
+            }
+            Ok((1, 0, freed))
+        }
+        Ok(DismResult::Failed { code, message }) => {
+            output.print(&format!(
+                "   ⚠️  DISM failed (error {}){}",
+                code,
+                message.map(|m| format!(": {}", m)).unwrap_or_default()
+            ));
+            Ok((0, 1, 0))
+        }
+        Err(e) => {
+            output.print(&format!("   ⚠️  Cannot run DISM: {}", e));
+            Ok((0, 1, 0))
+        }
+    }
+}
+
+// 子函数：清理一个应用（apps.rs 中的定义）；未安装时跳过，运行时不清理
+fn clean_app<O: CleanOutput>(id: &str, options: &CleanOptions, output: &mut O) -> Result<(usize, usize, u64)> {
+    let Some(app) = apps::find(id).filter(|app| app.installed()) else {
+        return Ok((0, 0, 0));
+    };
+    let name = CLEAN_TARGETS.iter().find(|t| t.id == id).map_or(id, |t| t.name);
+
+    output.print_empty();
+    output.print(&format!("📦 Cleaning {}", name));
+    output.print(&format!("   ℹ️  {}", app.safety));
+    if !options.dry_run && app.is_running() {
+        output.print("   ⚠️  Running, skipped (close it and try again)");
+        return Ok((0, 1, 0));
+    }
+    let (deleted, failed, size) = clean_paths_with_output(&app.paths(), options, &mut QuietOutput(output))?;
     output.print(&format!(
         "   Deleted: {} items, Skipped: {}, Freed: {:.2} MB",
         deleted, failed, size as f64 / 1024.0 / 1024.0
     ));
-    
+
     Ok((deleted, failed, size))
 }
 
 // 子函数：清理用户本地临时文件
-fn clean_user_local_temp<O: CleanOutput>(output: &mut O) -> Result<(usize, usize, u64)> {
+fn clean_user_local_temp<O: CleanOutput>(options: &CleanOptions, output: &mut O) -> Result<(usize, usize, u64)> {
     let userprofile = match env::var("USERPROFILE") {
         Ok(p) => p,
         Err(_) => return Ok((0, 0, 0)),
@@ -386,7 +853,7 @@ fn clean_user_local_temp<O: CleanOutput>(output: &mut O) -> Result<(usize, usize
 
     output.print_empty();
     output.print(&format!("📁 Cleaning user local temp: {}", local_temp.display()));
-    let (deleted, failed, size) = clean_directory_with_output(&local_temp, output)?;
+    let (deleted, failed, size) = clean_directory_with_output(&local_temp, options, output)?;
     output.print(&format!(
         "   Deleted: {} items, Skipped: {}, Freed: {:.2} MB",
         deleted, failed, size as f64 / 1024.0 / 1024.0
@@ -395,72 +862,159 @@ fn clean_user_local_temp<O: CleanOutput>(output: &mut O) -> Result<(usize, usize
     Ok((deleted, failed, size))
 }
 
+fn print_budget<O: CleanOutput>(options: &CleanOptions, output: &mut O) {
+    if let Some(max) = options.max_bytes {
+        output.print(&format!(
+            "   Oldest items first, stopping after {} freed",
+            history::format_bytes(max)
+        ));
+    }
+}
+
+fn print_exclusions<O: CleanOutput>(options: &CleanOptions, output: &mut O) {
+    let summary = options.exclusions.summary();
+    if !summary.is_empty() {
+        output.print("🛡️ Kept by exclusions:");
+        for (label, files) in summary {
+            output.print(&format!("   {}: {} files", label, files));
+        }
+    }
+
+    let skipped = options.skipped_links.list();
+    if !skipped.is_empty() {
+        output.print(&format!("🔗 Not followed: {} links / mount points", skipped.len()));
+        for (path, reason) in skipped.iter().take(5) {
+            output.print(&format!("   {} ({})", path.display(), reason));
+        }
+        if skipped.len() > 5 {
+            output.print(&format!("   ... and {} more", skipped.len() - 5));
+        }
+    }
+}
+
+fn print_budget_reached<O: CleanOutput>(freed: u64, output: &mut O) {
+    output.print_empty();
+    output.print(&format!(
+        "🎯 Freed {}, free-space target reached",
+        history::format_bytes(freed)
+    ));
+}
+
+// 单个清理目标：返回 (删除数, 跳过数, 释放字节数)
+type Cleaner<O> = fn(&CleanOptions, &mut O) -> Result<(usize, usize, u64)>;
+
+fn target_cleaner<O: CleanOutput>(id: &str) -> Cleaner<O> {
+    match id {
+        "windows-temp" => clean_windows_temp,
+        "prefetch" => clean_windows_prefetch,
+        // Windows 目录下的 .bak 文件
+        "windows-bak" => clean_windows_bak_files,
+        "recycle-bin" => clean_recycle_bin,
+        "system-drive-temp" => clean_system_drive_temp_files,
+        "windows-update-cache" => |options, output| clean_service_cache("windows-update-cache", options, output),
+        "delivery-optimization" => |options, output| clean_service_cache("delivery-optimization", options, output),
+        "component-store" => clean_component_store,
+        "user-temp" => clean_user_temp,
+        "cookies" => clean_user_cookies,
+        "recent" => clean_user_recent,
+        "browser-cache" => |options, output| clean_browser_data(Category::Cache, options, output),
+        "browser-code-cache" => |options, output| clean_browser_data(Category::CodeCache, options, output),
+        "browser-gpu-cache" => |options, output| clean_browser_data(Category::GpuCache, options, output),
+        "browser-service-workers" => |options, output| clean_browser_data(Category::ServiceWorker, options, output),
+        "browser-cookies" => |options, output| clean_browser_data(Category::Cookies, options, output),
+        "browser-history" => |options, output| clean_browser_data(Category::History, options, output),
+        "crash-dumps" => |options, output| clean_app("crash-dumps", options, output),
+        "error-reports" => |options, output| clean_app("error-reports", options, output),
+        "cbs-logs" => |options, output| clean_app("cbs-logs", options, output),
+        "jetbrains-caches" => |options, output| clean_app("jetbrains-caches", options, output),
+        "vscode-caches" => |options, output| clean_app("vscode-caches", options, output),
+        "teams-cache" => |options, output| clean_app("teams-cache", options, output),
+        "slack-cache" => |options, output| clean_app("slack-cache", options, output),
+        "user-local-temp" => clean_user_local_temp,
+        // 目标只来自 CLEAN_TARGETS（见 every_target_has_a_cleaner 测试）
+        _ => unreachable!("unknown clean target '{}'", id),
+    }
+}
+
+// 预估时使用：不输出，只响应取消
+struct ScanOutput<'a> {
+    cancel: &'a AtomicBool,
+}
+
+impl CleanOutput for ScanOutput<'_> {
+    fn print(&mut self, _msg: &str) {}
+    fn cancelled(&self) -> bool {
+        self.cancel.load(Ordering::Relaxed)
+    }
+}
+
+// 只转发取消状态，不输出逐个文件的删除记录
+struct QuietOutput<'a, O: CleanOutput>(&'a mut O);
+
+impl<O: CleanOutput> CleanOutput for QuietOutput<'_, O> {
+    fn print(&mut self, _msg: &str) {}
+    fn cancelled(&self) -> bool {
+        self.0.cancelled()
+    }
+}
+
+// 预估一个清理目标可释放的 (文件数, 字节数)，不删除任何文件
+pub fn estimate_target(id: &str, options: &CleanOptions, cancel: &AtomicBool) -> (usize, u64) {
+    let options = CleanOptions {
+        dry_run: true,
+        max_bytes: None,
+        exclusions: Arc::new(Exclusions::load().unwrap_or_default()),
+        skipped_links: Arc::default(),
+        ..options.clone()
+    };
+    let cleaner: Cleaner<ScanOutput> = target_cleaner(id);
+    match cleaner(&options, &mut ScanOutput { cancel }) {
+        Ok((items, _, bytes)) => (items, bytes),
+        Err(_) => (0, 0),
+    }
+}
+
 // Core cleaning logic (shared by all variants)
-fn clean_temp_core<O: CleanOutput>(include_user: bool, output: &mut O) -> Result<(usize, usize, u64)> {
+fn clean_temp_core<O: CleanOutput>(
+    options: &CleanOptions,
+    output: &mut O,
+    targets: &mut Vec<TargetResult>,
+) -> Result<()> {
+    let options = &options.prepare_run()?;
     output.print("🧹 Cleaning temporary files...");
+    if let Some(profile) = &options.profile {
+        output.print(&format!("   Profile: {}", profile));
+    }
+    if let Some(age) = options.older_than {
+        output.print(&format!("   Only items not modified in the last {}", format_age(age)));
+    }
+    print_budget(options, output);
     output.print("═══════════════════════════════════════════════════");
 
-    let mut total_deleted = 0;
-    let mut total_failed = 0;
-    let mut total_size_freed: u64 = 0;
-
-    // 清理 Windows Temp 目录
-    let (deleted, failed, size) = clean_windows_temp(output)?;
-    total_deleted += deleted;
-    total_failed += failed;
-    total_size_freed += size;
-
-    // 清理 Windows Prefetch
-    let (deleted, failed, size) = clean_windows_prefetch(output)?;
-    total_deleted += deleted;
-    total_failed += failed;
-    total_size_freed += size;
-
-    // 清理 Windows 目录下的 .bak 文件
-    let (deleted, failed, size) = clean_windows_bak_files(output)?;
-    total_deleted += deleted;
-    total_failed += failed;
-    total_size_freed += size;
-
-    // 清理回收站
-    let (deleted, failed, size) = clean_recycle_bin(output)?;
-    total_deleted += deleted;
-    total_failed += failed;
-    total_size_freed += size;
-
-    // 清理系统驱动器临时文件
-    let (deleted, failed, size) = clean_system_drive_temp_files(output)?;
-    total_deleted += deleted;
-    total_failed += failed;
-    total_size_freed += size;
-
-    // 如果需要清理用户相关目录
-    if include_user {
-        let (deleted, failed, size) = clean_user_temp(output)?;
-        total_deleted += deleted;
-        total_failed += failed;
-        total_size_freed += size;
-
-        let (deleted, failed, size) = clean_user_cookies(output)?;
-        total_deleted += deleted;
-        total_failed += failed;
-        total_size_freed += size;
-
-        let (deleted, failed, size) = clean_user_recent(output)?;
-        total_deleted += deleted;
-        total_failed += failed;
-        total_size_freed += size;
-
-        let (deleted, failed, size) = clean_ie_temp_files(output)?;
-        total_deleted += deleted;
-        total_failed += failed;
-        total_size_freed += size;
-
-        let (deleted, failed, size) = clean_user_local_temp(output)?;
-        total_deleted += deleted;
-        total_failed += failed;
-        total_size_freed += size;
+    let steps: Vec<(&str, Cleaner<O>)> = options
+        .selected_targets()
+        .into_iter()
+        .map(|target| (target.name, target_cleaner::<O>(target.id)))
+        .collect();
+
+    for (index, (name, cleaner)) in steps.iter().enumerate() {
+        if output.cancelled() {
+            return Err(Cancelled.into());
+        }
+        let freed: u64 = targets.iter().map(|t| t.freed_bytes).sum();
+        let Some(step_options) = options.after_freeing(freed) else {
+            print_budget_reached(freed, output);
+            break;
+        };
+        output.progress(index, steps.len(), name);
+        let (deleted, failed, size) = cleaner(&step_options, output)?;
+        targets.push(TargetResult::new(name, deleted, failed, size));
     }
+    output.progress(steps.len(), steps.len(), "Done");
+
+    let total_deleted: usize = targets.iter().map(|t| t.deleted).sum();
+    let total_failed: usize = targets.iter().map(|t| t.skipped).sum();
+    let total_size_freed: u64 = targets.iter().map(|t| t.freed_bytes).sum();
 
     output.print_empty();
     output.print("═══════════════════════════════════════════════════");
@@ -472,47 +1026,258 @@ fn clean_temp_core<O: CleanOutput>(include_user: bool, output: &mut O) -> Result
         total_size_freed as f64 / 1024.0 / 1024.0,
         total_size_freed as f64 / 1024.0 / 1024.0 / 1024.0
     ));
+    print_exclusions(options, output);
     output.print("═══════════════════════════════════════════════════");
     output.print("✨ Cleaning completed!");
 
-    Ok((total_deleted, total_failed, total_size_freed))
+    Ok(())
+}
+
+// Clean temporary files
+pub fn clean_temp_task(options: CleanOptions) -> Task {
+    Task::new("Clean Temp Files", move |ctx| {
+        history::track("clean-temp", &options.history_options(), |targets| {
+            clean_temp_core(&options, ctx, targets)
+        })
+    })
+}
+
+// 开发工具的下载缓存：删除后会在下次使用时重新下载，不影响已安装的项目
+fn dev_cache_dirs() -> Vec<(&'static str, PathBuf)> {
+    let home = config::home_dir();
+    let mut dirs = Vec::new();
+
+    if cfg!(windows) {
+        if let Some(local) = config::env_dir("LOCALAPPDATA") {
+            dirs.push(("npm", local.join("npm-cache")));
+            dirs.push(("pip", local.join(r"pip\Cache")));
+            dirs.push(("Yarn", local.join(r"Yarn\Cache")));
+            dirs.push(("Go build", local.join("go-build")));
+        }
+    } else if let (Some(home), Some(cache)) = (&home, config::xdg_cache_home()) {
+        dirs.push(("npm", home.join(".npm").join("_cacache")));
+        dirs.push(("pip", cache.join("pip")));
+        dirs.push(("Yarn", cache.join("yarn")));
+        dirs.push(("Go build", cache.join("go-build")));
+    }
+    if let Some(home) = &home {
+        dirs.push(("Cargo registry", home.join(".cargo").join("registry").join("cache")));
+        dirs.push(("Gradle", home.join(".gradle").join("caches")));
+    }
+    dirs
+}
+
+fn clean_dev_caches_core<O: CleanOutput>(
+    options: &CleanOptions,
+    output: &mut O,
+    targets: &mut Vec<TargetResult>,
+) -> Result<()> {
+    let options = &options.prepare_run()?;
+    output.print("🧹 Cleaning developer tool caches...");
+    let dirs: Vec<_> = dev_cache_dirs().into_iter().filter(|(_, dir)| dir.exists()).collect();
+    if dirs.is_empty() {
+        output.print("   No developer caches found");
+    }
+    print_budget(options, output);
+
+    for (index, (name, dir)) in dirs.iter().enumerate() {
+        if output.cancelled() {
+            return Err(Cancelled.into());
+        }
+        let freed: u64 = targets.iter().map(|t| t.freed_bytes).sum();
+        let Some(step_options) = options.after_freeing(freed) else {
+            print_budget_reached(freed, output);
+            break;
+        };
+        output.progress(index, dirs.len(), name);
+        output.print_empty();
+        output.print(&format!("📁 Cleaning {} cache: {}", name, dir.display()));
+        let (deleted, failed, size) = clean_directory_with_output(dir, &step_options, output)?;
+        output.print(&format!(
+            "   Deleted: {} items, Skipped: {}, Freed: {:.2} MB",
+            deleted, failed, size as f64 / 1024.0 / 1024.0
+        ));
+        targets.push(TargetResult::new(&format!("{} cache", name), deleted, failed, size));
+    }
+    output.progress(dirs.len(), dirs.len(), "Done");
+    if !targets.is_empty() {
+        output.print_empty();
+        print_exclusions(options, output);
+    }
+    Ok(())
 }
 
-// Clean temporary files (console output)
-pub fn clean_temp(include_user: bool) -> Result<()> {
-    let mut output = ConsoleOutput;
-    clean_temp_core(include_user, &mut output)?;
+fn empty_trash_core<O: CleanOutput>(
+    options: &CleanOptions,
+    output: &mut O,
+    targets: &mut Vec<TargetResult>,
+) -> Result<()> {
+    let (deleted, failed, size) = clean_recycle_bin(options, output)?;
+    targets.push(TargetResult::new("Recycle Bin", deleted, failed, size));
     Ok(())
 }
 
+// Clean developer tool caches (npm, pip, Yarn, Go, Cargo, Gradle)
+pub fn clean_dev_caches_task() -> Task {
+    Task::new("Clean Developer Caches", |ctx| {
+        let options = CleanOptions::default();
+        history::track("clean-dev-caches", &[], |targets| clean_dev_caches_core(&options, ctx, targets))
+    })
+}
+
+// Empty the recycle bin
+pub fn empty_trash_task(options: CleanOptions) -> Task {
+    Task::new("Empty Recycle Bin", move |ctx| {
+        let mut history_options = options.history_options();
+        history_options.retain(|(name, _)| *name != "include_user");
+        history::track("empty-trash", &history_options, |targets| empty_trash_core(&options, ctx, targets))
+    })
+}
+
+// 计划任务可以执行的操作
+pub const SCHEDULABLE_TASKS: [&str; 3] = ["clean-temp", "clean-dev-caches", "empty-trash"];
+
+// 非交互输出（计划任务）：不打印到终端，只收集到内存中写入报告
+#[derive(Default)]
+pub struct ReportOutput {
+    pub lines: Vec<String>,
+}
+
+impl CleanOutput for ReportOutput {
+    fn print(&mut self, msg: &str) {
+        self.lines.push(msg.to_string());
+    }
+}
+
+fn run_target_core<O: CleanOutput>(
+    task: &str,
+    options: &CleanOptions,
+    output: &mut O,
+    targets: &mut Vec<TargetResult>,
+) -> Result<()> {
+    match task {
+        "clean-temp" => clean_temp_core(options, output, targets),
+        "clean-dev-caches" => clean_dev_caches_core(options, output, targets),
+        "empty-trash" => empty_trash_core(options, output, targets),
+        _ => Err(anyhow::anyhow!(
+            "Unknown task '{}' (available: {})",
+            task,
+            SCHEDULABLE_TASKS.join(", ")
+        )),
+    }
+}
+
+// 无人值守执行一个清理操作，返回输出行和结果（同样写入操作历史）
+pub fn run_unattended(task: &str, options: &CleanOptions) -> (Vec<String>, Result<()>) {
+    let mut output = ReportOutput::default();
+    let result = history::track(task, &options.history_options(), |targets| {
+        run_target_core(task, options, &mut output, targets)
+    });
+    (output.lines, result)
+}
+
+// 按顺序执行多个清理操作，释放 options.max_bytes 后跳过剩余的操作（磁盘空间策略）
+fn clean_until_freed_core<O: CleanOutput>(
+    tasks: &[String],
+    options: &CleanOptions,
+    output: &mut O,
+    targets: &mut Vec<TargetResult>,
+) -> Result<()> {
+    for (index, task) in tasks.iter().enumerate() {
+        let freed: u64 = targets.iter().map(|t| t.freed_bytes).sum();
+        let Some(step_options) = options.after_freeing(freed) else {
+            output.print(&format!("⏭ Not needed: {}", tasks[index..].join(", ")));
+            break;
+        };
+        output.print_empty();
+        output.print(&format!("▶ {}", task));
+        // 每个操作按自己的目标统计释放量，之后再合并
+        let mut task_targets = Vec::new();
+        let result = run_target_core(task, &step_options, output, &mut task_targets);
+        targets.extend(task_targets);
+        result?;
+    }
+    Ok(())
+}
+
+fn policy_history_options(tasks: &[String], options: &CleanOptions) -> Vec<(&'static str, String)> {
+    let mut history_options = options.history_options();
+    history_options.push(("targets", tasks.join(",")));
+    history_options
+}
+
+pub fn clean_until_freed_task(tasks: Vec<String>, options: CleanOptions) -> Task {
+    Task::new("Free Disk Space", move |ctx| {
+        history::track("disk-policy", &policy_history_options(&tasks, &options), |targets| {
+            clean_until_freed_core(&tasks, &options, ctx, targets)
+        })
+    })
+}
+
+pub fn run_until_freed(tasks: &[String], options: &CleanOptions) -> (Vec<String>, Result<()>) {
+    let mut output = ReportOutput::default();
+    let result = history::track("disk-policy", &policy_history_options(tasks, options), |targets| {
+        clean_until_freed_core(tasks, options, &mut output, targets)
+    });
+    (output.lines, result)
+}
+
 // Clean a directory with custom output (internal helper)
-fn clean_directory_with_output<O: CleanOutput>(dir: &PathBuf, output: &mut O) -> Result<(usize, usize, u64)> {
+fn clean_directory_with_output<O: CleanOutput>(
+    dir: &PathBuf,
+    options: &CleanOptions,
+    output: &mut O,
+) -> Result<(usize, usize, u64)> {
     let mut deleted_count = 0;
     let mut failed_count = 0;
     let mut total_size = 0u64;
 
-    if let Ok(entries) = fs::read_dir(dir) {
-        for entry in entries.flatten() {
-            let path = entry.path();
+    // 清理目录本身是链接时不进入
+    if walk::is_link(dir) {
+        options.skipped_links.record(dir, &EntryKind::Link);
+        output.print(&format!("   🔗 {} is a link, not followed", dir.display()));
+        return Ok((0, 0, 0));
+    }
+    // 不越过文件系统时，目录中的内容和目录本身在同一个驱动器上
+    if !options.allows(dir) {
+        output.print(&format!("   ⏭ Not on {}, skipped", options.drive.as_deref().unwrap_or_default()));
+        return Ok((0, 0, 0));
+    }
+    let boundary = Boundary::of(dir, options.cross_filesystems);
 
-            let size = if path.is_file() {
-                fs::metadata(&path).map(|m| m.len()).unwrap_or(0)
-            } else if path.is_dir() {
-                calculate_dir_size(&path)
-            } else {
-                0
-            };
+    if let Ok(entries) = fs::read_dir(dir) {
+        let mut paths: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).collect();
+        // 有释放上限时从最旧的开始删除
+        if options.max_bytes.is_some() {
+            paths.sort_by_cached_key(|path| newest_modification(path));
+        }
 
-            let result = if path.is_dir() {
-                fs::remove_dir_all(&path)
-            } else {
-                fs::remove_file(&path)
+        for path in paths {
+            if output.cancelled() || options.max_bytes.is_some_and(|max| total_size >= max) {
+                break;
+            }
+            let is_dir = match boundary.classify(&path) {
+                Ok(kind @ (EntryKind::Link | EntryKind::OtherFilesystem)) => {
+                    options.skipped_links.record(&path, &kind);
+                    continue;
+                }
+                Ok(kind) => matches!(kind, EntryKind::Dir),
+                Err(_) => continue,
             };
+            if !is_older_than(&path, options.older_than) {
+                continue;
+            }
+            if let Some(rule) = options.exclusions.rule_for(&path, is_dir) {
+                options.exclusions.record(rule, count_files(&path));
+                continue;
+            }
 
-            match result {
-                Ok(_) => {
+            match remove_path(&path, &boundary, options) {
+                // 整个目录都被排除规则保留
+                Ok(removal) if removal.kept && removal.freed == 0 => {}
+                Ok(removal) => {
                     deleted_count += 1;
-                    total_size += size;
+                    total_size += removal.freed;
                     if deleted_count <= 5 {
                         let filename = path.file_name().unwrap_or_default().to_string_lossy();
                         let display_name = if filename.len() > 60 {
@@ -549,12 +1314,68 @@ fn clean_directory_with_output<O: CleanOutput>(dir: &PathBuf, output: &mut O) ->
     Ok((deleted_count, failed_count, total_size))
 }
 
+// 清理一组路径：目录清理其中的内容，文件直接删除
+fn clean_paths_with_output<O: CleanOutput>(
+    paths: &[PathBuf],
+    options: &CleanOptions,
+    output: &mut O,
+) -> Result<(usize, usize, u64)> {
+    let (mut deleted, mut failed, mut size) = (0, 0, 0u64);
+    for path in paths {
+        if output.cancelled() {
+            break;
+        }
+        let Some(step_options) = options.after_freeing(size) else {
+            break;
+        };
+        if walk::is_link(path) || path.is_dir() {
+            let (d, f, s) = clean_directory_with_output(path, &step_options, output)?;
+            deleted += d;
+            failed += f;
+            size += s;
+            continue;
+        }
+        if !is_older_than(path, options.older_than) || !options.allows(path) {
+            continue;
+        }
+        if let Some(rule) = options.exclusions.rule_for(path, false) {
+            options.exclusions.record(rule, 1);
+            continue;
+        }
+        let boundary = Boundary::of(path.parent().unwrap_or(path), options.cross_filesystems);
+        match remove_path(path, &boundary, options) {
+            Ok(removal) if removal.kept => {}
+            Ok(removal) => {
+                deleted += 1;
+                size += removal.freed;
+            }
+            Err(_) => failed += 1,
+        }
+    }
+    Ok((deleted, failed, size))
+}
+
+// 统计一组路径中可清理的 (文件数, 字节数)（lemo browsers）
+pub fn measure_paths(paths: &[PathBuf]) -> (usize, u64) {
+    let options = CleanOptions {
+        dry_run: true,
+        exclusions: Arc::new(Exclusions::load().unwrap_or_default()),
+        ..Default::default()
+    };
+    let cancel = AtomicBool::new(false);
+    match clean_paths_with_output(paths, &options, &mut ScanOutput { cancel: &cancel }) {
+        Ok((items, _, bytes)) => (items, bytes),
+        Err(_) => (0, 0),
+    }
+}
+
 // Clean files by extension with progress callback
 pub fn clean_files_by_extension_with_progress<F>(
-    dir: &PathBuf,
+    dir: &Path,
     extensions: &[&str],
     progress_callback: &mut F,
-    depth: usize,
+    options: &CleanOptions,
+    cancelled: &dyn Fn() -> bool,
 ) -> Result<(usize, usize, u64)>
 where
     F: FnMut(&str, usize, usize, u64),
@@ -563,14 +1384,83 @@ where
     let mut failed_count = 0;
     let mut total_size = 0u64;
 
+    if walk::is_link(dir) {
+        options.skipped_links.record(dir, &EntryKind::Link);
+        return Ok((0, 0, 0));
+    }
+    if !options.allows(dir) {
+        return Ok((0, 0, 0));
+    }
+    let boundary = Boundary::of(dir, options.cross_filesystems);
+    let mut files = Vec::new();
+    find_files_by_extension(dir, extensions, options, &boundary, 0, None, cancelled, &mut files);
+    // 有释放上限时从最旧的文件开始删除
+    if options.max_bytes.is_some() {
+        files.sort_by_cached_key(|path| newest_modification(path));
+    }
+
+    for path in files {
+        if cancelled() || options.max_bytes.is_some_and(|max| total_size >= max) {
+            break;
+        }
+        let size = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+        let result = if options.dry_run { Ok(()) } else { fs::remove_file(&path) };
+
+        match result {
+            Ok(_) => {
+                deleted_count += 1;
+                total_size += size;
+
+                // 每删除一个文件就更新进度
+                progress_callback(
+                    &path.display().to_string(),
+                    deleted_count,
+                    failed_count,
+                    total_size,
+                );
+            }
+            Err(_) => {
+                failed_count += 1;
+            }
+        }
+    }
+
+    Ok((deleted_count, failed_count, total_size))
+}
+
+// 递归查找扩展名匹配且足够旧的文件；kept_by 为保留当前目录的排除规则，其中匹配的文件只计数
+// 扫描整个驱动器可能需要很久，取消后立即停止
+#[allow(clippy::too_many_arguments)]
+fn find_files_by_extension(
+    dir: &Path,
+    extensions: &[&str],
+    options: &CleanOptions,
+    boundary: &Boundary,
+    depth: usize,
+    kept_by: Option<usize>,
+    cancelled: &dyn Fn() -> bool,
+    found: &mut Vec<PathBuf>,
+) {
     // 限制递归深度，避免过深（从第一级子目录开始计数）
     if depth > 5 {
-        return Ok((0, 0, 0));
+        return;
     }
 
     if let Ok(entries) = fs::read_dir(dir) {
-        for entry in entries.flatten() {
-            let path = entry.path();
+        let children: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).collect();
+        // 项目根目录中的文件全部保留（扫描的起始目录本身除外）
+        let kept_by = kept_by.or_else(|| {
+            if depth > 0 {
+                options.exclusions.project_rule(&children)
+            } else {
+                None
+            }
+        });
+
+        for path in children {
+            if cancelled() {
+                return;
+            }
 
             // 只在根目录层级（depth == 0）跳过系统关键目录
             if depth == 0 {
@@ -591,185 +1481,133 @@ where
                 }
             }
 
-            if path.is_file() {
+            let kind = match boundary.classify(&path) {
+                Ok(kind @ (EntryKind::Link | EntryKind::OtherFilesystem)) => {
+                    options.skipped_links.record(&path, &kind);
+                    continue;
+                }
+                Ok(kind) => kind,
+                Err(_) => continue,
+            };
+            let is_dir = matches!(kind, EntryKind::Dir);
+            let kept = kept_by.or_else(|| options.exclusions.rule_for(&path, is_dir));
+            if !is_dir {
                 if let Some(ext) = path.extension() {
                     if let Some(ext_str) = ext.to_str() {
-                        if extensions.contains(&ext_str) {
-                            let size = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
-
-                            match fs::remove_file(&path) {
-                                Ok(_) => {
-                                    deleted_count += 1;
-                                    total_size += size;
-                                    
-                                    // 每删除一个文件就更新进度
-                                    progress_callback(
-                                        &path.display().to_string(),
-                                        deleted_count,
-                                        failed_count,
-                                        total_size,
-                                    );
-                                }
-                                Err(_) => {
-                                    failed_count += 1;
-                                }
+                        if extensions.contains(&ext_str) && is_older_than(&path, options.older_than) {
+                            match kept {
+                                Some(rule) => options.exclusions.record(rule, 1),
+                                None => found.push(path),
                             }
                         }
                     }
                 }
-            } else if path.is_dir() {
-                // 递归清理子目录
-                if let Ok((d, f, s)) = clean_files_by_extension_with_progress(
-                    &path,
-                    extensions,
-                    progress_callback,
-                    depth + 1,
-                ) {
-                    deleted_count += d;
-                    failed_count += f;
-                    total_size += s;
-                }
+            } else {
+                // 递归查找子目录
+                find_files_by_extension(&path, extensions, options, boundary, depth + 1, kept, cancelled, found);
             }
         }
     }
-
-    Ok((deleted_count, failed_count, total_size))
 }
 
-// Calculate directory size
-pub fn calculate_dir_size(dir: &PathBuf) -> u64 {
-    let mut size = 0u64;
+// remove_path 的结果：释放的字节数，以及是否有内容被排除规则保留（目录因此未删除）
+struct Removal {
+    freed: u64,
+    kept: bool,
+}
 
-    if let Ok(entries) = fs::read_dir(dir) {
-        for entry in entries.flatten() {
-            let path = entry.path();
-            if path.is_file() {
-                size += fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
-            } else if path.is_dir() {
-                size += calculate_dir_size(&path);
+// 删除文件或目录（dry run 时只统计），目录中被排除的内容保留并计入对应规则
+// 链接和其他文件系统上的内容不删除，同样视为保留
+fn remove_path(path: &Path, boundary: &Boundary, options: &CleanOptions) -> std::io::Result<Removal> {
+    match boundary.classify(path)? {
+        kind @ (EntryKind::Link | EntryKind::OtherFilesystem) => {
+            options.skipped_links.record(path, &kind);
+            return Ok(Removal { freed: 0, kept: true });
+        }
+        EntryKind::File(size) => {
+            if !options.dry_run {
+                fs::remove_file(path)?;
             }
+            return Ok(Removal { freed: size, kept: false });
         }
+        EntryKind::Dir => {}
     }
 
-    size
-}
-
-// Show system information
-// ========================================
-// Streaming output functions (callback-based)
-// ========================================
-
-// Fix icon cache with streaming output (callback-based)
-pub fn fix_icon_cache_with_streaming() -> impl FnOnce(Box<dyn FnMut(String) + Send>) -> Result<()> + Send + 'static {
-    |mut callback: Box<dyn FnMut(String) + Send>| {
-        callback("🔧 Fixing icon cache...".to_string());
-        callback(String::new());
-
-        callback("⏳ Closing Windows Explorer...".to_string());
-        let _ = Command::new("taskkill")
-            .args(&["/f", "/im", "explorer.exe"])
-            .output();
-
-        thread::sleep(Duration::from_secs(2));
-
-        let user_profile = match env::var("USERPROFILE") {
-            Ok(p) => p,
-            Err(_) => return Err(anyhow::anyhow!("Cannot get USERPROFILE")),
-        };
-        let mut cache_files = Vec::new();
-
-        let icon_cache = PathBuf::from(&user_profile).join(r"AppData\Local\IconCache.db");
-        cache_files.push(icon_cache);
-
-        let icon_cache_pattern =
-            PathBuf::from(&user_profile).join(r"AppData\Local\Microsoft\Windows\Explorer");
+    let children: Vec<PathBuf> = fs::read_dir(path)?.flatten().map(|entry| entry.path()).collect();
+    let exclusions = &options.exclusions;
+    if let Some(rule) = exclusions.project_rule(&children) {
+        exclusions.record(rule, count_files(path));
+        return Ok(Removal { freed: 0, kept: true });
+    }
 
-        if let Ok(entries) = fs::read_dir(icon_cache_pattern) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if let Some(file_name) = path.file_name() {
-                    if let Some(name) = file_name.to_str() {
-                        if name.starts_with("iconcache_") && name.ends_with(".db") {
-                            cache_files.push(path);
-                        }
-                    }
-                }
-            }
+    let mut removal = Removal { freed: 0, kept: false };
+    let mut error = None;
+    for child in children {
+        let is_dir = fs::symlink_metadata(&child).is_ok_and(|m| m.is_dir());
+        if let Some(rule) = exclusions.rule_for(&child, is_dir) {
+            exclusions.record(rule, count_files(&child));
+            removal.kept = true;
+            continue;
         }
-
-        let mut deleted_count = 0;
-        let mut skipped_count = 0;
-
-        for file in cache_files {
-            match fs::remove_file(&file) {
-                Ok(_) => {
-                    callback(format!("✅ Deleted: {:?}", file));
-                    deleted_count += 1;
-                }
-                Err(e) => {
-                    callback(format!("⚠️  Skipped: {:?} ({})", file, e));
-                    skipped_count += 1;
-                }
+        match remove_path(&child, boundary, options) {
+            Ok(child_removal) => {
+                removal.freed += child_removal.freed;
+                removal.kept |= child_removal.kept;
             }
+            Err(e) => error = error.or(Some(e)),
         }
-
-        callback(String::new());
-        callback(format!(
-            "📊 Summary: Deleted {} files, Skipped {} files",
-            deleted_count, skipped_count
-        ));
-
-        callback(String::new());
-        callback("🔄 Restarting Windows Explorer...".to_string());
-        match Command::new("explorer.exe").spawn() {
-            Ok(_) => callback("✨ Fix completed! Desktop will restore in a few seconds.".to_string()),
-            Err(e) => callback(format!("⚠️  Warning: Failed to restart Explorer: {}", e)),
-        }
-        thread::sleep(Duration::from_secs(3));
-
-        Ok(())
     }
-}
-
-// Clean temp files with streaming output (callback-based)
-pub fn clean_temp_with_streaming() -> impl FnOnce(Box<dyn FnMut(String) + Send>) -> Result<()> + Send + 'static {
-    |mut callback: Box<dyn FnMut(String) + Send>| {
-        let mut output = CallbackOutput { callback: &mut callback };
-        clean_temp_core(false, &mut output)?;
-        Ok(())
+    if let Some(e) = error {
+        return Err(e);
     }
+    if !removal.kept && !options.dry_run {
+        fs::remove_dir(path)?;
+    }
+    Ok(removal)
 }
 
-// Clean directory with streaming output
-// Show system info with streaming output (callback-based)
 /// Install the application to the system
-pub fn install_to_system() -> Result<()> {
+pub fn install_task() -> Task {
+    Task::new("Install to System", |ctx| {
+        history::track("install", &[], |_| install_core(ctx))
+    })
+}
+
+/// Uninstall the application from the system
+pub fn uninstall_task() -> Task {
+    Task::new("Uninstall from System", |ctx| {
+        history::track("uninstall", &[], |_| uninstall_core(ctx))
+    })
+}
+
+#[cfg(windows)]
+fn install_core(ctx: &mut TaskContext) -> Result<()> {
     use std::os::windows::process::CommandExt;
     
-    println!("Starting installation...");
+    ctx.println("Starting installation...");
     
     // Get installation path
     let local_appdata = env::var("LOCALAPPDATA")?;
     let install_path = PathBuf::from(local_appdata).join("lemo");
     
-    println!("Installation directory: {}", install_path.display());
+    ctx.println(format!("Installation directory: {}", install_path.display()));
     
     // Create installation directory
     if !install_path.exists() {
         fs::create_dir_all(&install_path)?;
-        println!("Created installation directory");
+        ctx.println("Created installation directory");
     }
     
     // Copy current executable to installation directory
     let current_exe = env::current_exe()?;
     let target_exe = install_path.join("lemo.exe");
     
-    println!("Copying executable...");
+    ctx.println("Copying executable...");
     fs::copy(&current_exe, &target_exe)?;
-    println!("Executable copied successfully");
+    ctx.println("Executable copied successfully");
     
     // Add to system PATH using PowerShell
-    println!("Adding to system PATH...");
+    ctx.println("Adding to system PATH...");
     
     let install_path_str = install_path.to_string_lossy().to_string();
     let ps_script = format!(
@@ -791,40 +1629,42 @@ pub fn install_to_system() -> Result<()> {
     const CREATE_NO_WINDOW: u32 = 0x08000000;
     
     let output = Command::new("powershell")
-        .args(&["-NoProfile", "-ExecutionPolicy", "Bypass", "-Command", &ps_script])
+        .args(["-NoProfile", "-ExecutionPolicy", "Bypass", "-Command", &ps_script])
         .creation_flags(CREATE_NO_WINDOW)
         .output()?;
     
     if output.status.success() {
-        println!("{}", String::from_utf8_lossy(&output.stdout));
-        println!();
-        println!("====================================");
-        println!("Installation completed successfully!");
-        println!("====================================");
-        println!();
-        println!("Installation location: {}", install_path.display());
-        println!();
-        println!("Usage:");
-        println!("  lemo fix-icon-cache    # Fix icon cache");
-        println!("  lemo clean-temp        # Clean temporary files");
-        println!("  lemo install           # Install to system");
-        println!("  lemo uninstall         # Uninstall from system");
-        println!();
-        println!("NOTE: You may need to restart your terminal for PATH changes to take effect");
-        println!();
+        for line in String::from_utf8_lossy(&output.stdout).lines() {
+            ctx.println(line);
+        }
+        ctx.println("");
+        ctx.println("====================================");
+        ctx.println("Installation completed successfully!");
+        ctx.println("====================================");
+        ctx.println("");
+        ctx.println(format!("Installation location: {}", install_path.display()));
+        ctx.println("");
+        ctx.println("Usage:");
+        ctx.println("  lemo fix-icon-cache    # Fix icon cache");
+        ctx.println("  lemo clean-temp        # Clean temporary files");
+        ctx.println("  lemo install           # Install to system");
+        ctx.println("  lemo uninstall         # Uninstall from system");
+        ctx.println("");
+        ctx.println("NOTE: You may need to restart your terminal for PATH changes to take effect");
+        ctx.println("");
     } else {
-        eprintln!("Failed to add to PATH: {}", String::from_utf8_lossy(&output.stderr));
+        ctx.println(format!("Failed to add to PATH: {}", String::from_utf8_lossy(&output.stderr)));
         return Err(anyhow::anyhow!("Failed to add to system PATH"));
     }
     
     Ok(())
 }
 
-/// Uninstall the application from the system
-pub fn uninstall_from_system() -> Result<()> {
+#[cfg(windows)]
+fn uninstall_core(ctx: &mut TaskContext) -> Result<()> {
     use std::os::windows::process::CommandExt;
     
-    println!("Starting uninstallation...");
+    ctx.println("Starting uninstallation...");
     
     // Get installation path
     let local_appdata = env::var("LOCALAPPDATA")?;
@@ -832,7 +1672,7 @@ pub fn uninstall_from_system() -> Result<()> {
     let install_path_str = install_path.to_string_lossy().to_string();
     
     // Remove from system PATH using PowerShell
-    println!("Removing from system PATH...");
+    ctx.println("Removing from system PATH...");
     
     let ps_script = format!(
         r#"
@@ -853,31 +1693,201 @@ pub fn uninstall_from_system() -> Result<()> {
     const CREATE_NO_WINDOW: u32 = 0x08000000;
     
     let output = Command::new("powershell")
-        .args(&["-NoProfile", "-ExecutionPolicy", "Bypass", "-Command", &ps_script])
+        .args(["-NoProfile", "-ExecutionPolicy", "Bypass", "-Command", &ps_script])
         .creation_flags(CREATE_NO_WINDOW)
         .output()?;
     
     if output.status.success() {
-        println!("{}", String::from_utf8_lossy(&output.stdout));
+        for line in String::from_utf8_lossy(&output.stdout).lines() {
+            ctx.println(line);
+        }
     } else {
-        eprintln!("Warning: Failed to remove from PATH: {}", String::from_utf8_lossy(&output.stderr));
+        ctx.println(format!("Warning: Failed to remove from PATH: {}", String::from_utf8_lossy(&output.stderr)));
     }
     
     // Delete installation directory
     if install_path.exists() {
-        println!("Deleting installation directory: {}", install_path.display());
+        ctx.println(format!("Deleting installation directory: {}", install_path.display()));
         fs::remove_dir_all(&install_path)?;
-        println!("Installation directory deleted");
+        ctx.println("Installation directory deleted");
     } else {
-        println!("Installation directory does not exist");
+        ctx.println("Installation directory does not exist");
     }
     
-    println!();
-    println!("====================================");
-    println!("Uninstallation completed!");
-    println!("====================================");
-    println!();
+    ctx.println("");
+    ctx.println("====================================");
+    ctx.println("Uninstallation completed!");
+    ctx.println("====================================");
+    ctx.println("");
     
     Ok(())
 }
 
+#[cfg(not(windows))]
+fn install_core(_ctx: &mut TaskContext) -> Result<()> {
+    Err(anyhow::anyhow!("Installing to the system PATH is only supported on Windows"))
+}
+
+#[cfg(not(windows))]
+fn uninstall_core(_ctx: &mut TaskContext) -> Result<()> {
+    Err(anyhow::anyhow!("Uninstalling from the system PATH is only supported on Windows"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 临时目录：<tmp>/lemo-clean-<name>-<pid>/{temp, outside}
+    fn temp_tree(name: &str) -> (PathBuf, PathBuf, PathBuf) {
+        let root = std::env::temp_dir().join(format!("lemo-clean-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let temp = root.join("temp");
+        let outside = root.join("outside");
+        fs::create_dir_all(temp.join("nested")).unwrap();
+        fs::create_dir_all(&outside).unwrap();
+        fs::write(temp.join("old.tmp"), b"12345").unwrap();
+        fs::write(temp.join("nested").join("cache.log"), b"123").unwrap();
+        fs::write(outside.join("keep.log"), b"precious").unwrap();
+        (root, temp, outside)
+    }
+
+    fn options() -> CleanOptions {
+        CleanOptions {
+            exclusions: Arc::new(Exclusions::new(&[], false)),
+            ..Default::default()
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn clean_directory_does_not_follow_links() {
+        use std::os::unix::fs::symlink;
+        let (root, temp, outside) = temp_tree("links");
+        symlink(&outside, temp.join("outside-link")).unwrap();
+        symlink(&outside, temp.join("nested").join("deep-link")).unwrap();
+        symlink(outside.join("keep.log"), temp.join("file-link")).unwrap();
+
+        let options = options();
+        let mut output = ReportOutput { lines: Vec::new() };
+        let (deleted, failed, size) = clean_directory_with_output(&temp, &options, &mut output).unwrap();
+
+        assert_eq!((deleted, failed, size), (2, 0, 8));
+        assert!(outside.join("keep.log").exists());
+        assert!(!temp.join("old.tmp").exists());
+        // nested 中的链接保留下来，目录本身也就不能删除
+        assert!(!temp.join("nested").join("cache.log").exists());
+        assert!(temp.join("nested").join("deep-link").exists());
+
+        let mut skipped: Vec<PathBuf> = options.skipped_links.list().into_iter().map(|(path, _)| path).collect();
+        skipped.sort();
+        assert_eq!(
+            skipped,
+            vec![
+                temp.join("file-link"),
+                temp.join("nested").join("deep-link"),
+                temp.join("outside-link")
+            ]
+        );
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn linked_clean_root_is_refused() {
+        use std::os::unix::fs::symlink;
+        let (root, _, outside) = temp_tree("root-link");
+        let link = root.join("temp-link");
+        symlink(&outside, &link).unwrap();
+
+        let options = options();
+        let mut output = ReportOutput { lines: Vec::new() };
+        let result = clean_directory_with_output(&link, &options, &mut output).unwrap();
+        assert_eq!(result, (0, 0, 0));
+        assert!(outside.join("keep.log").exists());
+        assert_eq!(options.skipped_links.list(), vec![(link, "link")]);
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn extension_search_does_not_descend_into_links() {
+        use std::os::unix::fs::symlink;
+        let (root, temp, outside) = temp_tree("extensions");
+        symlink(&outside, temp.join("nested").join("outside-link")).unwrap();
+
+        let options = options();
+        let result =
+            clean_files_by_extension_with_progress(&temp, &["log"], &mut |_, _, _, _| {}, &options, &|| false).unwrap();
+        assert_eq!(result, (1, 0, 3));
+        assert!(outside.join("keep.log").exists());
+        assert_eq!(options.skipped_links.list().len(), 1);
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn extension_cleaning_stops_when_cancelled() {
+        let (root, temp, _) = temp_tree("extension-cancel");
+        let options = options();
+        let result =
+            clean_files_by_extension_with_progress(&temp, &["tmp", "log"], &mut |_, _, _, _| {}, &options, &|| true)
+                .unwrap();
+        assert_eq!(result, (0, 0, 0));
+        assert!(temp.join("old.tmp").exists());
+        assert!(temp.join("nested").join("cache.log").exists());
+
+        // 删除第一个文件后取消
+        let deleted = std::cell::Cell::new(0);
+        let result = clean_files_by_extension_with_progress(
+            &temp,
+            &["tmp", "log"],
+            &mut |_, count, _, _| deleted.set(count),
+            &options,
+            &|| deleted.get() >= 1,
+        )
+        .unwrap();
+        assert_eq!(result.0, 1);
+        assert!(temp.join("old.tmp").exists() != temp.join("nested").join("cache.log").exists());
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn every_target_has_a_cleaner() {
+        for target in CLEAN_TARGETS.iter() {
+            let _: Cleaner<ReportOutput> = target_cleaner(target.id);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "unknown clean target 'user-locl-temp'")]
+    fn unknown_target_has_no_cleaner() {
+        let _: Cleaner<ReportOutput> = target_cleaner("user-locl-temp");
+    }
+
+    #[test]
+    fn parses_ages() {
+        assert_eq!(parse_age("30m").unwrap(), Duration::from_secs(30 * 60));
+        assert_eq!(parse_age(" 14d ").unwrap(), Duration::from_secs(14 * 24 * 3600));
+        assert_eq!(parse_age("2w").unwrap(), Duration::from_secs(2 * 7 * 24 * 3600));
+        assert_eq!(format_age(parse_age("12h").unwrap()), "12h");
+        for invalid in ["", "d", "14", "14x", "1日", "日", "-1d", "1.5h"] {
+            assert!(parse_age(invalid).is_err(), "{:?}", invalid);
+        }
+        let error = parse_age("99999999999999999w").unwrap_err();
+        assert_eq!(error.to_string(), "Duration '99999999999999999w' is too long");
+    }
+
+    #[test]
+    fn report_lists_skipped_links() {
+        let options = options();
+        for i in 0..7 {
+            options
+                .skipped_links
+                .record(Path::new(&format!("/tmp/link{}", i)), &EntryKind::Link);
+        }
+        let mut output = ReportOutput { lines: Vec::new() };
+        print_exclusions(&options, &mut output);
+        assert_eq!(output.lines[0], "🔗 Not followed: 7 links / mount points");
+        assert_eq!(output.lines.len(), 7);
+        assert_eq!(output.lines[6], "   ... and 2 more");
+    }
+}
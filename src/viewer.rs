@@ -0,0 +1,337 @@
+// Scrollable output viewer with search, filter, save and clipboard copy
+use crate::config;
+use crate::theme::{self, theme};
+use anyhow::{Context, Result};
+use chrono::Local;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, MouseEventKind};
+use ratatui::{
+    backend::Backend,
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Terminal,
+};
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+// 过滤模式下保留的行（警告和错误）
+const PROBLEM_MARKERS: [&str; 5] = ["❌", "⚠", "error", "warning", "failed"];
+
+fn is_problem(line: &str) -> bool {
+    let lower = line.to_lowercase();
+    PROBLEM_MARKERS.iter().any(|marker| lower.contains(marker))
+}
+
+// 不区分大小写查找 query 在 line 中出现的位置（字节范围）
+fn match_ranges(line: &str, query: &str) -> Vec<(usize, usize)> {
+    let query: Vec<char> = query.chars().flat_map(char::to_lowercase).collect();
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    let chars: Vec<(usize, char)> = line.char_indices().collect();
+    let mut ranges = Vec::new();
+    let mut i = 0;
+    while i + query.len() <= chars.len() {
+        let found = (0..query.len()).all(|k| chars[i + k].1.to_lowercase().eq(std::iter::once(query[k])));
+        if found {
+            let start = chars[i].0;
+            let end = chars.get(i + query.len()).map(|(pos, _)| *pos).unwrap_or(line.len());
+            ranges.push((start, end));
+            i += query.len();
+        } else {
+            i += 1;
+        }
+    }
+    ranges
+}
+
+fn highlight_line(line: &str, query: &str) -> Line<'static> {
+    let ranges = match_ranges(line, query);
+    if ranges.is_empty() {
+        return Line::from(line.to_string());
+    }
+
+    let highlight = Style::default()
+        .fg(theme().selected_fg)
+        .bg(theme().selected_bg)
+        .add_modifier(Modifier::BOLD);
+    let mut spans = Vec::new();
+    let mut last = 0;
+    for (start, end) in ranges {
+        if start > last {
+            spans.push(Span::raw(line[last..start].to_string()));
+        }
+        spans.push(Span::styled(line[start..end].to_string(), highlight));
+        last = end;
+    }
+    if last < line.len() {
+        spans.push(Span::raw(line[last..].to_string()));
+    }
+    Line::from(spans)
+}
+
+// 保存到 <数据目录>/logs/output-YYYYmmdd-HHMMSS.log
+fn save_log(lines: &[String]) -> Result<PathBuf> {
+    let dir = config::data_dir()
+        .map(|d| d.join("logs"))
+        .ok_or_else(|| anyhow::anyhow!("Cannot determine lemo data directory"))?;
+    fs::create_dir_all(&dir).with_context(|| format!("Cannot create {}", dir.display()))?;
+
+    let path = dir.join(format!("output-{}.log", Local::now().format("%Y%m%d-%H%M%S")));
+    fs::write(&path, lines.join("\n") + "\n").with_context(|| format!("Cannot write {}", path.display()))?;
+    Ok(path)
+}
+
+fn base64_encode(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        out.push(ALPHABET[(n >> 18) as usize & 63] as char);
+        out.push(ALPHABET[(n >> 12) as usize & 63] as char);
+        out.push(if chunk.len() > 1 { ALPHABET[(n >> 6) as usize & 63] as char } else { '=' });
+        out.push(if chunk.len() > 2 { ALPHABET[n as usize & 63] as char } else { '=' });
+    }
+    out
+}
+
+// 通过 OSC 52 转义序列复制到剪贴板（支持的终端：Windows Terminal、iTerm2、kitty、tmux 等）
+fn copy_to_clipboard(text: &str) -> Result<()> {
+    let mut stdout = io::stdout();
+    write!(stdout, "\x1b]52;c;{}\x07", base64_encode(text.as_bytes()))?;
+    stdout.flush()?;
+    Ok(())
+}
+
+// 查看器状态
+#[derive(Default)]
+struct ViewerState {
+    scroll: usize,
+    // 横向滚动（关闭自动换行时使用）
+    hscroll: u16,
+    wrap: bool,
+    filter_problems: bool,
+    // 正在输入的搜索词（None 表示不在输入状态）
+    search_input: Option<String>,
+    query: String,
+    message: Option<(String, Instant)>,
+}
+
+impl ViewerState {
+    fn notify(&mut self, message: String) {
+        self.message = Some((message, Instant::now()));
+    }
+
+    fn current_message(&self) -> Option<&str> {
+        self.message
+            .as_ref()
+            .filter(|(_, at)| at.elapsed() < Duration::from_secs(5))
+            .map(|(msg, _)| msg.as_str())
+    }
+
+    // 当前显示的行（过滤后），保留原始行号
+    fn visible<'a>(&self, lines: &'a [String]) -> Vec<(usize, &'a String)> {
+        lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| !self.filter_problems || is_problem(line))
+            .collect()
+    }
+
+    // 跳转到下一个/上一个匹配行（include_current 为 true 时当前行也算）
+    fn jump(&mut self, visible: &[(usize, &String)], forward: bool, include_current: bool) {
+        if self.query.is_empty() {
+            return;
+        }
+        let matches: Vec<usize> = visible
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, line))| !match_ranges(line, &self.query).is_empty())
+            .map(|(pos, _)| pos)
+            .collect();
+        let target = if forward {
+            matches
+                .iter()
+                .find(|&&pos| pos > self.scroll || (include_current && pos == self.scroll))
+                .or(matches.first())
+        } else {
+            matches.iter().rev().find(|&&pos| pos < self.scroll).or(matches.last())
+        };
+        match target {
+            Some(&pos) => self.scroll = pos,
+            None => self.notify(format!("Pattern not found: {}", self.query)),
+        }
+    }
+}
+
+// 可滚动查看器
+pub fn show_scrollable_viewer<B: Backend>(terminal: &mut Terminal<B>, lines: &[String]) -> Result<()> {
+    let mut state = ViewerState {
+        wrap: true,
+        ..Default::default()
+    };
+
+    loop {
+        let visible = state.visible(lines);
+
+        terminal.draw(|f| {
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(0), Constraint::Length(3)])
+                .split(f.area());
+
+            // 计算可见行数
+            let visible_height = chunks[0].height.saturating_sub(2) as usize; // 减去边框
+
+            // 确保 scroll 不超过最大值
+            let max_scroll = visible.len().saturating_sub(visible_height);
+            state.scroll = state.scroll.min(max_scroll);
+
+            let shown: Vec<Line> = visible
+                .iter()
+                .skip(state.scroll)
+                .take(visible_height)
+                .map(|(_, line)| highlight_line(line, &state.query))
+                .collect();
+
+            let current_line = visible.get(state.scroll).map(|(index, _)| index + 1).unwrap_or(1);
+            let mut title = format!(" Output (Line {}/{}) ", current_line, lines.len());
+            if state.filter_problems {
+                title.push_str(&format!("[warnings/errors: {}] ", visible.len()));
+            }
+            if !state.query.is_empty() {
+                let count: usize = visible.iter().map(|(_, line)| match_ranges(line, &state.query).len()).sum();
+                title.push_str(&format!("[/{}: {} matches] ", state.query, count));
+            }
+
+            let mut paragraph = Paragraph::new(shown)
+                .block(
+                    Block::default()
+                        .title(title)
+                        .borders(Borders::ALL)
+                        .border_style(Style::default().fg(theme().accent)),
+                )
+                .style(Style::default().fg(theme().text));
+            paragraph = if state.wrap {
+                paragraph.wrap(Wrap { trim: true })
+            } else {
+                paragraph.scroll((0, state.hscroll))
+            };
+            f.render_widget(paragraph, chunks[0]);
+
+            // 底部提示（输入搜索词时显示输入框）
+            let footer_line = match (&state.search_input, state.current_message()) {
+                (Some(input), _) => Line::from(vec![
+                    Span::styled("/", Style::default().fg(theme().accent)),
+                    Span::raw(input.clone()),
+                    Span::styled("█", Style::default().fg(theme().muted)),
+                    Span::styled("  Enter: Search | Esc: Cancel", Style::default().fg(theme().muted)),
+                ]),
+                (None, Some(message)) => Line::from(Span::styled(message.to_string(), Style::default().fg(theme().title))),
+                (None, None) => Line::from(format!(
+                    "↑/↓/Wheel: Scroll | /: Search | n/N: Next/Prev | F: Errors only | W: Wrap {} | S: Save | Y: Copy | Q/Esc: Return",
+                    if state.wrap { "off" } else { "on" }
+                )),
+            };
+            let footer = Paragraph::new(footer_line)
+                .style(Style::default().fg(theme().muted))
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::ALL));
+            f.render_widget(footer, chunks[1]);
+            theme::finish_frame(f);
+        })?;
+
+        // 使用非阻塞的 poll 来检查事件，避免界面卡住
+        if !event::poll(Duration::from_millis(100))? {
+            continue;
+        }
+        let visible_height = terminal.size()?.height.saturating_sub(5) as usize;
+        let max_scroll = visible.len().saturating_sub(visible_height);
+
+        let key = match event::read()? {
+            Event::Key(key) if key.kind == KeyEventKind::Press => key,
+            Event::Mouse(mouse) => {
+                match mouse.kind {
+                    MouseEventKind::ScrollDown => state.scroll = (state.scroll + 3).min(max_scroll),
+                    MouseEventKind::ScrollUp => state.scroll = state.scroll.saturating_sub(3),
+                    _ => {}
+                }
+                continue;
+            }
+            _ => continue,
+        };
+
+        // 输入搜索词
+        if let Some(input) = state.search_input.as_mut() {
+            match key.code {
+                KeyCode::Esc => state.search_input = None,
+                KeyCode::Enter => {
+                    state.query = state.search_input.take().unwrap_or_default();
+                    state.jump(&visible, true, true);
+                }
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Char(c) => input.push(c),
+                _ => {}
+            }
+            continue;
+        }
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc | KeyCode::Enter => break,
+            KeyCode::Down | KeyCode::Char('j') => {
+                state.scroll = (state.scroll + 1).min(max_scroll);
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                state.scroll = state.scroll.saturating_sub(1);
+            }
+            KeyCode::PageDown => {
+                state.scroll = (state.scroll + visible_height).min(max_scroll);
+            }
+            KeyCode::PageUp => {
+                state.scroll = state.scroll.saturating_sub(visible_height);
+            }
+            KeyCode::Home => {
+                state.scroll = 0;
+            }
+            KeyCode::End => {
+                state.scroll = max_scroll;
+            }
+            KeyCode::Left if !state.wrap => state.hscroll = state.hscroll.saturating_sub(8),
+            KeyCode::Right if !state.wrap => state.hscroll = state.hscroll.saturating_add(8),
+            KeyCode::Char('/') => state.search_input = Some(String::new()),
+            KeyCode::Char('n') => state.jump(&visible, true, false),
+            KeyCode::Char('N') => state.jump(&visible, false, false),
+            KeyCode::Char('f') => {
+                state.filter_problems = !state.filter_problems;
+                state.scroll = 0;
+            }
+            KeyCode::Char('w') => {
+                state.wrap = !state.wrap;
+                state.hscroll = 0;
+            }
+            KeyCode::Char('s') => match save_log(lines) {
+                Ok(path) => state.notify(format!("💾 Saved to {}", path.display())),
+                Err(e) => state.notify(format!("❌ Failed to save: {}", e)),
+            },
+            KeyCode::Char('y') => {
+                // 过滤模式下只复制显示的行
+                let text: Vec<&str> = visible.iter().map(|(_, line)| line.as_str()).collect();
+                match copy_to_clipboard(&text.join("\n")) {
+                    Ok(()) => state.notify(format!("📋 Copied {} lines to clipboard (OSC 52)", text.len())),
+                    Err(e) => state.notify(format!("❌ Failed to copy: {}", e)),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    terminal.clear()?;
+    Ok(())
+}
@@ -0,0 +1,216 @@
+// Link-aware filesystem checks for the cleaner
+//
+// 清理时从不跟随符号链接、Windows junction 和挂载点（所有重解析点），默认也不进入其他文件系统，
+// 避免临时目录中的链接把删除范围引到别处。被跳过的路径记录下来，在清理结束时报告。
+use std::fs::{self, Metadata};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+pub enum EntryKind {
+    File(u64),
+    Dir,
+    // 符号链接、junction 或其他重解析点
+    Link,
+    // 位于另一个文件系统（挂载点）
+    OtherFilesystem,
+}
+
+#[cfg(unix)]
+fn device_id(metadata: &Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    Some(metadata.dev())
+}
+
+// Windows 上挂载点本身就是重解析点，按链接处理即可
+#[cfg(not(unix))]
+fn device_id(_metadata: &Metadata) -> Option<u64> {
+    None
+}
+
+#[cfg(windows)]
+fn is_reparse_point(metadata: &Metadata) -> bool {
+    use std::os::windows::fs::MetadataExt;
+    const FILE_ATTRIBUTE_REPARSE_POINT: u32 = 0x400;
+    metadata.file_attributes() & FILE_ATTRIBUTE_REPARSE_POINT != 0
+}
+
+#[cfg(not(windows))]
+fn is_reparse_point(_metadata: &Metadata) -> bool {
+    false
+}
+
+fn is_link_metadata(metadata: &Metadata) -> bool {
+    metadata.file_type().is_symlink() || is_reparse_point(metadata)
+}
+
+// 路径本身（最后一级）是链接
+pub fn is_link(path: &Path) -> bool {
+    fs::symlink_metadata(path).is_ok_and(|m| is_link_metadata(&m))
+}
+
+// 路径是否位于某个驱动器（挂载点）所在的文件系统上
+#[cfg(unix)]
+pub fn on_drive(path: &Path, drive: &str) -> bool {
+    match (fs::metadata(path), fs::metadata(drive)) {
+        (Ok(path), Ok(drive)) => device_id(&path) == device_id(&drive),
+        _ => false,
+    }
+}
+
+// Windows：比较盘符（其他卷的挂载点是重解析点，清理时不会进入）
+#[cfg(not(unix))]
+pub fn on_drive(path: &Path, drive: &str) -> bool {
+    use std::path::{Component, Prefix};
+    let Some(Component::Prefix(prefix)) = path.components().next() else {
+        return false;
+    };
+    let root = match prefix.kind() {
+        Prefix::Disk(letter) | Prefix::VerbatimDisk(letter) => format!("{}:", letter as char),
+        _ => prefix.as_os_str().to_string_lossy().to_string(),
+    };
+    crate::policy::same_mount(&root, drive)
+}
+
+// 扫描边界：起始目录所在的文件系统，以及是否允许进入其他文件系统
+#[derive(Clone, Copy)]
+pub struct Boundary {
+    device: Option<u64>,
+    cross_filesystems: bool,
+}
+
+impl Boundary {
+    pub fn of(root: &Path, cross_filesystems: bool) -> Self {
+        Self {
+            device: fs::metadata(root).ok().and_then(|m| device_id(&m)),
+            cross_filesystems,
+        }
+    }
+
+    // 不跟随链接地判断目录项类型
+    pub fn classify(&self, path: &Path) -> io::Result<EntryKind> {
+        let metadata = fs::symlink_metadata(path)?;
+        if is_link_metadata(&metadata) {
+            return Ok(EntryKind::Link);
+        }
+        if !self.cross_filesystems && self.device.is_some() && device_id(&metadata) != self.device {
+            return Ok(EntryKind::OtherFilesystem);
+        }
+        if metadata.is_dir() {
+            Ok(EntryKind::Dir)
+        } else {
+            Ok(EntryKind::File(metadata.len()))
+        }
+    }
+}
+
+// 清理过程中跳过的链接和挂载点
+#[derive(Debug, Default)]
+pub struct SkippedLinks {
+    paths: Mutex<Vec<(PathBuf, &'static str)>>,
+}
+
+impl SkippedLinks {
+    pub fn record(&self, path: &Path, kind: &EntryKind) {
+        let reason = match kind {
+            EntryKind::OtherFilesystem => "other filesystem",
+            _ => "link",
+        };
+        if let Ok(mut paths) = self.paths.lock() {
+            paths.push((path.to_path_buf(), reason));
+        }
+    }
+
+    pub fn list(&self) -> Vec<(PathBuf, &'static str)> {
+        self.paths.lock().map(|p| p.clone()).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::process;
+
+    fn temp_tree(name: &str) -> PathBuf {
+        let root = env::temp_dir().join(format!("lemo-walk-{}-{}", name, process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("dir")).unwrap();
+        fs::write(root.join("file.txt"), b"hello").unwrap();
+        root
+    }
+
+    #[test]
+    fn classifies_files_and_dirs() {
+        let root = temp_tree("classify");
+        let boundary = Boundary::of(&root, false);
+        assert!(matches!(boundary.classify(&root.join("file.txt")).unwrap(), EntryKind::File(5)));
+        assert!(matches!(boundary.classify(&root.join("dir")).unwrap(), EntryKind::Dir));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks_are_links_not_targets() {
+        use std::os::unix::fs::symlink;
+        let root = temp_tree("symlink");
+        symlink(root.join("dir"), root.join("dir-link")).unwrap();
+        symlink(root.join("file.txt"), root.join("file-link")).unwrap();
+        symlink(root.join("missing"), root.join("dangling")).unwrap();
+
+        let boundary = Boundary::of(&root, true);
+        for name in ["dir-link", "file-link", "dangling"] {
+            assert!(matches!(boundary.classify(&root.join(name)).unwrap(), EntryKind::Link), "{}", name);
+            assert!(is_link(&root.join(name)));
+        }
+        assert!(!is_link(&root.join("dir")));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn other_filesystems_need_permission() {
+        let root = temp_tree("device");
+        // 假装起始目录在另一个设备上：目录中的所有项都属于"其他文件系统"
+        let elsewhere = Boundary {
+            device: Some(u64::MAX),
+            cross_filesystems: false,
+        };
+        assert!(matches!(elsewhere.classify(&root.join("dir")).unwrap(), EntryKind::OtherFilesystem));
+        assert!(matches!(elsewhere.classify(&root.join("file.txt")).unwrap(), EntryKind::OtherFilesystem));
+
+        let allowed = Boundary {
+            cross_filesystems: true,
+            ..elsewhere
+        };
+        assert!(matches!(allowed.classify(&root.join("dir")).unwrap(), EntryKind::Dir));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn paths_on_drive_share_its_filesystem() {
+        let root = temp_tree("drive");
+        let drive = root.to_string_lossy().to_string();
+        assert!(on_drive(&root.join("file.txt"), &drive));
+        assert!(on_drive(&root.join("dir"), &drive));
+        // /proc 总是单独的文件系统
+        assert!(!on_drive(&root.join("file.txt"), "/proc"));
+        assert!(!on_drive(&root.join("missing"), &drive));
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn records_skipped_paths() {
+        let skipped = SkippedLinks::default();
+        skipped.record(Path::new("/tmp/a"), &EntryKind::Link);
+        skipped.record(Path::new("/mnt/b"), &EntryKind::OtherFilesystem);
+        assert_eq!(
+            skipped.list(),
+            vec![
+                (PathBuf::from("/tmp/a"), "link"),
+                (PathBuf::from("/mnt/b"), "other filesystem")
+            ]
+        );
+    }
+}